@@ -22,7 +22,7 @@ pub enum Node {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
-    IntegerLiteral(i32),
+    IntegerLiteral(i64),
     FloatLiteral(f64),
     BooleanLiteral(bool),
     StringLiteral(String),
@@ -55,6 +55,48 @@ pub enum Expr {
         property: Box<Expression>,
         new_value: Box<Expression>,
     },
+    // `target::start..end`, the characters from `start` up to `end`
+    Slice {
+        target: Box<Expression>,
+        start: Box<Expression>,
+        end: Box<Expression>,
+    },
+    // "text {value} text", the text between interpolations is kept as
+    // StringLiteral parts
+    StringInterpolation {
+        parts: Vec<Expression>,
+    },
+    Call {
+        callee: Box<Expression>,
+        arguments: Vec<Box<Expression>>,
+    },
+    Cast {
+        value: Box<Expression>,
+        target: Box<TypeAnnotation>,
+    },
+    StructLiteral {
+        struct_type: Box<TypeAnnotation>,
+        fields: Vec<StructLiteralField>,
+    },
+    FieldAccess {
+        target: Box<Expression>,
+        field: Box<Expression>,
+    },
+    FieldAssignment {
+        target: Box<Expression>,
+        field: Box<Expression>,
+        new_value: Box<Expression>,
+    },
+    // `Name::Variant` or `Name::Variant(values)`
+    EnumVariant {
+        enum_type: Box<TypeAnnotation>,
+        variant: Box<Expression>,
+        arguments: Vec<Box<Expression>>,
+    },
+    Match {
+        subject: Box<Expression>,
+        arms: Vec<MatchExprArm>,
+    },
 }
 
 impl Display for Expression {
@@ -111,6 +153,83 @@ impl Display for Expr {
             } => {
                 write!(f, "{}::{} = {}", target, property, new_value)
             }
+            Expr::Slice { target, start, end } => {
+                write!(f, "{}::{}..{}", target, start, end)
+            }
+            Expr::StringInterpolation { parts } => {
+                write!(f, "\"")?;
+
+                for part in parts {
+                    match &part.node {
+                        Expr::StringLiteral(s) => write!(f, "{}", s)?,
+                        other => write!(f, "{{{}}}", other)?,
+                    }
+                }
+
+                write!(f, "\"")
+            }
+            Expr::Call { callee, arguments } => {
+                let args = arguments
+                    .iter()
+                    .map(|a| format!("{}", a))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{}({})", callee, args)
+            }
+            Expr::Cast { value, target } => {
+                write!(f, "({} as {})", value, target)
+            }
+            Expr::StructLiteral {
+                struct_type,
+                fields,
+            } => {
+                let fields = fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.identifier, field.value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{} {{ {} }}", struct_type, fields)
+            }
+            Expr::FieldAccess { target, field } => {
+                write!(f, "{}.{}", target, field)
+            }
+            Expr::FieldAssignment {
+                target,
+                field,
+                new_value,
+            } => {
+                write!(f, "{}.{} = {}", target, field, new_value)
+            }
+            Expr::EnumVariant {
+                enum_type,
+                variant,
+                arguments,
+            } => {
+                write!(f, "{}::{}", enum_type, variant)?;
+
+                if !arguments.is_empty() {
+                    let args = arguments
+                        .iter()
+                        .map(|a| format!("{}", a))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    write!(f, "({})", args)?;
+                }
+
+                Ok(())
+            }
+            Expr::Match { subject, arms } => {
+                let arms = arms
+                    .iter()
+                    .map(|arm| format!("{} => {}", arm.pattern.node, arm.value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "match {} {{ {} }}", subject, arms)
+            }
         }
     }
 }
@@ -135,6 +254,18 @@ pub enum Stmt {
         identifier: Expression,
         value: TypeAnnotation,
     },
+    StructDeclaration {
+        identifier: Expression,
+        fields: Vec<StructField>,
+    },
+    EnumDeclaration {
+        identifier: Expression,
+        variants: Vec<EnumVariant>,
+    },
+    Match {
+        subject: Expression,
+        arms: Vec<MatchArm>,
+    },
     StdoutLog {
         log_value: Expression,
     },
@@ -165,10 +296,98 @@ pub enum Stmt {
         steps: Option<Expression>,
         body: Box<Statement>,
     },
+    FunctionDeclaration {
+        identifier: Expression,
+        parameters: Vec<FunctionParameter>,
+        return_type: Option<TypeAnnotation>,
+        body: Box<Statement>,
+    },
+    Return {
+        value: Option<Expression>,
+    },
     Continue,
     Break,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionParameter {
+    pub identifier: Expression,
+    pub annotated_type: TypeAnnotation,
+    pub mutable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    pub identifier: Expression,
+    pub annotated_type: TypeAnnotation,
+}
+
+// A `field: value` pair inside a struct literal
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLiteralField {
+    pub identifier: Expression,
+    pub value: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    pub identifier: Expression,
+    pub payload: Vec<TypeAnnotation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    // `Name::Variant(a, b)`, a binding named `_` ignores its value
+    Variant {
+        enum_type: TypeAnnotation,
+        variant: Expression,
+        bindings: Vec<Expression>,
+    },
+    // `_` matches every value
+    Wildcard,
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Pattern::Variant {
+                enum_type,
+                variant,
+                bindings,
+            } => {
+                write!(f, "{}::{}", enum_type, variant)?;
+
+                if !bindings.is_empty() {
+                    let bindings = bindings
+                        .iter()
+                        .map(|b| format!("{}", b))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    write!(f, "({})", bindings)?;
+                }
+
+                Ok(())
+            }
+            Pattern::Wildcard => write!(f, "_"),
+        }
+    }
+}
+
+// An arm of a `match` statement, its body runs for its side effects
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Spanned<Pattern>,
+    pub body: Box<Statement>,
+}
+
+// An arm of a `match` expression, its value is the value of the match
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchExprArm {
+    pub pattern: Spanned<Pattern>,
+    pub value: Expression,
+}
+
 impl Expr {
     pub fn spanned(self, span: Span) -> Spanned<Self> {
         Spanned { node: self, span }
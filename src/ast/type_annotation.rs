@@ -15,6 +15,18 @@ pub enum TypeAnnotation {
     BooleanType,
     ArrayType(Box<TypeAnnotation>, Expression),
     SequenceType(Box<TypeAnnotation>),
+    // `T?`, either a T or nil
+    OptionalType(Box<TypeAnnotation>),
+    // Fields are kept in declaration order, which is also their layout
+    StructType {
+        name: String,
+        fields: Vec<(String, TypeAnnotation)>,
+    },
+    // Variants are kept in declaration order, a variant's position is its tag
+    EnumType {
+        name: String,
+        variants: Vec<(String, Vec<TypeAnnotation>)>,
+    },
 }
 
 impl Display for TypeAnnotation {
@@ -27,6 +39,10 @@ impl Display for TypeAnnotation {
 
             TypeAnnotation::ArrayType(ta, s) => write!(f, "[{}]{}", s, ta),
             TypeAnnotation::SequenceType(ta) => write!(f, "[]{}", ta),
+            TypeAnnotation::OptionalType(ta) => write!(f, "{}?", ta),
+            TypeAnnotation::StructType { name, .. } | TypeAnnotation::EnumType { name, .. } => {
+                write!(f, "{}", name)
+            }
         }
     }
 }
@@ -21,6 +21,20 @@ macro_rules! define_opcodes {
             )*
         }
 
+        impl OpCode {
+            /*
+             * Decodes an opcode byte without panicking on unknown values
+             * */
+            pub fn from_byte(byte: u8) -> Option<OpCode> {
+                match byte {
+                    $(
+                        $value => Some(OpCode::$variant),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+
         impl ToOpcode for u8 {
             fn to_opcode(self) -> OpCode {
                 match self {
@@ -47,6 +61,9 @@ define_opcodes! {
     LoadNil, LOAD_NIL = 0x06,
     LoadTrue, LOAD_TRUE = 0x07,
     LoadFalse, LOAD_FALSE = 0x08,
+    LoadConstIntWide, LOAD_CONST_INT_WIDE = 0x09,
+    LoadConstFloatWide, LOAD_CONST_FLOAT_WIDE = 0x0A,
+    LoadStringWide, LOAD_STRING_WIDE = 0x0B,
 
     AddInt, ADD_INT = 0x10,
     SubtractInt, SUBTRACT_INT = 0x11,
@@ -61,10 +78,16 @@ define_opcodes! {
     ExponentFloat, EXPONENT_FLOAT = 0x19,
 
     ConcatString, CONCAT_STRING = 0x1A,
+    StringLength, STRING_LENGTH = 0x1B,
+    StringIndex, STRING_INDEX = 0x1C,
+    StringSlice, STRING_SLICE = 0x1D,
+    ToString, TO_STRING = 0x1E,
 
     NegateInt, NEGATE_INT = 0x20,
     NegateFloat, NEGATE_FLOAT = 0x21,
     Not, NOT = 0x22,
+    IntToFloat, INT_TO_FLOAT = 0x23,
+    FloatToInt, FLOAT_TO_INT = 0x24,
 
     LessInt, LESS_INT = 0x30,
     LessEqualInt, LESS_EQUAL_INT = 0x31,
@@ -79,8 +102,17 @@ define_opcodes! {
     Equal, EQUAL = 0x38,
     NotEqual, NOT_EQUAL = 0x39,
 
+    LessString, LESS_STRING = 0x3A,
+    LessEqualString, LESS_EQUAL_STRING = 0x3B,
+    GreaterString, GREATER_STRING = 0x3C,
+    GreaterEqualString, GREATER_EQUAL_STRING = 0x3D,
+
     StoreGlobal, STORE_GLOBAL = 0x40,
     LoadGlobal, LOAD_GLOBAL = 0x41,
+    Spill, SPILL = 0x42,
+    Reload, RELOAD = 0x43,
+    StoreGlobalWide, STORE_GLOBAL_WIDE = 0x44,
+    LoadGlobalWide, LOAD_GLOBAL_WIDE = 0x45,
 
     Move, MOVE = 0x50,
     LogAddr, LOG_ADDR = 0x51,
@@ -93,6 +125,26 @@ define_opcodes! {
     ArraySetReg, ARRAY_SET_REG = 0x57,
     ArrayGet, ARRAY_GET = 0x58,
     ArrayPush, ARRAY_PUSH = 0x59,
+
+    Call, CALL = 0x5A,
+    Return, RETURN = 0x5B,
+
+    // 4-byte target variants, emitted once a program outgrows 64 KiB
+    JumpIfFalseWide, JUMP_IF_FALSE_WIDE = 0x5C,
+    JumpUncondWide, JUMP_UNCOND_WIDE = 0x5D,
+    CallWide, CALL_WIDE = 0x5E,
+
+    CallNative, CALL_NATIVE = 0x5F,
+
+    StructNew, STRUCT_NEW = 0x60,
+    StructGet, STRUCT_GET = 0x61,
+    StructSet, STRUCT_SET = 0x62,
+
+    EnumNew, ENUM_NEW = 0x63,
+    EnumTag, ENUM_TAG = 0x64,
+
+    CheckStep, CHECK_STEP = 0x65,
+    Freeze, FREEZE = 0x66,
 }
 
 impl From<OpCode> for u8 {
@@ -111,10 +163,12 @@ impl fmt::Display for OpCode {
             OpCode::ExponentInt | OpCode::ExponentFloat => "^",
             OpCode::NegateInt | OpCode::NegateFloat => "-",
             OpCode::Not => "not",
-            OpCode::LessInt | OpCode::LessFloat => "<",
-            OpCode::LessEqualInt | OpCode::LessEqualFloat => "<=",
-            OpCode::GreaterInt | OpCode::GreaterFloat => ">",
-            OpCode::GreaterEqualInt | OpCode::GreaterEqualFloat => ">=",
+            OpCode::LessInt | OpCode::LessFloat | OpCode::LessString => "<",
+            OpCode::LessEqualInt | OpCode::LessEqualFloat | OpCode::LessEqualString => "<=",
+            OpCode::GreaterInt | OpCode::GreaterFloat | OpCode::GreaterString => ">",
+            OpCode::GreaterEqualInt | OpCode::GreaterEqualFloat | OpCode::GreaterEqualString => {
+                ">="
+            }
             OpCode::Equal => "==",
             OpCode::NotEqual => "!=",
             OpCode::ConcatString => "+",
@@ -199,6 +253,18 @@ impl OpCode {
                 name: "LOAD_STRING",
                 operands_width: vec![1, 2],
             },
+            OpCode::LoadConstIntWide => Definition {
+                name: "LOAD_CONST_INT_WIDE",
+                operands_width: vec![1, 4],
+            },
+            OpCode::LoadConstFloatWide => Definition {
+                name: "LOAD_CONST_FLOAT_WIDE",
+                operands_width: vec![1, 4],
+            },
+            OpCode::LoadStringWide => Definition {
+                name: "LOAD_STRING_WIDE",
+                operands_width: vec![1, 4],
+            },
             OpCode::LoadNil => Definition {
                 name: "LOAD_NIL",
                 operands_width: vec![1],
@@ -255,6 +321,22 @@ impl OpCode {
                 name: "CONCAT_STRING",
                 operands_width: vec![1, 1, 1],
             },
+            OpCode::StringLength => Definition {
+                name: "STRING_LENGTH",
+                operands_width: vec![1, 1],
+            },
+            OpCode::StringIndex => Definition {
+                name: "STRING_INDEX",
+                operands_width: vec![1, 1, 1],
+            },
+            OpCode::StringSlice => Definition {
+                name: "STRING_SLICE",
+                operands_width: vec![1, 1, 1, 1],
+            },
+            OpCode::ToString => Definition {
+                name: "TO_STRING",
+                operands_width: vec![1, 1],
+            },
             OpCode::NegateInt => Definition {
                 name: "NEGATE_INT",
                 operands_width: vec![1, 1],
@@ -267,6 +349,14 @@ impl OpCode {
                 name: "NOT",
                 operands_width: vec![1, 1],
             },
+            OpCode::IntToFloat => Definition {
+                name: "INT_TO_FLOAT",
+                operands_width: vec![1, 1],
+            },
+            OpCode::FloatToInt => Definition {
+                name: "FLOAT_TO_INT",
+                operands_width: vec![1, 1],
+            },
             OpCode::LessInt => Definition {
                 name: "LESS_INT",
                 operands_width: vec![1, 1, 1],
@@ -307,14 +397,46 @@ impl OpCode {
                 name: "NOT_EQUAL",
                 operands_width: vec![1, 1, 1],
             },
+            OpCode::LessString => Definition {
+                name: "LESS_STRING",
+                operands_width: vec![1, 1, 1],
+            },
+            OpCode::LessEqualString => Definition {
+                name: "LESS_EQUAL_STRING",
+                operands_width: vec![1, 1, 1],
+            },
+            OpCode::GreaterString => Definition {
+                name: "GREATER_STRING",
+                operands_width: vec![1, 1, 1],
+            },
+            OpCode::GreaterEqualString => Definition {
+                name: "GREATER_EQUAL_STRING",
+                operands_width: vec![1, 1, 1],
+            },
             OpCode::StoreGlobal => Definition {
                 name: "STORE_GLOBAL",
-                operands_width: vec![1, 2],
+                operands_width: vec![2, 1],
             },
             OpCode::LoadGlobal => Definition {
                 name: "LOAD_GLOBAL",
                 operands_width: vec![1, 2],
             },
+            OpCode::StoreGlobalWide => Definition {
+                name: "STORE_GLOBAL_WIDE",
+                operands_width: vec![4, 1],
+            },
+            OpCode::LoadGlobalWide => Definition {
+                name: "LOAD_GLOBAL_WIDE",
+                operands_width: vec![1, 4],
+            },
+            OpCode::Spill => Definition {
+                name: "SPILL",
+                operands_width: vec![2, 1], // slot, src_reg
+            },
+            OpCode::Reload => Definition {
+                name: "RELOAD",
+                operands_width: vec![1, 2], // dest_reg, slot
+            },
             OpCode::Move => Definition {
                 name: "MOVE",
                 operands_width: vec![1, 1],
@@ -355,6 +477,58 @@ impl OpCode {
                 name: "ARRAY_PUSH",
                 operands_width: vec![1, 1], // array_reg, value_reg
             },
+            OpCode::Call => Definition {
+                name: "CALL",
+                operands_width: vec![1, 2, 1, 1], // dest_reg, target, window, argc
+            },
+            OpCode::Return => Definition {
+                name: "RETURN",
+                operands_width: vec![1], // value_reg
+            },
+            OpCode::JumpIfFalseWide => Definition {
+                name: "JUMP_IF_FALSE_WIDE",
+                operands_width: vec![1, 4],
+            },
+            OpCode::JumpUncondWide => Definition {
+                name: "JUMP_UNCOND_WIDE",
+                operands_width: vec![4],
+            },
+            OpCode::StructNew => Definition {
+                name: "STRUCT_NEW",
+                operands_width: vec![1, 4, 1], // dest_reg, layout string, field count
+            },
+            OpCode::StructGet => Definition {
+                name: "STRUCT_GET",
+                operands_width: vec![1, 1, 1], // dest_reg, struct_reg, field
+            },
+            OpCode::StructSet => Definition {
+                name: "STRUCT_SET",
+                operands_width: vec![1, 1, 1], // struct_reg, field, value_reg
+            },
+            OpCode::EnumNew => Definition {
+                name: "ENUM_NEW",
+                operands_width: vec![1, 4, 1, 1], // dest_reg, name string, tag, payload size
+            },
+            OpCode::EnumTag => Definition {
+                name: "ENUM_TAG",
+                operands_width: vec![1, 1], // dest_reg, enum_reg
+            },
+            OpCode::CheckStep => Definition {
+                name: "CHECK_STEP",
+                operands_width: vec![1], // step_reg
+            },
+            OpCode::Freeze => Definition {
+                name: "FREEZE",
+                operands_width: vec![1], // value_reg
+            },
+            OpCode::CallWide => Definition {
+                name: "CALL_WIDE",
+                operands_width: vec![1, 4, 1, 1], // dest_reg, target, window, argc
+            },
+            OpCode::CallNative => Definition {
+                name: "CALL_NATIVE",
+                operands_width: vec![1, 2, 1, 1], // dest_reg, native index, window, argc
+            },
         }
     }
 }
@@ -1 +1,3 @@
 pub mod bytecode;
+pub mod verifier;
+pub mod vync;
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+
+use crate::{
+    bytecode::bytecode::{OpCode, read_uint8, read_uint16, read_uint32},
+    compiler::compiler::Bytecode,
+    error_handler::errors::VynError,
+    runtime_value::values::RuntimeValue,
+    vyn_vm::{
+        natives::NATIVES,
+        vm::{MAX_ARRAY_LENGTH, MAX_GLOBALS, MAX_REGISTERS, MAX_SPILL_SLOTS},
+    },
+};
+
+/*
+ * What an instruction operand refers to, used to range-check it
+ * */
+#[derive(Clone, Copy)]
+enum Operand {
+    Register,
+    Constant,
+    String,
+    JumpTarget,
+    Native,
+    // Element count of a new array or sequence
+    Length,
+    Global,
+    Slot,
+    // Indices into heap objects, argument counts...
+    Literal,
+}
+
+impl Bytecode {
+    /*
+     * Checks that the bytecode is safe to hand to the VM
+     *
+     * Anything the VM would index with an operand without checking it is
+     * validated here first:
+     * - every opcode byte is known and its operands fit in the stream
+     * - register operands are below MAX_REGISTERS
+     * - constant and string operands point into their tables
+     * - jump and call targets land on an instruction boundary
+     * - native calls name an existing native and pass all of its arguments
+     * - array lengths, global indices and spill slots stay within the
+     *   limits the compiler keeps to, so nothing allocates without bound
+     * - execution cannot run past the last instruction
+     *
+     * What registers hold is only known at runtime, so the VM checks value
+     * types and STRUCT_GET/STRUCT_SET field literals itself and reports a
+     * mismatch as InvalidBytecode. ARRAY_SET literal indices are bounds
+     * checked like any other index and ENUM_NEW payload counts fit in a byte
+     *
+     * -- Arguments: [&self]
+     * -- Return value: Ok(()) if the bytecode is valid,
+     *                  Err(VynError::InvalidBytecode) for the first problem found
+     * */
+    pub fn verify(&self) -> Result<(), VynError> {
+        let boundaries = self.decode_boundaries()?;
+
+        for &offset in &boundaries.ordered {
+            self.verify_instruction(offset, &boundaries.set)?;
+        }
+
+        let Some(&last) = boundaries.ordered.last() else {
+            return Err(self.invalid(0, "program contains no instructions"));
+        };
+
+        // The dispatch loop has no end-of-stream check, so the last
+        // instruction must never fall through
+        let last_opcode = OpCode::from_byte(self.instructions[last]).unwrap();
+        if !matches!(
+            last_opcode,
+            OpCode::Halt | OpCode::Return | OpCode::JumpUncond | OpCode::JumpUncondWide
+        ) {
+            return Err(self.invalid(last, "execution can run past the last instruction"));
+        }
+
+        for (idx, constant) in self.constants.iter().enumerate() {
+            match constant {
+                RuntimeValue::StringLiteral(string_idx) if *string_idx >= self.string_table.len() => {
+                    return Err(self.invalid(
+                        0,
+                        &format!("constant {idx} points to missing string {string_idx}"),
+                    ));
+                }
+                RuntimeValue::ArrayLiteral(_) | RuntimeValue::SequenceLiteral(_) => {
+                    return Err(self.invalid(0, &format!("constant {idx} is a heap value")));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Walks the instruction stream once, recording where each instruction starts
+     * */
+    fn decode_boundaries(&self) -> Result<Boundaries, VynError> {
+        let mut ordered = Vec::new();
+        let mut offset = 0;
+
+        while offset < self.instructions.len() {
+            let byte = self.instructions[offset];
+            let Some(opcode) = OpCode::from_byte(byte) else {
+                return Err(self.invalid(offset, &format!("unknown opcode {byte:#04x}")));
+            };
+
+            let width: usize = OpCode::get_definition(opcode).operands_width.iter().sum();
+            if offset + 1 + width > self.instructions.len() {
+                return Err(self.invalid(
+                    offset,
+                    &format!("operands of {opcode:?} run past the end of the program"),
+                ));
+            }
+
+            ordered.push(offset);
+            offset += 1 + width;
+        }
+
+        let set = ordered.iter().copied().collect();
+        Ok(Boundaries { ordered, set })
+    }
+
+    fn verify_instruction(&self, offset: usize, boundaries: &HashSet<usize>) -> Result<(), VynError> {
+        let opcode = OpCode::from_byte(self.instructions[offset]).unwrap();
+        let definition = OpCode::get_definition(opcode);
+        let kinds = operand_kinds(opcode);
+
+        let mut operand_offset = offset + 1;
+        let mut values = Vec::with_capacity(kinds.len());
+
+        for (&width, &kind) in definition.operands_width.iter().zip(kinds) {
+            let value = match width {
+                1 => read_uint8(&self.instructions, operand_offset) as usize,
+                2 => read_uint16(&self.instructions, operand_offset) as usize,
+                4 => read_uint32(&self.instructions, operand_offset) as usize,
+                _ => return Err(self.invalid(offset, &format!("unsupported operand width {width}"))),
+            };
+            operand_offset += width;
+            values.push(value);
+
+            match kind {
+                Operand::Register if value >= MAX_REGISTERS as usize => {
+                    return Err(self.invalid(offset, &format!("register r{value} does not exist")));
+                }
+                Operand::Constant if value >= self.constants.len() => {
+                    return Err(self.invalid(offset, &format!("constant {value} does not exist")));
+                }
+                Operand::String if value >= self.string_table.len() => {
+                    return Err(self.invalid(offset, &format!("string {value} does not exist")));
+                }
+                Operand::Native if value >= NATIVES.len() => {
+                    return Err(self.invalid(offset, &format!("native {value} does not exist")));
+                }
+                Operand::Length if value > MAX_ARRAY_LENGTH => {
+                    return Err(self.invalid(offset, &format!("array length {value} is too large")));
+                }
+                Operand::Global if value >= MAX_GLOBALS => {
+                    return Err(self.invalid(offset, &format!("global {value} does not exist")));
+                }
+                Operand::Slot if value >= MAX_SPILL_SLOTS => {
+                    return Err(self.invalid(offset, &format!("slot {value} does not exist")));
+                }
+                Operand::JumpTarget if !boundaries.contains(&value) => {
+                    return Err(self.invalid(
+                        offset,
+                        &format!("target {value:#06x} is not the start of an instruction"),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        match opcode {
+            OpCode::LoadConstInt | OpCode::LoadConstIntWide
+                if !matches!(self.constants[values[1]], RuntimeValue::IntegerLiteral(_)) =>
+            {
+                Err(self.invalid(offset, "LOAD_CONST_INT must load an integer constant"))
+            }
+            OpCode::LoadConstFloat | OpCode::LoadConstFloatWide
+                if !matches!(self.constants[values[1]], RuntimeValue::FloatLiteral(_)) =>
+            {
+                Err(self.invalid(offset, "LOAD_CONST_FLOAT must load a float constant"))
+            }
+            // Arguments are copied into window..window+argc
+            OpCode::Call | OpCode::CallWide | OpCode::CallNative
+                if values[2] + values[3] > MAX_REGISTERS as usize =>
+            {
+                Err(self.invalid(offset, "call arguments overflow the register window"))
+            }
+            // Natives index their arguments without checking how many came
+            OpCode::CallNative if values[3] != NATIVES[values[1]].parameters.len() => {
+                Err(self.invalid(offset, "native call passes the wrong number of arguments"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn invalid(&self, offset: usize, reason: &str) -> VynError {
+        VynError::InvalidBytecode {
+            offset,
+            reason: reason.to_string(),
+            span: self.debug_info.get_span(offset),
+        }
+    }
+}
+
+struct Boundaries {
+    ordered: Vec<usize>,
+    set: HashSet<usize>,
+}
+
+fn operand_kinds(opcode: OpCode) -> &'static [Operand] {
+    use Operand::*;
+
+    match opcode {
+        OpCode::Halt => &[],
+
+        OpCode::LoadConstInt
+        | OpCode::LoadConstFloat
+        | OpCode::LoadConstIntWide
+        | OpCode::LoadConstFloatWide => &[Register, Constant],
+        OpCode::LoadString | OpCode::LoadStringWide => &[Register, String],
+        OpCode::LoadNil | OpCode::LoadTrue | OpCode::LoadFalse => &[Register],
+
+        OpCode::AddInt
+        | OpCode::SubtractInt
+        | OpCode::MultiplyInt
+        | OpCode::DivideInt
+        | OpCode::ExponentInt
+        | OpCode::AddFloat
+        | OpCode::SubtractFloat
+        | OpCode::MultiplyFloat
+        | OpCode::DivideFloat
+        | OpCode::ExponentFloat
+        | OpCode::ConcatString
+        | OpCode::LessInt
+        | OpCode::LessEqualInt
+        | OpCode::GreaterInt
+        | OpCode::GreaterEqualInt
+        | OpCode::LessFloat
+        | OpCode::LessEqualFloat
+        | OpCode::GreaterFloat
+        | OpCode::GreaterEqualFloat
+        | OpCode::LessString
+        | OpCode::LessEqualString
+        | OpCode::GreaterString
+        | OpCode::GreaterEqualString
+        | OpCode::Equal
+        | OpCode::NotEqual
+        | OpCode::StringIndex => &[Register, Register, Register],
+
+        OpCode::NegateInt
+        | OpCode::NegateFloat
+        | OpCode::Not
+        | OpCode::IntToFloat
+        | OpCode::FloatToInt
+        | OpCode::StringLength
+        | OpCode::ToString
+        | OpCode::Move => {
+            &[Register, Register]
+        }
+        OpCode::StringSlice => &[Register, Register, Register, Register],
+
+        OpCode::StoreGlobal | OpCode::StoreGlobalWide => &[Global, Register],
+        OpCode::LoadGlobal | OpCode::LoadGlobalWide => &[Register, Global],
+        OpCode::Spill => &[Slot, Register],
+        OpCode::Reload => &[Register, Slot],
+
+        OpCode::LogAddr => &[Register],
+        OpCode::JumpIfFalse | OpCode::JumpIfFalseWide => &[Register, JumpTarget],
+        OpCode::JumpUncond | OpCode::JumpUncondWide => &[JumpTarget],
+
+        OpCode::ArrayNewFixed | OpCode::ArrayNewDynamic => &[Register, Length],
+        OpCode::ArraySet => &[Register, Literal, Register],
+        OpCode::ArraySetReg | OpCode::ArrayGet => &[Register, Register, Register],
+        OpCode::ArrayPush => &[Register, Register],
+
+        OpCode::StructNew => &[Register, String, Literal],
+        OpCode::StructGet => &[Register, Register, Literal],
+        OpCode::StructSet => &[Register, Literal, Register],
+
+        OpCode::EnumNew => &[Register, String, Literal, Literal],
+        OpCode::EnumTag => &[Register, Register],
+
+        OpCode::CheckStep | OpCode::Freeze => &[Register],
+
+        OpCode::Call | OpCode::CallWide => &[Register, JumpTarget, Register, Literal],
+        OpCode::CallNative => &[Register, Native, Register, Literal],
+        OpCode::Return => &[Register],
+    }
+}
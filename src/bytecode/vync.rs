@@ -0,0 +1,237 @@
+use std::io::{Cursor, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    compiler::{compiler::Bytecode, debug_info::DebugInfo, symbol_table::SymbolTable},
+    error_handler::errors::VynError,
+    runtime_value::values::RuntimeValue,
+    utils::Span,
+};
+
+/*
+ * `.vync` container layout (all integers big-endian, like the bytecode itself):
+ *
+ *   magic          "VYNC"
+ *   version        u16
+ *   constants      u32 count, then per constant: u8 tag + payload
+ *   string table   u32 count, then per string: u32 byte length + UTF-8 bytes
+ *   instructions   u32 length + raw bytecode
+ *   debug info     u32 count, then per run: u32 offset, u32 length,
+ *                  u32 line, u32 start column, u32 end column
+ * */
+pub const VYNC_MAGIC: &[u8; 4] = b"VYNC";
+pub const VYNC_VERSION: u16 = 2;
+
+// Constant pool tags
+const TAG_INT: u8 = 0x01;
+const TAG_FLOAT: u8 = 0x02;
+const TAG_BOOL: u8 = 0x03;
+const TAG_STRING: u8 = 0x04;
+const TAG_NIL: u8 = 0x05;
+
+impl Bytecode {
+    /*
+     * Serializes the bytecode into a `.vync` file image
+     *
+     * -- Arguments: [&self]
+     * -- Return value: Vec<u8> - complete file contents
+     * */
+    pub fn to_vync(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        out.extend_from_slice(VYNC_MAGIC);
+        out.write_u16::<BigEndian>(VYNC_VERSION).unwrap();
+
+        out.write_u32::<BigEndian>(self.constants.len() as u32)
+            .unwrap();
+        for constant in &self.constants {
+            write_constant(&mut out, constant);
+        }
+
+        out.write_u32::<BigEndian>(self.string_table.len() as u32)
+            .unwrap();
+        for string in &self.string_table {
+            out.write_u32::<BigEndian>(string.len() as u32).unwrap();
+            out.extend_from_slice(string.as_bytes());
+        }
+
+        out.write_u32::<BigEndian>(self.instructions.len() as u32)
+            .unwrap();
+        out.extend_from_slice(&self.instructions);
+
+        let runs = self.debug_info.runs();
+        out.write_u32::<BigEndian>(runs.len() as u32).unwrap();
+        for (offset, length, span) in runs {
+            out.write_u32::<BigEndian>(*offset as u32).unwrap();
+            out.write_u32::<BigEndian>(*length as u32).unwrap();
+            out.write_u32::<BigEndian>(span.line).unwrap();
+            out.write_u32::<BigEndian>(span.start_column).unwrap();
+            out.write_u32::<BigEndian>(span.end_column).unwrap();
+        }
+
+        out
+    }
+
+    /*
+     * Loads bytecode from a `.vync` file image
+     *
+     * -- Arguments: bytes - complete file contents
+     * -- Return value: Result<Bytecode, VynError>
+     *                  Err(MalformedBytecode) if the file is truncated or corrupt
+     *                  Err(UnsupportedBytecodeVersion) if it was built by an
+     *                  incompatible version
+     * */
+    pub fn from_vync(bytes: &[u8]) -> Result<Bytecode, VynError> {
+        let mut reader = Cursor::new(bytes);
+
+        let mut magic = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| malformed("missing file header"))?;
+        if &magic != VYNC_MAGIC {
+            return Err(malformed("not a .vync file"));
+        }
+
+        let version = read_u16(&mut reader)?;
+        if version != VYNC_VERSION {
+            return Err(VynError::UnsupportedBytecodeVersion {
+                found: version,
+                expected: VYNC_VERSION,
+            });
+        }
+
+        let constant_count = read_u32(&mut reader)? as usize;
+        let mut constants = Vec::with_capacity(constant_count.min(bytes.len()));
+        for _ in 0..constant_count {
+            constants.push(read_constant(&mut reader)?);
+        }
+
+        let string_count = read_u32(&mut reader)? as usize;
+        let mut string_table = Vec::with_capacity(string_count.min(bytes.len()));
+        for _ in 0..string_count {
+            let length = read_u32(&mut reader)? as usize;
+            let raw = read_bytes(&mut reader, length)?;
+            let string =
+                String::from_utf8(raw).map_err(|_| malformed("string table is not valid UTF-8"))?;
+            string_table.push(string);
+        }
+
+        let instruction_count = read_u32(&mut reader)? as usize;
+        let instructions = read_bytes(&mut reader, instruction_count)?;
+
+        let run_count = read_u32(&mut reader)? as usize;
+        let mut runs = Vec::with_capacity(run_count.min(bytes.len()));
+        for _ in 0..run_count {
+            let offset = read_u32(&mut reader)? as usize;
+            let length = read_u32(&mut reader)? as usize;
+            let span = Span {
+                line: read_u32(&mut reader)?,
+                start_column: read_u32(&mut reader)?,
+                end_column: read_u32(&mut reader)?,
+            };
+            runs.push((offset, length, span));
+        }
+
+        if (reader.position() as usize) != bytes.len() {
+            return Err(malformed("unexpected data after the debug info"));
+        }
+
+        Ok(Bytecode {
+            instructions,
+            constants,
+            string_table,
+            symbol_table: SymbolTable::new(),
+            debug_info: DebugInfo::from_runs(runs),
+        })
+    }
+}
+
+fn write_constant(out: &mut Vec<u8>, constant: &RuntimeValue) {
+    match constant {
+        RuntimeValue::IntegerLiteral(n) => {
+            out.push(TAG_INT);
+            out.write_i64::<BigEndian>(*n).unwrap();
+        }
+        RuntimeValue::FloatLiteral(f) => {
+            out.push(TAG_FLOAT);
+            out.write_f64::<BigEndian>(*f).unwrap();
+        }
+        RuntimeValue::BooleanLiteral(b) => {
+            out.push(TAG_BOOL);
+            out.push(*b as u8);
+        }
+        RuntimeValue::StringLiteral(idx) => {
+            out.push(TAG_STRING);
+            out.write_u32::<BigEndian>(*idx as u32).unwrap();
+        }
+        RuntimeValue::NilLiteral => out.push(TAG_NIL),
+        RuntimeValue::ArrayLiteral(_)
+        | RuntimeValue::SequenceLiteral(_)
+        | RuntimeValue::StructLiteral(_)
+        | RuntimeValue::EnumLiteral(_) => {
+            unreachable!("Heap values are never stored in the constant pool")
+        }
+    }
+}
+
+fn read_constant(reader: &mut Cursor<&[u8]>) -> Result<RuntimeValue, VynError> {
+    let tag = reader
+        .read_u8()
+        .map_err(|_| malformed("unexpected end of file"))?;
+
+    let constant = match tag {
+        TAG_INT => RuntimeValue::IntegerLiteral(
+            reader
+                .read_i64::<BigEndian>()
+                .map_err(|_| malformed("unexpected end of file"))?,
+        ),
+        TAG_FLOAT => RuntimeValue::FloatLiteral(
+            reader
+                .read_f64::<BigEndian>()
+                .map_err(|_| malformed("unexpected end of file"))?,
+        ),
+        TAG_BOOL => RuntimeValue::BooleanLiteral(
+            reader
+                .read_u8()
+                .map_err(|_| malformed("unexpected end of file"))?
+                != 0,
+        ),
+        TAG_STRING => RuntimeValue::StringLiteral(read_u32(reader)? as usize),
+        TAG_NIL => RuntimeValue::NilLiteral,
+        unknown => return Err(malformed(&format!("unknown constant tag {unknown:#04x}"))),
+    };
+
+    Ok(constant)
+}
+
+fn read_u16(reader: &mut Cursor<&[u8]>) -> Result<u16, VynError> {
+    reader
+        .read_u16::<BigEndian>()
+        .map_err(|_| malformed("unexpected end of file"))
+}
+
+fn read_u32(reader: &mut Cursor<&[u8]>) -> Result<u32, VynError> {
+    reader
+        .read_u32::<BigEndian>()
+        .map_err(|_| malformed("unexpected end of file"))
+}
+
+fn read_bytes(reader: &mut Cursor<&[u8]>, length: usize) -> Result<Vec<u8>, VynError> {
+    let remaining = reader.get_ref().len() - reader.position() as usize;
+    if length > remaining {
+        return Err(malformed("unexpected end of file"));
+    }
+
+    let mut buffer = vec![0u8; length];
+    reader
+        .read_exact(&mut buffer)
+        .map_err(|_| malformed("unexpected end of file"))?;
+    Ok(buffer)
+}
+
+fn malformed(reason: &str) -> VynError {
+    VynError::MalformedBytecode {
+        reason: reason.to_string(),
+    }
+}
@@ -2,6 +2,7 @@ use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 use crate::cli::commands::VERSION;
+use crate::error_handler::warnings::Lint;
 
 #[derive(Parser)]
 #[command(name = "vyn")]
@@ -30,14 +31,39 @@ pub struct CliArgs {
     /// Show time taken for each phase
     #[arg(long, global = true)]
     pub time: bool,
+
+    /// Wrap integer arithmetic on overflow instead of raising an error
+    #[arg(long, global = true)]
+    pub wrapping: bool,
+
+    /// Report string heap and garbage collection statistics after the run
+    #[arg(long, global = true)]
+    pub heap_stats: bool,
+
+    /// Fail compilation when any warning is reported
+    #[arg(long, global = true)]
+    pub deny_warnings: bool,
+
+    /// Silence a group of warnings, can be given several times
+    #[arg(long, global = true, value_name = "LINT")]
+    pub allow: Vec<Lint>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run a Vyn program
     Run {
+        /// Path to the .vyn source or a .vync bytecode file
+        file: PathBuf,
+    },
+    /// Compile a Vyn program to a .vync bytecode file
+    Build {
         /// Path to the .vyn file
         file: PathBuf,
+
+        /// Output path (defaults to the input path with a .vync extension)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
     /// Type check a Vyn program without running it
     Check {
@@ -46,9 +72,11 @@ pub enum Commands {
     },
     /// Disassemble bytecode
     Disasm {
-        /// Path to the .vyn file
+        /// Path to the .vyn source or a .vync bytecode file
         file: PathBuf,
     },
+    /// Start an interactive session
+    Repl,
     /// Show version information
     Version,
 }
@@ -1,6 +1,9 @@
 use crate::cli::{args::CliArgs, commands::CommandHandler};
+use crate::error_handler::internal::install_panic_hook;
 
 pub fn run() {
+    install_panic_hook();
+
     let args = CliArgs::parse();
     let handler = CommandHandler::new(args);
 
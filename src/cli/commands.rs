@@ -1,7 +1,10 @@
 use crate::cli::args::{CliArgs, Commands};
 use crate::cli::phases::{Phase, PhaseTracker};
-use crate::compiler::compiler::VynCompiler;
+use crate::cli::repl::Repl;
+use crate::compiler::compiler::{Bytecode, VynCompiler};
 use crate::compiler::disassembler::disassemble;
+use crate::error_handler::error_collector::ErrorCollector;
+use crate::error_handler::internal;
 use crate::ir::builder::VynIRBuilder;
 use crate::lexer::Lexer;
 use crate::parser::parser::Parser;
@@ -11,7 +14,7 @@ use crate::utils::print_info;
 use crate::vyn_vm::vm::VynVM;
 use colored::*;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub const VERSION: &str = "0.12.0";
 
@@ -27,13 +30,21 @@ impl CommandHandler {
     pub fn execute(&self) -> Result<(), i32> {
         match &self.args.command {
             Commands::Run { file } => self.run_file(file),
+            Commands::Build { file, output } => self.build_file(file, output.as_ref()),
             Commands::Check { file } => self.check_file(file),
             Commands::Disasm { file } => self.disasm_file(file),
+            Commands::Repl => Repl::new(self.args.wrapping).run(),
             Commands::Version => self.show_version(),
         }
     }
 
     fn run_file(&self, file: &PathBuf) -> Result<(), i32> {
+        if is_vync_file(file) {
+            let mut bc = self.load_bytecode(file)?;
+            // Precompiled files ship without their source
+            return self.execute_bytecode(&mut bc, "");
+        }
+
         let source = self.read_file(file)?;
         let file_name = self.get_file_name(file);
 
@@ -48,15 +59,26 @@ impl CommandHandler {
         tracker.start();
 
         // Compile the program
-        let (ir, mut bc) = match self.compile_program(&source, &mut tracker) {
+        let (_, mut bc) = match self.compile_program(&source, &mut tracker) {
             Ok(result) => result,
             Err(code) => return Err(code),
         };
 
         tracker.finish();
 
-        let mut vm = VynVM::new(&mut bc);
-        match vm.execute() {
+        self.execute_bytecode(&mut bc, &source)
+    }
+
+    fn execute_bytecode(&self, bc: &mut Bytecode, source: &str) -> Result<(), i32> {
+        if let Err(error) = bc.verify() {
+            error.report(source);
+            return Err(2);
+        }
+
+        internal::enter_phase(internal::RUNNING);
+        let mut vm = VynVM::new(bc);
+        vm.set_wrapping_arithmetic(self.args.wrapping);
+        let result = match vm.execute() {
             Ok(r) => {
                 if self.args.time {
                     let duration = vm.get_runtime_exec_dur();
@@ -65,10 +87,72 @@ impl CommandHandler {
                 Ok(r)
             }
             Err(ec) => {
-                ec.report(&source);
+                ec.report(source);
                 Err(2)
             }
+        };
+
+        if self.args.heap_stats {
+            vm.report_heap_stats();
+        }
+
+        result
+    }
+
+    fn build_file(&self, file: &PathBuf, output: Option<&PathBuf>) -> Result<(), i32> {
+        let source = self.read_file(file)?;
+        let file_name = self.get_file_name(file);
+
+        let mut tracker = PhaseTracker::new(
+            file_name,
+            !self.args.no_progress,
+            self.args.verbose,
+            self.args.quiet,
+            self.args.slow_mode,
+        );
+
+        tracker.start();
+
+        let (_, bc) = self.compile_program(&source, &mut tracker)?;
+
+        tracker.finish();
+
+        let output = output
+            .cloned()
+            .unwrap_or_else(|| file.with_extension("vync"));
+
+        fs::write(&output, bc.to_vync()).map_err(|e| {
+            if !self.args.quiet {
+                print_io_error(&format!("Could not write '{}': {}", output.display(), e));
+            }
+            1
+        })?;
+
+        if !self.args.quiet {
+            println!(
+                "\n{} {}",
+                "✓ Wrote".bright_green().bold(),
+                output.display().to_string().bright_white()
+            );
         }
+
+        Ok(())
+    }
+
+    fn load_bytecode(&self, file: &PathBuf) -> Result<Bytecode, i32> {
+        let bytes = fs::read(file).map_err(|e| {
+            if !self.args.quiet {
+                print_io_error(&format!("Could not read file: {}", e));
+            }
+            1
+        })?;
+
+        Bytecode::from_vync(&bytes).map_err(|e| {
+            if !self.args.quiet {
+                e.report("");
+            }
+            1
+        })
     }
 
     fn check_file(&self, file: &PathBuf) -> Result<(), i32> {
@@ -102,6 +186,16 @@ impl CommandHandler {
     }
 
     fn disasm_file(&self, file: &PathBuf) -> Result<(), i32> {
+        if is_vync_file(file) {
+            let bc = self.load_bytecode(file)?;
+            if let Err(error) = bc.verify() {
+                error.report("");
+                return Err(1);
+            }
+            disassemble(&bc);
+            return Ok(());
+        }
+
         let source = self.read_file(file)?;
         let file_name = self.get_file_name(file);
 
@@ -133,6 +227,14 @@ impl CommandHandler {
             println!("  {}: {:?}", i, instr.node);
         }
 
+        for function in &ir.functions {
+            println!();
+            println!("{}", format!("fn {}:", function.name).bright_green().bold());
+            for (i, instr) in function.instructions.iter().enumerate() {
+                println!("  {}: {:?}", i, instr.node);
+            }
+        }
+
         println!();
 
         // Disassemble
@@ -141,6 +243,50 @@ impl CommandHandler {
         Ok(())
     }
 
+    /*
+     * Prints the warnings type checking left behind, minus allowed lints
+     *
+     * -- Return value: Err(1) if `--deny-warnings` turns them into a failure
+     * */
+    fn report_warnings(
+        &self,
+        collector: &ErrorCollector,
+        source: &str,
+        tracker: &mut PhaseTracker,
+    ) -> Result<(), i32> {
+        let allowed = &self.args.allow;
+        let count = collector
+            .warnings()
+            .iter()
+            .filter(|warning| !allowed.contains(&warning.lint()))
+            .count();
+
+        if count == 0 {
+            return Ok(());
+        }
+
+        if !self.args.deny_warnings {
+            if !self.args.quiet {
+                tracker.suspend(|| collector.report_warnings(source, allowed));
+            }
+            return Ok(());
+        }
+
+        tracker.clear_display();
+        if !self.args.quiet {
+            collector.report_warnings(source, allowed);
+
+            let warning_word = if count == 1 { "warning" } else { "warnings" };
+            eprintln!(
+                "{} Could not compile due to {} {} (--deny-warnings)",
+                "*".bright_red().bold(),
+                count.to_string().bright_red().bold(),
+                warning_word.bright_red()
+            );
+        }
+        Err(1)
+    }
+
     fn compile_program(
         &self,
         source: &str,
@@ -152,10 +298,21 @@ impl CommandHandler {
         ),
         i32,
     > {
+        internal::set_source(source);
+
         // Tokenize
         tracker.begin_phase(Phase::Tokenizing);
         let mut lexer = Lexer::new(source);
-        let tokens = lexer.tokenize();
+        let tokens = match lexer.tokenize() {
+            Ok(t) => t,
+            Err(errors) => {
+                tracker.clear_display();
+                if !self.args.quiet {
+                    errors.report_all(source);
+                }
+                return Err(1);
+            }
+        };
         tracker.complete_phase(Phase::Tokenizing);
 
         // Parse
@@ -196,6 +353,7 @@ impl CommandHandler {
             }
             return Err(1);
         }
+        self.report_warnings(&type_checker.errors, source, tracker)?;
         tracker.complete_phase(Phase::TypeChecking);
 
         // Build IR
@@ -240,13 +398,7 @@ impl CommandHandler {
     fn read_file(&self, file: &PathBuf) -> Result<String, i32> {
         fs::read_to_string(file).map_err(|e| {
             if !self.args.quiet {
-                eprintln!(
-                    "{}{}{} {}",
-                    "Error".red().bold(),
-                    "::".white().dimmed(),
-                    "IO".bright_white().bold(),
-                    format!("-> Could not read file: {}", e).bright_red()
-                );
+                print_io_error(&format!("Could not read file: {}", e));
             }
             1
         })
@@ -259,3 +411,17 @@ impl CommandHandler {
             .to_string()
     }
 }
+
+fn is_vync_file(file: &Path) -> bool {
+    file.extension().is_some_and(|ext| ext == "vync")
+}
+
+fn print_io_error(message: &str) {
+    eprintln!(
+        "{}{}{} {}",
+        "Error".red().bold(),
+        "::".white().dimmed(),
+        "IO".bright_white().bold(),
+        format!("-> {}", message).bright_red()
+    );
+}
@@ -3,4 +3,5 @@ pub mod cli;
 pub mod commands;
 pub mod phases;
 pub mod progress;
+pub mod repl;
 pub mod theme;
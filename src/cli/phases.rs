@@ -1,4 +1,5 @@
 use crate::cli::theme::Theme;
+use crate::error_handler::internal;
 use colored::*;
 use indicatif::{ProgressBar as IndicatifBar, ProgressStyle};
 use std::thread;
@@ -15,7 +16,7 @@ pub enum Phase {
 }
 
 impl Phase {
-    fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'static str {
         match self {
             Phase::Tokenizing => "Tokenizing",
             Phase::Parsing => "Parsing",
@@ -96,6 +97,8 @@ impl PhaseTracker {
     }
 
     pub fn begin_phase(&mut self, phase: Phase) {
+        internal::enter_phase(phase.name());
+
         if self.quiet {
             return;
         }
@@ -170,6 +173,17 @@ impl PhaseTracker {
         );
     }
 
+    /*
+     * Runs `print` with the progress bar hidden, for output that shows up
+     * while compilation goes on
+     * */
+    pub fn suspend<R>(&self, print: impl FnOnce() -> R) -> R {
+        match &self.progress_bar {
+            Some(pb) => pb.suspend(print),
+            None => print(),
+        }
+    }
+
     pub fn clear_display(&mut self) {
         if let Some(pb) = &self.progress_bar {
             pb.finish_and_clear();
@@ -0,0 +1,382 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use colored::*;
+
+use crate::{
+    ast::ast::{Expr, Stmt},
+    cli::{commands::VERSION, phases::Phase},
+    compiler::{
+        compiler::{Bytecode, VynCompiler},
+        disassembler::disassemble,
+    },
+    error_handler::{error_collector::ErrorCollector, internal},
+    ir::{
+        builder::{IRBuilderState, VynIR, VynIRBuilder},
+        ir_instr::VynIRFunction,
+    },
+    lexer::Lexer,
+    parser::{parser::Parser, type_parser::TypeTable},
+    type_checker::{
+        static_evaluator::StaticEvaluator,
+        symbol_type_table::SymbolTypeTable,
+        type_checker::{Type, TypeChecker},
+    },
+    vyn_vm::vm::VynVM,
+};
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+
+/*
+ * Everything an input can declare, carried over to the next input
+ *
+ * A snapshot is taken before each input and restored if it fails, so a
+ * broken input never leaves half of its declarations behind
+ * */
+#[derive(Clone)]
+struct ReplState {
+    type_table: TypeTable,
+    static_eval: StaticEvaluator,
+    symbol_type_table: SymbolTypeTable,
+    ir_state: IRBuilderState,
+    // Every function declared so far, recompiled with each input
+    functions: Vec<VynIRFunction>,
+}
+
+impl ReplState {
+    fn new() -> Self {
+        Self {
+            type_table: TypeTable::new(),
+            static_eval: StaticEvaluator::new(),
+            symbol_type_table: SymbolTypeTable::new(),
+            ir_state: IRBuilderState::default(),
+            functions: Vec::new(),
+        }
+    }
+}
+
+/*
+ * Interactive session for `vyn repl`
+ *
+ * Each input goes through the whole pipeline on its own, but declarations
+ * and the VM's globals survive from one input to the next
+ *
+ * -- Entry method: `.run()`
+ * */
+pub struct Repl {
+    state: ReplState,
+    vm: VynVM,
+    // Bytecode of the last input that ran, for `:disasm`
+    last_bytecode: Option<Bytecode>,
+    wrapping_arithmetic: bool,
+}
+
+impl Repl {
+    pub fn new(wrapping_arithmetic: bool) -> Self {
+        Self {
+            state: ReplState::new(),
+            vm: Self::new_vm(wrapping_arithmetic),
+            last_bytecode: None,
+            wrapping_arithmetic,
+        }
+    }
+
+    fn new_vm(wrapping_arithmetic: bool) -> VynVM {
+        let mut vm = VynVM::new(&mut Bytecode::default());
+        vm.set_wrapping_arithmetic(wrapping_arithmetic);
+        vm
+    }
+
+    /*
+     * Reads and evaluates inputs until `:quit` or end of input
+     *
+     * -- Arguments: [&mut self]
+     * -- Return value: Result<(), i32> - Err only if stdin can't be read
+     * */
+    pub fn run(&mut self) -> Result<(), i32> {
+        println!(
+            "{} {} {}",
+            "Vyn".bright_green().bold(),
+            VERSION,
+            "- type :help for commands".dimmed()
+        );
+
+        loop {
+            let input = match read_input() {
+                Ok(Some(input)) => input,
+                Ok(None) => {
+                    println!();
+                    return Ok(());
+                }
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".bright_red().bold(), e);
+                    return Err(1);
+                }
+            };
+
+            let trimmed = input.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            match trimmed.strip_prefix(':') {
+                Some(command) => {
+                    if !self.run_command(command) {
+                        return Ok(());
+                    }
+                }
+                None => self.eval(&input),
+            }
+        }
+    }
+
+    /*
+     * Handles a `:command`
+     *
+     * -- Arguments: [&mut self], command - the input without its leading ':'
+     * -- Return value: bool - false if the session should end
+     * */
+    fn run_command(&mut self, command: &str) -> bool {
+        let (name, argument) = command
+            .split_once(char::is_whitespace)
+            .map(|(name, rest)| (name, rest.trim()))
+            .unwrap_or((command, ""));
+
+        match name {
+            "type" | "t" => self.show_type(argument),
+            "disasm" => match &self.last_bytecode {
+                Some(bytecode) => disassemble(bytecode),
+                None => println!("Nothing has been run yet"),
+            },
+            "reset" => {
+                self.state = ReplState::new();
+                self.vm = Self::new_vm(self.wrapping_arithmetic);
+                self.last_bytecode = None;
+                println!("Session reset");
+            }
+            "help" | "h" => print_help(),
+            "quit" | "q" => return false,
+            unknown => {
+                eprintln!(
+                    "{} unknown command ':{}', type :help for commands",
+                    "Error:".bright_red().bold(),
+                    unknown
+                );
+            }
+        }
+
+        true
+    }
+
+    /*
+     * Runs one input, rolling the session back if any phase fails
+     * */
+    fn eval(&mut self, source: &str) {
+        let snapshot = self.state.clone();
+
+        let bytecode = match self.compile(source) {
+            Ok(bytecode) => bytecode,
+            Err(errors) => {
+                errors.report_all(source);
+                self.state = snapshot;
+                return;
+            }
+        };
+
+        if let Err(error) = bytecode.verify() {
+            error.report(source);
+            self.state = snapshot;
+            return;
+        }
+
+        self.vm.load_program(&bytecode);
+        self.last_bytecode = Some(bytecode);
+
+        internal::enter_phase(internal::RUNNING);
+        if let Err(error) = self.vm.execute() {
+            error.report(source);
+            self.state = snapshot;
+        }
+    }
+
+    fn compile(&mut self, source: &str) -> Result<Bytecode, ErrorCollector> {
+        internal::set_source(source);
+
+        internal::enter_phase(Phase::Tokenizing.name());
+        let tokens = Lexer::new(source).tokenize()?;
+
+        internal::enter_phase(Phase::Parsing.name());
+        let mut parser = Parser::new(tokens);
+        parser.type_table = mem::take(&mut self.state.type_table);
+        let mut program = parser.parse_program()?;
+        self.state.type_table = mem::take(&mut parser.type_table);
+
+        internal::enter_phase(Phase::StaticEvaluation.name());
+        let mut errors = ErrorCollector::new();
+        if self
+            .state
+            .static_eval
+            .evaluate_program(&program, &mut errors)
+            .is_err()
+        {
+            return Err(errors);
+        }
+
+        internal::enter_phase(Phase::TypeChecking.name());
+        let mut type_checker = TypeChecker::new(&self.state.static_eval);
+        type_checker.symbol_type_table = mem::take(&mut self.state.symbol_type_table);
+        type_checker.check_program(&program)?;
+
+        // A trailing expression is echoed, unless it is an assignment or
+        // produces nothing
+        if let Some(last) = program.statements.last_mut()
+            && let Stmt::Expression { expression } = &last.node
+            && !matches!(
+                expression.node,
+                Expr::VariableAssignment { .. } | Expr::IndexAssignment { .. }
+            )
+            && type_checker.check_expression(expression, None) != Ok(Type::Nil)
+        {
+            last.node = Stmt::StdoutLog {
+                log_value: expression.clone(),
+            };
+        }
+        self.state.symbol_type_table = mem::take(&mut type_checker.symbol_type_table);
+
+        internal::enter_phase(Phase::IRBuilding.name());
+        let mut ir_builder = VynIRBuilder::with_state(
+            &self.state.static_eval,
+            &self.state.symbol_type_table,
+            mem::take(&mut self.state.ir_state),
+        );
+        let ir = ir_builder.build_ir(&program)?;
+        self.state.ir_state = ir_builder.take_state();
+        self.state.functions.extend(ir.functions);
+
+        let ir = VynIR {
+            instructions: ir.instructions,
+            functions: self.state.functions.clone(),
+        };
+        internal::enter_phase(Phase::Compiling.name());
+        let mut compiler = VynCompiler::with_string_table(self.vm.strings.as_slice().to_vec());
+        compiler.compile_ir(&ir)
+    }
+
+    /*
+     * Type checks an expression against the session without running it
+     * */
+    fn show_type(&mut self, source: &str) {
+        if source.is_empty() {
+            println!("Usage: :type <expression>");
+            return;
+        }
+
+        match self.type_of(source) {
+            Ok(Some(t)) => println!("{} : {}", source, t.to_string().bright_cyan()),
+            Ok(None) => println!("':type' expects a single expression"),
+            Err(errors) => errors.report_all(source),
+        }
+    }
+
+    fn type_of(&self, source: &str) -> Result<Option<Type>, ErrorCollector> {
+        let mut parser = Parser::new(Lexer::new(source).tokenize()?);
+        parser.type_table = self.state.type_table.clone();
+        let program = parser.parse_program()?;
+
+        let [statement] = program.statements.as_slice() else {
+            return Ok(None);
+        };
+        let Stmt::Expression { expression } = &statement.node else {
+            return Ok(None);
+        };
+
+        let mut type_checker = TypeChecker::new(&self.state.static_eval);
+        type_checker.symbol_type_table = self.state.symbol_type_table.clone();
+
+        match type_checker.check_expression(expression, None) {
+            Ok(t) => Ok(Some(t)),
+            Err(_) => Err(mem::take(&mut type_checker.errors)),
+        }
+    }
+}
+
+/*
+ * Reads one input, continuing on new lines while brackets are unbalanced
+ *
+ * -- Return value: Ok(None) at end of input
+ * */
+fn read_input() -> io::Result<Option<String>> {
+    let mut input = String::new();
+    let mut prompt = PROMPT;
+
+    loop {
+        print!("{}", prompt.bright_green());
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            return Ok(if input.is_empty() { None } else { Some(input) });
+        }
+
+        input.push_str(&line);
+
+        if open_brackets(&input) <= 0 {
+            return Ok(Some(input));
+        }
+
+        prompt = CONTINUATION_PROMPT;
+    }
+}
+
+/*
+ * Counts brackets left open in `source`, skipping strings and comments
+ * */
+fn open_brackets(source: &str) -> i32 {
+    let mut depth = 0;
+    let mut chars = source.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' | '\'' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' | '\'' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    depth
+}
+
+fn print_help() {
+    let commands = [
+        (":type <expr>", "Show the type of an expression without running it"),
+        (":disasm", "Disassemble the bytecode of the last input"),
+        (":reset", "Forget every declaration and start over"),
+        (":help", "Show this message"),
+        (":quit", "Leave the REPL (or press Ctrl-D)"),
+    ];
+
+    for (command, description) in commands {
+        println!("  {:<14} {}", command.bright_white(), description.dimmed());
+    }
+}
@@ -7,14 +7,14 @@ use crate::{
     compiler::{
         debug_info::DebugInfo, register_allocator::RegisterAllocator, symbol_table::SymbolTable,
     },
-    error_handler::error_collector::ErrorCollector,
+    error_handler::{error_collector::ErrorCollector, errors::VynError, internal},
     ir::{
         builder::VynIR,
         ir_instr::{Label, VynIROC, VynIROpCode},
     },
     runtime_value::values::RuntimeValue,
     utils::{Span, log_to_file},
-    vyn_vm::vm::MAX_REGISTERS,
+    vyn_vm::vm::{MAX_GLOBALS, MAX_REGISTERS},
 };
 
 /*
@@ -29,10 +29,19 @@ pub struct VynCompiler {
     constants: Vec<RuntimeValue>,
     debug_info: DebugInfo,
     string_table: Vec<String>,
+    // Index of every string in `string_table`, so equal constants share one
+    string_indices: HashMap<String, usize>,
     symbol_table: SymbolTable,
 
     register_allocator: RegisterAllocator,
     error_collector: ErrorCollector,
+
+    // Emit jumps and calls with 4-byte targets
+    wide_jumps: bool,
+
+    // (slot, scratch register) of spilled values the current instruction
+    // defines, stored once it has been emitted
+    pending_spills: Vec<(u16, u8)>,
 }
 
 /*
@@ -43,6 +52,7 @@ pub struct VynCompiler {
  * - constants: Constant pool (integers, floats, etc.)
  * - debug_info: Source location mapping for debugging/disassembly
  * */
+#[derive(Default)]
 pub struct Bytecode {
     pub instructions: Vec<u8>,
     pub constants: Vec<RuntimeValue>,
@@ -63,10 +73,35 @@ impl VynCompiler {
             instructions: Vec::new(),
             constants: Vec::new(),
             string_table: Vec::new(),
+            string_indices: HashMap::new(),
             symbol_table: SymbolTable::new(),
             debug_info: DebugInfo::new(),
             register_allocator: RegisterAllocator::new(MAX_REGISTERS),
             error_collector: ErrorCollector::new(),
+            wide_jumps: false,
+            pending_spills: Vec::new(),
+        }
+    }
+
+    /*
+     * Creates a compiler whose string table continues an existing one
+     *
+     * String values hold indices into the VM's string table, so a program
+     * compiled for a VM that already ran must keep those indices valid
+     *
+     * -- Arguments: string_table - the VM's current string table
+     * -- Return value: VynCompiler instance
+     * */
+    pub fn with_string_table(string_table: Vec<String>) -> Self {
+        let mut string_indices = HashMap::with_capacity(string_table.len());
+        for (index, string) in string_table.iter().enumerate() {
+            string_indices.entry(string.clone()).or_insert(index);
+        }
+
+        Self {
+            string_table,
+            string_indices,
+            ..Self::new()
         }
     }
 
@@ -84,18 +119,20 @@ impl VynCompiler {
      *                  Err(ErrorCollector) if compilation fails
      * */
     pub fn compile_ir(&mut self, ir: &VynIR) -> Result<Bytecode, ErrorCollector> {
-        self.register_allocator.analyze_liveness(&ir.instructions);
+        let string_table = self.string_table.clone();
 
-        let mut jump_patches: Vec<(usize, Label)> = Vec::new();
-        let mut label_positions: HashMap<Label, usize> = HashMap::new();
+        let (mut jump_patches, mut label_positions) = self.generate_code(ir);
 
-        for (i, inst) in ir.instructions.iter().enumerate() {
-            if self
-                .compile_inst(inst, i, &mut jump_patches, &mut label_positions)
-                .is_none()
-            {
-                break;
-            }
+        // A 2-byte target can't reach past 64 KiB. Jump sizes have to be known
+        // before their targets are, so the whole program is generated again
+        // with every jump wide
+        if label_positions.values().any(|&pos| pos > u16::MAX as usize) {
+            *self = Self {
+                wide_jumps: true,
+                ..Self::with_string_table(string_table)
+            };
+
+            (jump_patches, label_positions) = self.generate_code(ir);
         }
 
         // Backpatch all jump instructions with actual offsets
@@ -113,6 +150,72 @@ impl VynCompiler {
         }
     }
 
+    /*
+     * Emits the main program followed by every function, leaving jumps unpatched
+     *
+     * -- Arguments: [&mut self], ir - intermediate representation from IR builder
+     * -- Return value: (jumps to backpatch, bytecode offset of every label)
+     * */
+    fn generate_code(&mut self, ir: &VynIR) -> (Vec<(usize, Label)>, HashMap<Label, usize>) {
+        let mut jump_patches: Vec<(usize, Label)> = Vec::new();
+        let mut label_positions: HashMap<Label, usize> = HashMap::new();
+
+        self.compile_instructions(&ir.instructions, &mut jump_patches, &mut label_positions);
+
+        // Functions are laid out after the main program's Halt, each one
+        // with its own register allocation starting from r0
+        for function in &ir.functions {
+            self.register_allocator = RegisterAllocator::new(MAX_REGISTERS);
+            for (phys, vreg) in function.parameters.iter().enumerate() {
+                self.register_allocator.bind(*vreg, phys as u8);
+            }
+
+            self.compile_instructions(
+                &function.instructions,
+                &mut jump_patches,
+                &mut label_positions,
+            );
+        }
+
+        (jump_patches, label_positions)
+    }
+
+    /*
+     * Compiles one instruction stream (the main program or a function body)
+     *
+     * -- Arguments: [&mut self], instructions - IR instructions to compile
+     *               jump_patches, label_positions - shared backpatching state
+     * -- Return value: void
+     * */
+    fn compile_instructions(
+        &mut self,
+        instructions: &[VynIROpCode],
+        jump_patches: &mut Vec<(usize, Label)>,
+        label_positions: &mut HashMap<Label, usize>,
+    ) {
+        self.register_allocator.analyze_liveness(instructions);
+
+        let span = instructions.first().map(|inst| inst.span).unwrap_or_default();
+        if let Err(error) = self.register_allocator.plan_spills(instructions, span) {
+            self.error_collector.add(error);
+            return;
+        }
+
+        for (i, inst) in instructions.iter().enumerate() {
+            if self
+                .compile_inst(inst, i, jump_patches, label_positions)
+                .is_none()
+            {
+                break;
+            }
+
+            for (slot, scratch) in mem::take(&mut self.pending_spills) {
+                self.emit(OpCode::Spill, vec![slot as usize, scratch as usize], inst.span);
+            }
+            self.register_allocator.release_scratch();
+        }
+    }
+
     /*
      * Converts a single IR instruction to bytecode
      *
@@ -138,6 +241,8 @@ impl VynCompiler {
         jump_patches: &mut Vec<(usize, Label)>,
         label_positions: &mut HashMap<Label, usize>,
     ) -> Option<()> {
+        internal::track_span(inst.span);
+
         match &inst.node {
             /*
              * Emits a LoadConstInt OpCode
@@ -147,12 +252,13 @@ impl VynCompiler {
                 let vreg = *dest;
                 let dest = self.allocate(*dest, inst_idx, inst.span)?;
                 let const_idx = self.add_constant(RuntimeValue::IntegerLiteral(*value));
+                let opcode = if const_idx > u16::MAX as usize {
+                    OpCode::LoadConstIntWide
+                } else {
+                    OpCode::LoadConstInt
+                };
 
-                self.emit(
-                    OpCode::LoadConstInt,
-                    vec![dest as usize, const_idx as usize],
-                    inst.span,
-                );
+                self.emit(opcode, vec![dest as usize, const_idx], inst.span);
             }
 
             /*
@@ -162,12 +268,13 @@ impl VynCompiler {
             VynIROC::LoadConstFloat { dest, value } => {
                 let dest = self.allocate(*dest, inst_idx, inst.span)?;
                 let const_idx = self.add_constant(RuntimeValue::FloatLiteral(*value));
+                let opcode = if const_idx > u16::MAX as usize {
+                    OpCode::LoadConstFloatWide
+                } else {
+                    OpCode::LoadConstFloat
+                };
 
-                self.emit(
-                    OpCode::LoadConstFloat,
-                    vec![dest as usize, const_idx as usize],
-                    inst.span,
-                );
+                self.emit(opcode, vec![dest as usize, const_idx], inst.span);
             }
 
             /*
@@ -177,11 +284,13 @@ impl VynCompiler {
             VynIROC::LoadString { dest, value } => {
                 let dest = self.allocate(*dest, inst_idx, inst.span)?;
                 let string_idx = self.intern_string(value.clone());
-                self.emit(
-                    OpCode::LoadString,
-                    vec![dest as usize, string_idx],
-                    inst.span,
-                );
+                let opcode = if string_idx > u16::MAX as usize {
+                    OpCode::LoadStringWide
+                } else {
+                    OpCode::LoadString
+                };
+
+                self.emit(opcode, vec![dest as usize, string_idx], inst.span);
             }
 
             /*
@@ -198,6 +307,15 @@ impl VynCompiler {
                 }
             }
 
+            /*
+             * Emits a LoadNil OpCode
+             * -- Operands: [dest]
+             * */
+            VynIROC::LoadNil { dest } => {
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                self.emit(OpCode::LoadNil, vec![dest as usize], inst.span);
+            }
+
             /*
              * Compiles a binary expression OpCode
              * -- Operands: [dest, left_reg, right_reg]
@@ -211,10 +329,11 @@ impl VynCompiler {
             | VynIROC::DivInt { dest, left, right }
             | VynIROC::DivFloat { dest, left, right }
             | VynIROC::ExpInt { dest, left, right }
-            | VynIROC::ExpFloat { dest, left, right } => {
+            | VynIROC::ExpFloat { dest, left, right }
+            | VynIROC::ConcatString { dest, left, right } => {
                 // Get physical registers for operands (already allocated)
-                let left_reg = self.get(*left)?;
-                let right_reg = self.get(*right)?;
+                let left_reg = self.get(*left, inst.span)?;
+                let right_reg = self.get(*right, inst.span)?;
 
                 // Allocate new physical register for destination
                 let dest_reg = self.allocate(*dest, inst_idx, inst.span)?;
@@ -231,6 +350,7 @@ impl VynCompiler {
                     VynIROC::DivFloat { .. } => OpCode::DivideFloat,
                     VynIROC::ExpInt { .. } => OpCode::ExponentInt,
                     VynIROC::ExpFloat { .. } => OpCode::ExponentFloat,
+                    VynIROC::ConcatString { .. } => OpCode::ConcatString,
                     _ => unreachable!(),
                 };
 
@@ -241,8 +361,8 @@ impl VynCompiler {
                 );
 
                 // Free operands if no longer live
-                self.free(*left, inst_idx + 1);
-                self.free(*right, inst_idx + 1);
+                self.free(*left, inst_idx);
+                self.free(*right, inst_idx);
             }
 
             /*
@@ -257,10 +377,14 @@ impl VynCompiler {
             | VynIROC::CompareLessEqualFloat { dest, left, right }
             | VynIROC::CompareGreaterEqualInt { dest, left, right }
             | VynIROC::CompareGreaterEqualFloat { dest, left, right }
+            | VynIROC::CompareLessString { dest, left, right }
+            | VynIROC::CompareLessEqualString { dest, left, right }
+            | VynIROC::CompareGreaterString { dest, left, right }
+            | VynIROC::CompareGreaterEqualString { dest, left, right }
             | VynIROC::CompareEqual { dest, left, right }
             | VynIROC::CompareNotEqual { dest, left, right } => {
-                let left_reg = self.get(*left)?;
-                let right_reg = self.get(*right)?;
+                let left_reg = self.get(*left, inst.span)?;
+                let right_reg = self.get(*right, inst.span)?;
                 let dest = self.allocate(*dest, inst_idx, inst.span)?;
 
                 let opcode = match &inst.node {
@@ -272,6 +396,10 @@ impl VynCompiler {
                     VynIROC::CompareGreaterEqualFloat { .. } => OpCode::GreaterEqualFloat,
                     VynIROC::CompareLessEqualInt { .. } => OpCode::LessEqualInt,
                     VynIROC::CompareLessEqualFloat { .. } => OpCode::LessEqualFloat,
+                    VynIROC::CompareLessString { .. } => OpCode::LessString,
+                    VynIROC::CompareLessEqualString { .. } => OpCode::LessEqualString,
+                    VynIROC::CompareGreaterString { .. } => OpCode::GreaterString,
+                    VynIROC::CompareGreaterEqualString { .. } => OpCode::GreaterEqualString,
                     VynIROC::CompareEqual { .. } => OpCode::Equal,
                     VynIROC::CompareNotEqual { .. } => OpCode::NotEqual,
                     _ => unreachable!(),
@@ -282,8 +410,8 @@ impl VynCompiler {
                     vec![dest as usize, left_reg as usize, right_reg as usize],
                     inst.span,
                 );
-                self.free(*left, inst_idx + 1);
-                self.free(*right, inst_idx + 1);
+                self.free(*left, inst_idx);
+                self.free(*right, inst_idx);
             }
 
             /*
@@ -291,12 +419,12 @@ impl VynCompiler {
              * -- Operands: [addr]
              * */
             VynIROC::LogAddr { addr } => {
-                let val = self.get(*addr)?;
+                let val = self.get(*addr, inst.span)?;
 
                 self.emit(OpCode::LogAddr, vec![val as usize], inst.span);
 
                 // Free address register after use
-                self.free(*addr, inst_idx + 1);
+                self.free(*addr, inst_idx);
             }
 
             /*
@@ -308,16 +436,22 @@ impl VynCompiler {
                 condition_reg,
                 label,
             } => {
-                let cond_reg = self.get(*condition_reg)?;
+                let cond_reg = self.get(*condition_reg, inst.span)?;
                 let jump_pos = self.instructions.len();
 
+                let opcode = if self.wide_jumps {
+                    OpCode::JumpIfFalseWide
+                } else {
+                    OpCode::JumpIfFalse
+                };
+
                 // Emit with placeholder offset (0)
-                self.emit(OpCode::JumpIfFalse, vec![cond_reg as usize, 0], inst.span);
+                self.emit(opcode, vec![cond_reg as usize, 0], inst.span);
 
                 // Record position to patch (skip opcode byte + condition register byte)
                 jump_patches.push((jump_pos + 2, *label));
 
-                self.free(*condition_reg, inst_idx + 1);
+                self.free(*condition_reg, inst_idx);
             }
 
             /*
@@ -328,8 +462,14 @@ impl VynCompiler {
             VynIROC::JumpUncond { label } => {
                 let jump_pos = self.instructions.len();
 
+                let opcode = if self.wide_jumps {
+                    OpCode::JumpUncondWide
+                } else {
+                    OpCode::JumpUncond
+                };
+
                 // Emit with placeholder offset (0)
-                self.emit(OpCode::JumpUncond, vec![0], inst.span);
+                self.emit(opcode, vec![0], inst.span);
 
                 // Record position to patch (skip opcode byte)
                 jump_patches.push((jump_pos + 1, *label));
@@ -350,13 +490,426 @@ impl VynCompiler {
              * -- Operands: [reg_x, reg_y]
              * */
             VynIROC::Move { dest, src } => {
-                let dest_reg = self.get(*dest)?;
-                let src_reg = self.get(*src)?;
+                let src_reg = self.get(*src, inst.span)?;
+                // Destination may be a fresh copy that has no register yet
+                let dest_reg = self.allocate(*dest, inst_idx, inst.span)?;
                 self.emit(
                     OpCode::Move,
                     vec![dest_reg as usize, src_reg as usize],
                     inst.span,
                 );
+
+                self.free(*src, inst_idx);
+            }
+
+            /*
+             * Reads a global variable into a register
+             * -- Operands: [dest, global_idx]
+             * */
+            VynIROC::LoadGlobal { dest, index } => {
+                self.check_global(*index, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                let opcode = if *index > u16::MAX as usize {
+                    OpCode::LoadGlobalWide
+                } else {
+                    OpCode::LoadGlobal
+                };
+
+                self.emit(opcode, vec![dest as usize, *index], inst.span);
+            }
+
+            /*
+             * Writes a register into a global variable
+             * -- Operands: [global_idx, src]
+             * */
+            VynIROC::StoreGlobal { index, src } => {
+                self.check_global(*index, inst.span)?;
+                let src_reg = self.get(*src, inst.span)?;
+                let opcode = if *index > u16::MAX as usize {
+                    OpCode::StoreGlobalWide
+                } else {
+                    OpCode::StoreGlobal
+                };
+
+                self.emit(opcode, vec![*index, src_reg as usize], inst.span);
+
+                self.free(*src, inst_idx);
+            }
+
+            /*
+             * Compiles a unary expression, a conversion or a string length
+             * -- Operands: [dest, src]
+             * */
+            VynIROC::NegateInt { dest, src }
+            | VynIROC::NegateFloat { dest, src }
+            | VynIROC::Not { dest, src }
+            | VynIROC::IntToFloat { dest, src }
+            | VynIROC::FloatToInt { dest, src }
+            | VynIROC::StringLength { dest, src }
+            | VynIROC::ToString { dest, src } => {
+                let src_reg = self.get(*src, inst.span)?;
+                let dest_reg = self.allocate(*dest, inst_idx, inst.span)?;
+
+                let opcode = match &inst.node {
+                    VynIROC::NegateInt { .. } => OpCode::NegateInt,
+                    VynIROC::NegateFloat { .. } => OpCode::NegateFloat,
+                    VynIROC::Not { .. } => OpCode::Not,
+                    VynIROC::IntToFloat { .. } => OpCode::IntToFloat,
+                    VynIROC::FloatToInt { .. } => OpCode::FloatToInt,
+                    VynIROC::StringLength { .. } => OpCode::StringLength,
+                    VynIROC::ToString { .. } => OpCode::ToString,
+                    _ => unreachable!(),
+                };
+
+                self.emit(
+                    opcode,
+                    vec![dest_reg as usize, src_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*src, inst_idx);
+            }
+
+            /*
+             * Allocates a fixed array on the VM heap
+             * -- Operands: [dest, size]
+             * */
+            VynIROC::NewArray { dest, size } => {
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                self.emit(OpCode::ArrayNewFixed, vec![dest as usize, *size], inst.span);
+            }
+
+            /*
+             * Allocates a sequence (dynamic array) on the VM heap
+             * -- Operands: [dest, capacity]
+             * */
+            VynIROC::NewSequence { dest, capacity } => {
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                self.emit(
+                    OpCode::ArrayNewDynamic,
+                    vec![dest as usize, *capacity],
+                    inst.span,
+                );
+            }
+
+            /*
+             * Stores a value at a constant index
+             * -- Operands: [array_reg, index, value_reg]
+             * */
+            VynIROC::ArraySet {
+                array,
+                index,
+                value,
+            } => {
+                let array_reg = self.get(*array, inst.span)?;
+                let value_reg = self.get(*value, inst.span)?;
+
+                self.emit(
+                    OpCode::ArraySet,
+                    vec![array_reg as usize, *index, value_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*array, inst_idx);
+                self.free(*value, inst_idx);
+            }
+
+            /*
+             * Stores a value at an index held in a register
+             * -- Operands: [array_reg, index_reg, value_reg]
+             * */
+            VynIROC::ArraySetReg {
+                array,
+                index,
+                value,
+            } => {
+                let array_reg = self.get(*array, inst.span)?;
+                let index_reg = self.get(*index, inst.span)?;
+                let value_reg = self.get(*value, inst.span)?;
+
+                self.emit(
+                    OpCode::ArraySetReg,
+                    vec![array_reg as usize, index_reg as usize, value_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*array, inst_idx);
+                self.free(*index, inst_idx);
+                self.free(*value, inst_idx);
+            }
+
+            /*
+             * Reads an element into a register
+             * -- Operands: [dest, array_reg, index_reg]
+             * */
+            VynIROC::ArrayGet { dest, array, index } => {
+                let array_reg = self.get(*array, inst.span)?;
+                let index_reg = self.get(*index, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::ArrayGet,
+                    vec![dest as usize, array_reg as usize, index_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*array, inst_idx);
+                self.free(*index, inst_idx);
+            }
+
+            /*
+             * Appends a value to a sequence
+             * -- Operands: [array_reg, value_reg]
+             * */
+            VynIROC::ArrayPush { array, value } => {
+                let array_reg = self.get(*array, inst.span)?;
+                let value_reg = self.get(*value, inst.span)?;
+
+                self.emit(
+                    OpCode::ArrayPush,
+                    vec![array_reg as usize, value_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*array, inst_idx);
+                self.free(*value, inst_idx);
+            }
+
+            /*
+             * Allocates a struct on the VM heap
+             * -- Operands: [dest, layout_idx, field_count]
+             * */
+            VynIROC::NewStruct { dest, layout, size } => {
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                let layout_idx = self.intern_string(layout.clone());
+
+                self.emit(
+                    OpCode::StructNew,
+                    vec![dest as usize, layout_idx, *size],
+                    inst.span,
+                );
+            }
+
+            /*
+             * Reads a field into a register
+             * -- Operands: [dest, struct_reg, field]
+             * */
+            VynIROC::StructGet {
+                dest,
+                target,
+                field,
+            } => {
+                let struct_reg = self.get(*target, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::StructGet,
+                    vec![dest as usize, struct_reg as usize, *field],
+                    inst.span,
+                );
+
+                self.free(*target, inst_idx);
+            }
+
+            /*
+             * Stores a value into a field
+             * -- Operands: [struct_reg, field, value_reg]
+             * */
+            VynIROC::StructSet {
+                target,
+                field,
+                value,
+            } => {
+                let struct_reg = self.get(*target, inst.span)?;
+                let value_reg = self.get(*value, inst.span)?;
+
+                self.emit(
+                    OpCode::StructSet,
+                    vec![struct_reg as usize, *field, value_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*target, inst_idx);
+                self.free(*value, inst_idx);
+            }
+
+            /*
+             * Allocates an enum value on the VM heap
+             * -- Operands: [dest, name_idx, tag, payload_size]
+             * */
+            VynIROC::NewEnum {
+                dest,
+                name,
+                tag,
+                size,
+            } => {
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+                let name_idx = self.intern_string(name.clone());
+
+                self.emit(
+                    OpCode::EnumNew,
+                    vec![dest as usize, name_idx, *tag, *size],
+                    inst.span,
+                );
+            }
+
+            /*
+             * Reads the variant tag of an enum value as an Int
+             * -- Operands: [dest, enum_reg]
+             * */
+            VynIROC::EnumTag { dest, target } => {
+                let enum_reg = self.get(*target, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::EnumTag,
+                    vec![dest as usize, enum_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*target, inst_idx);
+            }
+
+            /*
+             * Stops the program if an index loop steps by 0
+             * -- Operands: [step_reg]
+             * */
+            VynIROC::CheckStep { step } => {
+                let step_reg = self.get(*step, inst.span)?;
+                self.emit(OpCode::CheckStep, vec![step_reg as usize], inst.span);
+
+                self.free(*step, inst_idx);
+            }
+
+            /*
+             * Marks a heap value and everything reachable from it read-only
+             * -- Operands: [value_reg]
+             * */
+            VynIROC::Freeze { value } => {
+                let value_reg = self.get(*value, inst.span)?;
+                self.emit(OpCode::Freeze, vec![value_reg as usize], inst.span);
+
+                self.free(*value, inst_idx);
+            }
+
+            /*
+             * Reads one character of a string as a new string
+             * -- Operands: [dest, string_reg, index_reg]
+             * */
+            VynIROC::StringIndex {
+                dest,
+                string,
+                index,
+            } => {
+                let string_reg = self.get(*string, inst.span)?;
+                let index_reg = self.get(*index, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::StringIndex,
+                    vec![dest as usize, string_reg as usize, index_reg as usize],
+                    inst.span,
+                );
+
+                self.free(*string, inst_idx);
+                self.free(*index, inst_idx);
+            }
+
+            /*
+             * Copies the characters in [start, end) of a string
+             * -- Operands: [dest, string_reg, start_reg, end_reg]
+             * */
+            VynIROC::StringSlice {
+                dest,
+                string,
+                start,
+                end,
+            } => {
+                let string_reg = self.get(*string, inst.span)?;
+                let start_reg = self.get(*start, inst.span)?;
+                let end_reg = self.get(*end, inst.span)?;
+                let dest = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::StringSlice,
+                    vec![
+                        dest as usize,
+                        string_reg as usize,
+                        start_reg as usize,
+                        end_reg as usize,
+                    ],
+                    inst.span,
+                );
+
+                self.free(*string, inst_idx);
+                self.free(*start, inst_idx);
+                self.free(*end, inst_idx);
+            }
+
+            /*
+             * Calls a function in a fresh register window
+             * -- Operands: [dest, target, window, argc]
+             * -- Notes:
+             * # Arguments are moved into window..window+argc first, the
+             *   callee sees them as r0..rN-1
+             * # target is patched later via backpatching
+             * */
+            VynIROC::Call {
+                dest,
+                function,
+                args,
+            } => {
+                let window = self.move_call_args(args, inst_idx, inst.span)?;
+                let dest_reg = self.allocate(*dest, inst_idx, inst.span)?;
+                let call_pos = self.instructions.len();
+                let opcode = if self.wide_jumps {
+                    OpCode::CallWide
+                } else {
+                    OpCode::Call
+                };
+
+                self.emit(
+                    opcode,
+                    vec![dest_reg as usize, 0, window, args.len()],
+                    inst.span,
+                );
+
+                // Skip opcode byte + dest register byte
+                jump_patches.push((call_pos + 2, *function));
+            }
+
+            /*
+             * Calls a native function, the VM runs it without entering a
+             * new register window
+             * -- Operands: [dest, native, window, argc]
+             * -- Note: arguments are moved into window..window+argc like Call
+             * */
+            VynIROC::CallNative { dest, native, args } => {
+                let window = self.move_call_args(args, inst_idx, inst.span)?;
+                let dest_reg = self.allocate(*dest, inst_idx, inst.span)?;
+
+                self.emit(
+                    OpCode::CallNative,
+                    vec![dest_reg as usize, *native, window, args.len()],
+                    inst.span,
+                );
+            }
+
+            /*
+             * Returns from the current function
+             * -- Operands: [value_reg]
+             * -- Note: a bare `return` loads nil into r0 first
+             * */
+            VynIROC::Return { value } => {
+                let value_reg = match value {
+                    Some(value) => self.get(*value, inst.span)?,
+                    None => {
+                        self.emit(OpCode::LoadNil, vec![0], inst.span);
+                        0
+                    }
+                };
+
+                self.emit(OpCode::Return, vec![value_reg as usize], inst.span);
             }
 
             /*
@@ -366,8 +919,6 @@ impl VynCompiler {
             VynIROC::Halt => {
                 self.emit(OpCode::Halt, vec![], inst.span);
             }
-
-            unknown => todo!("Implement inst {unknown:?}"),
         }
 
         Some(())
@@ -376,8 +927,8 @@ impl VynCompiler {
     /*
      * Patches a jump instruction with the actual target offset
      *
-     * Writes a 2-byte offset into the bytecode at the specified position.
-     * Uses little-endian encoding.
+     * Writes a 2-byte offset into the bytecode at the specified position,
+     * or a 4-byte one when jumps are wide. Uses big-endian encoding.
      *
      * -- Arguments: [&mut self],
      *               position - bytecode offset where the jump offset should be written
@@ -385,10 +936,17 @@ impl VynCompiler {
      * -- Return value: void
      * */
     fn patch_jump(&mut self, position: usize, target: usize) {
-        BigEndian::write_u16(
-            &mut self.instructions[position..position + 2],
-            target as u16,
-        );
+        if self.wide_jumps {
+            BigEndian::write_u32(
+                &mut self.instructions[position..position + 4],
+                target as u32,
+            );
+        } else {
+            BigEndian::write_u16(
+                &mut self.instructions[position..position + 2],
+                target as u16,
+            );
+        }
     }
 
     /*
@@ -402,6 +960,14 @@ impl VynCompiler {
      *                  None if allocation fails (error added to collector)
      * */
     fn allocate(&mut self, virtual_reg: u32, inst_idx: usize, span: Span) -> Option<u8> {
+        // A spilled value is written to a scratch register and stored to
+        // its slot after the instruction
+        if let Some(slot) = self.register_allocator.spill_slot(virtual_reg) {
+            let scratch = self.take_scratch(span)?;
+            self.pending_spills.push((slot, scratch));
+            return Some(scratch);
+        }
+
         let result = self
             .register_allocator
             .allocate(virtual_reg, inst_idx, span);
@@ -417,21 +983,52 @@ impl VynCompiler {
     /*
      * Gets the physical register assigned to a virtual register
      *
+     * A spilled value is reloaded from its slot into a scratch register first
+     *
      * -- Arguments: [&mut self],
      *               virtual_reg - virtual register ID to look up
+     *               span - source location of the reload, if one is needed
      * -- Return value: Some(physical_register) if found
      *
      * -- Notes:
      * # Panics if virtual register was never allocated (compiler bug)
      * # This should only be called for registers that were previously allocated
      * */
-    fn get(&mut self, virtual_reg: u32) -> Option<u8> {
+    fn get(&mut self, virtual_reg: u32, span: Span) -> Option<u8> {
+        if let Some(slot) = self.register_allocator.spill_slot(virtual_reg) {
+            let scratch = self.take_scratch(span)?;
+            self.emit(OpCode::Reload, vec![scratch as usize, slot as usize], span);
+            return Some(scratch);
+        }
+
         match self.register_allocator.get(virtual_reg) {
             Ok(phys_reg) => Some(phys_reg),
             _ => unreachable!("COMPILER BUG: v{} was never allocated", virtual_reg),
         }
     }
 
+    /*
+     * Reports a global index past the ones the verifier accepts
+     * */
+    fn check_global(&mut self, index: usize, span: Span) -> Option<()> {
+        if index >= MAX_GLOBALS {
+            self.error_collector.add(VynError::TooManyGlobals { span });
+            return None;
+        }
+
+        Some(())
+    }
+
+    fn take_scratch(&mut self, span: Span) -> Option<u8> {
+        match self.register_allocator.take_scratch(span) {
+            Ok(scratch) => Some(scratch),
+            Err(error) => {
+                self.error_collector.add(error);
+                None
+            }
+        }
+    }
+
     /*
      * Frees a virtual register if it's no longer live
      *
@@ -440,13 +1037,47 @@ impl VynCompiler {
      *
      * -- Arguments: [&mut self],
      *               virtual_reg - virtual register to potentially free
-     *               inst_idx - instruction index to check liveness after
+     *               inst_idx - instruction that just read it
      * -- Return value: void
      * */
     fn free(&mut self, virtual_reg: u32, inst_idx: usize) {
         self.register_allocator.free(virtual_reg, inst_idx);
     }
 
+    /*
+     * Moves call arguments into the registers past every live one
+     *
+     * -- Arguments: [&mut self], args - argument vregs in order,
+     *               inst_idx - index of the call, span - span of the call
+     * -- Return value: Some(window) - register holding the first argument
+     * */
+    fn move_call_args(&mut self, args: &[u32], inst_idx: usize, span: Span) -> Option<usize> {
+        let window = self.register_allocator.window_start();
+
+        if window + args.len() > MAX_REGISTERS as usize {
+            self.error_collector.add(VynError::RegisterOverflow { span });
+            return None;
+        }
+
+        for (i, arg) in args.iter().enumerate() {
+            // Reloaded straight into the window, a scratch register
+            // could be part of it
+            if let Some(slot) = self.register_allocator.spill_slot(*arg) {
+                self.emit(OpCode::Reload, vec![window + i, slot as usize], span);
+                continue;
+            }
+
+            let arg_reg = self.get(*arg, span)?;
+            self.emit(OpCode::Move, vec![window + i, arg_reg as usize], span);
+        }
+
+        for arg in args {
+            self.free(*arg, inst_idx);
+        }
+
+        Some(window)
+    }
+
     /*
      * Emits bytecode instruction with debug info
      *
@@ -479,18 +1110,18 @@ impl VynCompiler {
      * Adds a constant to the constant pool
      *
      * -- Arguments: [&mut self], value - runtime value to add
-     * -- Return value: u16 - index of constant in pool
+     * -- Return value: usize - index of constant in pool
      *
      * -- Notes:
-     * # Returns index as u16 (supports up to 65536 constants)
+     * # Indices past u16::MAX need the wide load instructions
      * */
-    fn add_constant(&mut self, value: RuntimeValue) -> u16 {
+    fn add_constant(&mut self, value: RuntimeValue) -> usize {
         if let Some(idx) = self.constants.iter().position(|c| c == &value) {
-            return idx as u16;
+            return idx;
         }
 
         self.constants.push(value);
-        (self.constants.len() - 1) as u16
+        self.constants.len() - 1
     }
 
     /*
@@ -503,12 +1134,14 @@ impl VynCompiler {
      * # If not, it will register the string AND return the index
      * */
     fn intern_string(&mut self, string: String) -> usize {
-        if let Some(index) = self.string_table.iter().position(|s| s == &string) {
+        if let Some(&index) = self.string_indices.get(&string) {
             return index;
         }
 
+        let index = self.string_table.len();
+        self.string_indices.insert(string.clone(), index);
         self.string_table.push(string);
-        self.string_table.len() - 1
+        index
     }
 
     /*
@@ -522,6 +1155,8 @@ impl VynCompiler {
      * # Should only be called when the compiler finished compiling
      * */
     pub fn finish(&mut self) -> Bytecode {
+        self.string_indices.clear();
+
         Bytecode {
             instructions: mem::take(&mut self.instructions),
             constants: mem::take(&mut self.constants),
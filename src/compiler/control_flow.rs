@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::ir::ir_instr::{VynIROC, VynIROpCode};
+
+/*
+ * A run of instructions only ever entered at its first instruction and
+ * left after its last one
+ * */
+pub struct BasicBlock {
+    // Index of the first instruction
+    pub start: usize,
+    // Index past the last instruction
+    pub end: usize,
+    // Blocks control can reach right after this one
+    pub successors: Vec<usize>,
+}
+
+/*
+ * Control-flow graph of one instruction stream (the main program or a
+ * function body), blocks are in instruction order
+ * */
+pub struct ControlFlowGraph {
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl ControlFlowGraph {
+    /*
+     * Splits an instruction stream into basic blocks and links them
+     *
+     * A block starts at the first instruction, at every label and after
+     * every jump, return or halt. Conditional jumps have two successors,
+     * the label and the next block, unconditional ones only the label
+     *
+     * -- Arguments: instructions - IR of one function or the main program
+     * -- Return value: ControlFlowGraph
+     * */
+    pub fn build(instructions: &[VynIROpCode]) -> Self {
+        let mut starts = vec![0];
+        for (i, inst) in instructions.iter().enumerate() {
+            match &inst.node {
+                VynIROC::Label(_) => starts.push(i),
+                VynIROC::JumpIfFalse { .. }
+                | VynIROC::JumpUncond { .. }
+                | VynIROC::Return { .. }
+                | VynIROC::Halt => starts.push(i + 1),
+                _ => {}
+            }
+        }
+        starts.retain(|&start| start < instructions.len());
+        starts.dedup();
+
+        let block_of_label: HashMap<usize, usize> = starts
+            .iter()
+            .enumerate()
+            .filter_map(|(block, &start)| match &instructions[start].node {
+                VynIROC::Label(label) => Some((label.0, block)),
+                _ => None,
+            })
+            .collect();
+
+        let blocks = starts
+            .iter()
+            .enumerate()
+            .map(|(block, &start)| {
+                let end = starts.get(block + 1).copied().unwrap_or(instructions.len());
+                let next = (block + 1 < starts.len()).then_some(block + 1);
+
+                let successors = match &instructions[end - 1].node {
+                    VynIROC::JumpUncond { label } => {
+                        block_of_label.get(&label.0).copied().into_iter().collect()
+                    }
+                    VynIROC::JumpIfFalse { label, .. } => {
+                        let mut successors: Vec<usize> = next.into_iter().collect();
+                        successors.extend(block_of_label.get(&label.0));
+                        successors
+                    }
+                    VynIROC::Return { .. } | VynIROC::Halt => Vec::new(),
+                    _ => next.into_iter().collect(),
+                };
+
+                BasicBlock {
+                    start,
+                    end,
+                    successors,
+                }
+            })
+            .collect();
+
+        Self { blocks }
+    }
+}
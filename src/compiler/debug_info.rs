@@ -89,6 +89,26 @@ impl DebugInfo {
         }
     }
 
+    /*
+     * Rebuilds DebugInfo from previously recorded runs
+     *
+     * -- Arguments: runs - (start_offset, run_length, span) entries, sorted by offset
+     * -- Return value: DebugInfo instance
+     * */
+    pub fn from_runs(runs: Vec<(usize, usize, Span)>) -> Self {
+        Self { runs }
+    }
+
+    /*
+     * Get the raw RLE runs
+     *
+     * -- Arguments: [&self]
+     * -- Return value: &[(start_offset, run_length, span)]
+     * */
+    pub fn runs(&self) -> &[(usize, usize, Span)] {
+        &self.runs
+    }
+
     /*
      * Get the number of compressed runs
      *
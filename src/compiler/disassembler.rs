@@ -4,6 +4,7 @@ use crate::{
     bytecode::bytecode::{Instructions, OpCode, ToOpcode, read_uint8, read_uint16, read_uint32},
     compiler::{compiler::Bytecode, debug_info::DebugInfo},
     runtime_value::values::RuntimeValue,
+    vyn_vm::natives::NATIVES,
 };
 
 pub fn disassemble(bytecode: &Bytecode) {
@@ -66,6 +67,11 @@ fn disassemble_instructions(instructions: &Instructions, debug_info: &DebugInfo)
                             print!("{}", format!("str[{}]", operand).magenta());
                         } else if is_global_index(&opcode, i) {
                             print!("{}", format!("global[{}]", operand).blue());
+                        } else if is_spill_slot(&opcode, i) {
+                            print!("{}", format!("slot[{}]", operand).blue());
+                        } else if is_native_index(&opcode, i) {
+                            let name = NATIVES.get(operand as usize).map_or("?", |n| n.name);
+                            print!("{}", format!("native[{}]", name).magenta());
                         } else {
                             print!("{}", format!("{:#04x}", operand).white());
                         }
@@ -81,6 +87,8 @@ fn disassemble_instructions(instructions: &Instructions, debug_info: &DebugInfo)
                             print!("{}", format!("str[{}]", operand).magenta());
                         } else if is_global_index(&opcode, i) {
                             print!("{}", format!("global[{}]", operand).blue());
+                        } else if is_spill_slot(&opcode, i) {
+                            print!("{}", format!("slot[{}]", operand).blue());
                         } else {
                             print!("{}", format!("{:#04x}", operand).white());
                         }
@@ -101,7 +109,12 @@ fn disassemble_instructions(instructions: &Instructions, debug_info: &DebugInfo)
 fn is_register_operand(opcode: &OpCode, operand_index: usize) -> bool {
     match opcode {
         // All register operands for each instruction type
-        OpCode::LoadConstInt | OpCode::LoadConstFloat | OpCode::LoadString => {
+        OpCode::LoadConstInt
+        | OpCode::LoadConstFloat
+        | OpCode::LoadString
+        | OpCode::LoadConstIntWide
+        | OpCode::LoadConstFloatWide
+        | OpCode::LoadStringWide => {
             operand_index == 0 // dest_reg
         }
         OpCode::LoadNil | OpCode::LoadTrue | OpCode::LoadFalse => {
@@ -120,7 +133,14 @@ fn is_register_operand(opcode: &OpCode, operand_index: usize) -> bool {
         | OpCode::ConcatString => {
             true // All 3 operands are registers: dest, left, right
         }
-        OpCode::NegateInt | OpCode::NegateFloat | OpCode::Not | OpCode::Move => {
+        OpCode::NegateInt
+        | OpCode::NegateFloat
+        | OpCode::Not
+        | OpCode::IntToFloat
+        | OpCode::FloatToInt
+        | OpCode::StringLength
+        | OpCode::ToString
+        | OpCode::Move => {
             true // Both operands are registers: dest, src
         }
         OpCode::LessInt
@@ -131,18 +151,34 @@ fn is_register_operand(opcode: &OpCode, operand_index: usize) -> bool {
         | OpCode::LessEqualFloat
         | OpCode::GreaterFloat
         | OpCode::GreaterEqualFloat
+        | OpCode::LessString
+        | OpCode::LessEqualString
+        | OpCode::GreaterString
+        | OpCode::GreaterEqualString
         | OpCode::Equal
         | OpCode::NotEqual => {
             true // All 3 operands are registers: dest, left, right
         }
-        OpCode::LoadGlobal | OpCode::LogAddr => {
+        OpCode::StringIndex | OpCode::StringSlice => {
+            true // dest, string and the character indices
+        }
+        OpCode::LoadGlobal | OpCode::LoadGlobalWide | OpCode::LogAddr => {
             operand_index == 0 // dest_reg (operand 1 is global index)
         }
-        OpCode::StoreGlobal => {
+        OpCode::StoreGlobal | OpCode::StoreGlobalWide => {
             operand_index == 1 // src_reg (operand 0 is global index)
         }
+        OpCode::Reload => {
+            operand_index == 0 // dest_reg (operand 1 is the slot)
+        }
+        OpCode::Spill => {
+            operand_index == 1 // src_reg (operand 0 is the slot)
+        }
 
-        OpCode::JumpIfFalse | OpCode::JumpUncond => true,
+        OpCode::JumpIfFalse
+        | OpCode::JumpUncond
+        | OpCode::JumpIfFalseWide
+        | OpCode::JumpUncondWide => true,
 
         OpCode::ArrayNewFixed => {
             operand_index == 0 // first operand is dest
@@ -161,6 +197,34 @@ fn is_register_operand(opcode: &OpCode, operand_index: usize) -> bool {
         OpCode::ArraySetReg => true,
         OpCode::ArrayPush => true,
 
+        OpCode::StructNew => {
+            operand_index == 0 // dest_reg (operand 1 is the layout string)
+        }
+        OpCode::StructGet => {
+            operand_index != 2 // operand 2 is the field slot
+        }
+        OpCode::StructSet => {
+            operand_index != 1 // operand 1 is the field slot
+        }
+
+        OpCode::EnumNew => {
+            operand_index == 0 // dest_reg (operand 1 is the variant name string)
+        }
+        OpCode::EnumTag => true,
+
+        OpCode::CheckStep | OpCode::Freeze => true,
+
+        OpCode::Call | OpCode::CallWide => {
+            // operand 0: dest_reg, operand 1: target offset,
+            // operand 2: window start, operand 3: argument count
+            operand_index == 0 || operand_index == 2
+        }
+        OpCode::CallNative => {
+            // operand 1 is the native's index in NATIVES
+            operand_index == 0 || operand_index == 2
+        }
+        OpCode::Return => true,
+
         OpCode::Halt => false,
     }
 }
@@ -169,23 +233,48 @@ fn is_register_operand(opcode: &OpCode, operand_index: usize) -> bool {
 fn is_constant_index(opcode: &OpCode, operand_index: usize) -> bool {
     matches!(
         (opcode, operand_index),
-        (OpCode::LoadConstInt, 1) | (OpCode::LoadConstFloat, 1)
+        (OpCode::LoadConstInt, 1)
+            | (OpCode::LoadConstFloat, 1)
+            | (OpCode::LoadConstIntWide, 1)
+            | (OpCode::LoadConstFloatWide, 1)
     )
 }
 
 /// Check if an operand is a string table index
 fn is_string_index(opcode: &OpCode, operand_index: usize) -> bool {
-    matches!((opcode, operand_index), (OpCode::LoadString, 1))
+    matches!(
+        (opcode, operand_index),
+        (OpCode::LoadString, 1)
+            | (OpCode::LoadStringWide, 1)
+            | (OpCode::StructNew, 1)
+            | (OpCode::EnumNew, 1)
+    )
 }
 
 /// Check if an operand is a global variable index
 fn is_global_index(opcode: &OpCode, operand_index: usize) -> bool {
     matches!(
         (opcode, operand_index),
-        (OpCode::LoadGlobal, 1) | (OpCode::StoreGlobal, 0)
+        (OpCode::LoadGlobal, 1)
+            | (OpCode::StoreGlobal, 0)
+            | (OpCode::LoadGlobalWide, 1)
+            | (OpCode::StoreGlobalWide, 0)
+    )
+}
+
+/// Check if an operand is a spill slot
+fn is_spill_slot(opcode: &OpCode, operand_index: usize) -> bool {
+    matches!(
+        (opcode, operand_index),
+        (OpCode::Spill, 0) | (OpCode::Reload, 1)
     )
 }
 
+/// Check if an operand is an index into the native function table
+fn is_native_index(opcode: &OpCode, operand_index: usize) -> bool {
+    matches!((opcode, operand_index), (OpCode::CallNative, 1))
+}
+
 fn disassemble_constants(constants: &Vec<RuntimeValue>) {
     println!("{}", "--== Constants ==--".bright_yellow().bold());
 
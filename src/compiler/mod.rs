@@ -1,4 +1,5 @@
 pub mod compiler;
+pub mod control_flow;
 pub mod debug_info;
 pub mod disassembler;
 pub mod register_allocator;
@@ -1,21 +1,31 @@
 use crate::{
+    compiler::control_flow::ControlFlowGraph,
     error_handler::errors::VynError,
     ir::ir_instr::{VynIROC, VynIROpCode},
     utils::Span,
+    vyn_vm::vm::MAX_SPILL_SLOTS,
 };
 use std::collections::{HashMap, HashSet};
 
+// Registers set aside for reloading spilled values, the most an
+// instruction reads or writes at once
+const SCRATCH_REGISTERS: u8 = 3;
+
 /*
  * Register allocator that maps virtual registers to physical registers
  * with register reuse based on liveness analysis.
  *
  * Performs two-phase allocation:
- * 1. Liveness analysis (backward pass) - determines which virtual registers
- *    are live (still needed) after each instruction
+ * 1. Liveness analysis (dataflow over the control-flow graph) - determines
+ *    which virtual registers are live (still needed) after each instruction
  * 2. Register allocation (forward pass) - assigns physical registers to
  *    virtual registers, reusing physical registers when virtual ones die
  *
- * -- Entry method: `.analyze_liveness()` then `.allocate()`
+ * When more virtual registers are live at once than there are physical ones,
+ * some are spilled: they live in a VM-side slot for their whole lifetime and
+ * go through a scratch register each time they are read or written.
+ *
+ * -- Entry method: `.analyze_liveness()`, `.plan_spills()` then `.allocate()`
  * -- Max registers: Configurable (typically 256 for your VM)
  * */
 pub struct RegisterAllocator {
@@ -35,6 +45,16 @@ pub struct RegisterAllocator {
 
     // Maximum number of physical registers available in the VM
     max_registers: u8,
+
+    // Registers handed out by allocate(), the rest are scratch registers
+    allocatable: u8,
+    scratch_in_use: u8,
+
+    // Spilled virtual register -> its slot
+    spill_slots: HashMap<u32, u16>,
+
+    // Virtual registers that must stay in their physical register
+    pinned: HashSet<u32>,
 }
 
 impl RegisterAllocator {
@@ -52,22 +72,29 @@ impl RegisterAllocator {
             live_in: Vec::new(),
             live_out: Vec::new(),
             max_registers,
+            allocatable: max_registers,
+            scratch_in_use: 0,
+            spill_slots: HashMap::new(),
+            pinned: HashSet::new(),
         }
     }
 
     /*
-     * Performs liveness analysis on all instructions (backward pass)
+     * Performs liveness analysis on all instructions
      *
      * Computes which virtual registers are "live" (still needed) before
-     * and after each instruction. A register is live if its value will be
-     * used in a future instruction.
+     * and after each instruction. A register is live if its value may be
+     * used by a later instruction on some path through the program.
      *
      * Algorithm:
-     * - Start from the last instruction and work backwards
-     * - For each instruction:
-     *   1. live_out[i] = live_in[i+1] (what's live after = what's live before next)
-     *   2. live_in[i] = (live_out[i] - def[i]) ∪ use[i]
-     *      (remove what's written, add what's read)
+     * - Split the instructions into basic blocks (see ControlFlowGraph)
+     * - Solve the dataflow equations per block until nothing changes:
+     *   1. live_out[b] = ∪ live_in[s] for every successor s of b
+     *   2. live_in[b] = (live_out[b] - def[b]) ∪ use[b]
+     *      (use[b] only counts reads that come before a write in b)
+     * - Walk each block backwards from its live_out to get the sets of
+     *   every instruction
+     * - Close the holes in every live range (see close_live_ranges)
      *
      * -- Arguments: [&mut self], instructions - slice of IR instructions
      * -- Return value: void (stores results in self.live_in and self.live_out)
@@ -82,30 +109,203 @@ impl RegisterAllocator {
         self.live_in = vec![HashSet::new(); inst_len];
         self.live_out = vec![HashSet::new(); inst_len];
 
-        // Iterate backwards to propagate liveness information
-        for i in (0..inst_len).rev() {
-            let inst = &instructions[i];
+        let cfg = ControlFlowGraph::build(instructions);
+
+        let mut block_uses: Vec<HashSet<u32>> = Vec::with_capacity(cfg.blocks.len());
+        let mut block_defs: Vec<HashSet<u32>> = Vec::with_capacity(cfg.blocks.len());
+        for block in &cfg.blocks {
+            let mut uses = HashSet::new();
+            let mut defs = HashSet::new();
+
+            for inst in &instructions[block.start..block.end] {
+                for used in self.get_uses(inst) {
+                    if !defs.contains(&used) {
+                        uses.insert(used);
+                    }
+                }
+                defs.extend(self.get_def(inst));
+            }
 
-            // live_out[i] = live_in[i+1] (if there is a next instruction)
-            if i + 1 < inst_len {
-                self.live_out[i] = self.live_in[i + 1].clone();
+            block_uses.push(uses);
+            block_defs.push(defs);
+        }
+
+        // Blocks are visited last to first, which settles straight-line
+        // code in one pass, every loop needs at most one more
+        let mut block_in: Vec<HashSet<u32>> = vec![HashSet::new(); cfg.blocks.len()];
+        let mut block_out: Vec<HashSet<u32>> = vec![HashSet::new(); cfg.blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for (b, block) in cfg.blocks.iter().enumerate().rev() {
+                let out: HashSet<u32> = block
+                    .successors
+                    .iter()
+                    .flat_map(|&s| block_in[s].iter().copied())
+                    .collect();
+
+                let mut live_in: HashSet<u32> = out.difference(&block_defs[b]).copied().collect();
+                live_in.extend(block_uses[b].iter().copied());
+
+                if live_in != block_in[b] {
+                    block_in[b] = live_in;
+                    changed = true;
+                }
+                block_out[b] = out;
             }
+        }
+
+        for (b, block) in cfg.blocks.iter().enumerate() {
+            let mut live = block_out[b].clone();
 
-            // live_in[i] = (live_out[i] - def) ∪ uses
-            let mut live_in = self.live_out[i].clone();
+            for i in (block.start..block.end).rev() {
+                self.live_out[i] = live.clone();
 
-            // Remove what this instruction defines (writes to)
+                // Remove what this instruction defines (writes to)
+                if let Some(def) = self.get_def(&instructions[i]) {
+                    live.remove(&def);
+                }
+
+                // Add what this instruction uses (reads from)
+                live.extend(self.get_uses(&instructions[i]));
+
+                self.live_in[i] = live.clone();
+            }
+        }
+
+        self.close_live_ranges(instructions);
+    }
+
+    /*
+     * Widens every live range to a single unbroken span of instructions
+     *
+     * Registers are handed out in instruction order, and a virtual register
+     * keeps its physical register for as long as it is live. A range with a
+     * hole, e.g. a variable reassigned in one branch and read after the
+     * `if`, would free its register inside the hole while another path
+     * still needs the value. So each virtual register is kept live from the
+     * first instruction that touches it to the last one.
+     *
+     * -- Arguments: [&mut self], instructions - slice of IR instructions
+     * -- Return value: void
+     * */
+    fn close_live_ranges(&mut self, instructions: &[VynIROpCode]) {
+        let mut ranges: HashMap<u32, (usize, usize)> = HashMap::new();
+        let mut touch = |virt: u32, i: usize| {
+            let range = ranges.entry(virt).or_insert((i, i));
+            range.0 = range.0.min(i);
+            range.1 = range.1.max(i);
+        };
+
+        for (i, inst) in instructions.iter().enumerate() {
+            for &virt in self.live_in[i].iter().chain(&self.live_out[i]) {
+                touch(virt, i);
+            }
             if let Some(def) = self.get_def(inst) {
-                live_in.remove(&def);
+                touch(def, i);
             }
+        }
 
-            // Add what this instruction uses (reads from)
-            for used in self.get_uses(inst) {
-                live_in.insert(used);
+        for (virt, (first, last)) in ranges {
+            for i in first..=last {
+                if i > first {
+                    self.live_in[i].insert(virt);
+                }
+                if i < last {
+                    self.live_out[i].insert(virt);
+                }
             }
+        }
+    }
+
+    /*
+     * Chooses which virtual registers to spill (spill-everywhere)
+     *
+     * Wherever more registers are live than can be allocated, the live one
+     * whose live range covers the most instructions is spilled, as it holds
+     * a register the longest while being needed the least often per
+     * instruction. A spilled register stays in its slot for its whole
+     * lifetime so its location is the same on every control flow path.
+     *
+     * A call's arguments are copied above every register in use, so calls
+     * also leave room for them.
+     *
+     * -- Arguments: [&mut self], instructions - slice of IR instructions
+     *               span - reported if the slots run out
+     * -- Return value: Result<(), VynError>
+     *
+     * -- Notes:
+     * # Must be called after analyze_liveness()
+     * # Pinned registers (parameters) are never spilled
+     * */
+    pub fn plan_spills(&mut self, instructions: &[VynIROpCode], span: Span) -> Result<(), VynError> {
+        let defs: Vec<Option<u32>> = instructions.iter().map(|inst| self.get_def(inst)).collect();
+        let pressure_at = |i: usize, live_in: &[HashSet<u32>]| {
+            let mut live = live_in[i].clone();
+            live.extend(defs[i]);
+            live
+        };
+        let call_args = |i: usize| match &instructions[i].node {
+            VynIROC::Call { args, .. } | VynIROC::CallNative { args, .. } => args.len(),
+            _ => 0,
+        };
+
+        let fits = (0..instructions.len()).all(|i| {
+            pressure_at(i, &self.live_in).len() + call_args(i) <= self.max_registers as usize
+        });
+        if fits {
+            return Ok(());
+        }
 
-            self.live_in[i] = live_in;
+        self.allocatable = self.max_registers - SCRATCH_REGISTERS;
+
+        let mut range_lengths: HashMap<u32, usize> = HashMap::new();
+        for live in &self.live_in {
+            for &virt in live {
+                *range_lengths.entry(virt).or_default() += 1;
+            }
+        }
+
+        let mut spilled: HashSet<u32> = HashSet::new();
+
+        for i in 0..instructions.len() {
+            let mut live: Vec<u32> = pressure_at(i, &self.live_in)
+                .into_iter()
+                .filter(|virt| !spilled.contains(virt))
+                .collect();
+
+            let limit = (self.allocatable as usize)
+                .min((self.max_registers as usize).saturating_sub(call_args(i)));
+
+            while live.len() > limit {
+                let Some((idx, &victim)) = live
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, virt)| !self.pinned.contains(virt))
+                    .max_by_key(|(_, virt)| (range_lengths.get(virt).copied().unwrap_or(0), **virt))
+                else {
+                    break;
+                };
+
+                live.swap_remove(idx);
+                spilled.insert(victim);
+            }
+        }
+
+        if spilled.len() > MAX_SPILL_SLOTS {
+            return Err(VynError::RegisterOverflow { span });
         }
+
+        let mut spilled: Vec<u32> = spilled.into_iter().collect();
+        spilled.sort_unstable();
+        self.spill_slots = spilled
+            .into_iter()
+            .enumerate()
+            .map(|(slot, virt)| (virt, slot as u16))
+            .collect();
+
+        Ok(())
     }
 
     /*
@@ -129,6 +329,7 @@ impl RegisterAllocator {
             VynIROC::LoadConstFloat { dest, .. } => Some(*dest),
             VynIROC::LoadString { dest, .. } => Some(*dest),
             VynIROC::LoadBool { dest, .. } => Some(*dest),
+            VynIROC::LoadNil { dest } => Some(*dest),
 
             VynIROC::AddInt { dest, .. } => Some(*dest),
             VynIROC::AddFloat { dest, .. } => Some(*dest),
@@ -141,6 +342,13 @@ impl RegisterAllocator {
             VynIROC::ExpInt { dest, .. } => Some(*dest),
             VynIROC::ExpFloat { dest, .. } => Some(*dest),
 
+            VynIROC::NegateInt { dest, .. } => Some(*dest),
+            VynIROC::NegateFloat { dest, .. } => Some(*dest),
+            VynIROC::Not { dest, .. } => Some(*dest),
+
+            VynIROC::IntToFloat { dest, .. } => Some(*dest),
+            VynIROC::FloatToInt { dest, .. } => Some(*dest),
+
             VynIROC::CompareEqual { dest, .. } => Some(*dest),
             VynIROC::CompareNotEqual { dest, .. } => Some(*dest),
             VynIROC::CompareLessInt { dest, .. } => Some(*dest),
@@ -151,8 +359,41 @@ impl RegisterAllocator {
             VynIROC::CompareLessEqualFloat { dest, .. } => Some(*dest),
             VynIROC::CompareGreaterEqualInt { dest, .. } => Some(*dest),
             VynIROC::CompareGreaterEqualFloat { dest, .. } => Some(*dest),
+            VynIROC::CompareLessString { dest, .. } => Some(*dest),
+            VynIROC::CompareLessEqualString { dest, .. } => Some(*dest),
+            VynIROC::CompareGreaterString { dest, .. } => Some(*dest),
+            VynIROC::CompareGreaterEqualString { dest, .. } => Some(*dest),
+
+            VynIROC::ConcatString { dest, .. } => Some(*dest),
+            VynIROC::StringLength { dest, .. } => Some(*dest),
+            VynIROC::StringIndex { dest, .. } => Some(*dest),
+            VynIROC::StringSlice { dest, .. } => Some(*dest),
+            VynIROC::ToString { dest, .. } => Some(*dest),
 
             VynIROC::Move { dest, .. } => Some(*dest),
+            VynIROC::LoadGlobal { dest, .. } => Some(*dest),
+            VynIROC::StoreGlobal { .. } => None,
+
+            VynIROC::NewArray { dest, .. } => Some(*dest),
+            VynIROC::NewSequence { dest, .. } => Some(*dest),
+            VynIROC::ArrayGet { dest, .. } => Some(*dest),
+            // Element writes mutate the heap object, not the register
+            VynIROC::ArraySet { .. } => None,
+            VynIROC::ArraySetReg { .. } => None,
+            VynIROC::ArrayPush { .. } => None,
+
+            VynIROC::NewStruct { dest, .. } => Some(*dest),
+            VynIROC::StructGet { dest, .. } => Some(*dest),
+            // Field writes mutate the heap object, not the register
+            VynIROC::StructSet { .. } => None,
+
+            VynIROC::NewEnum { dest, .. } => Some(*dest),
+            VynIROC::EnumTag { dest, .. } => Some(*dest),
+
+            VynIROC::CheckStep { .. } | VynIROC::Freeze { .. } => None,
+
+            VynIROC::Call { dest, .. } | VynIROC::CallNative { dest, .. } => Some(*dest),
+            VynIROC::Return { .. } => None,
 
             VynIROC::LogAddr { .. } => None,
             VynIROC::JumpIfFalse { .. } => None,
@@ -182,6 +423,7 @@ impl RegisterAllocator {
             VynIROC::LoadConstFloat { .. } => vec![],
             VynIROC::LoadString { .. } => vec![],
             VynIROC::LoadBool { .. } => vec![],
+            VynIROC::LoadNil { .. } => vec![],
 
             VynIROC::AddInt { left, right, .. } => vec![*left, *right],
             VynIROC::AddFloat { left, right, .. } => vec![*left, *right],
@@ -194,6 +436,13 @@ impl RegisterAllocator {
             VynIROC::ExpInt { left, right, .. } => vec![*left, *right],
             VynIROC::ExpFloat { left, right, .. } => vec![*left, *right],
 
+            VynIROC::NegateInt { src, .. } => vec![*src],
+            VynIROC::NegateFloat { src, .. } => vec![*src],
+            VynIROC::Not { src, .. } => vec![*src],
+
+            VynIROC::IntToFloat { src, .. } => vec![*src],
+            VynIROC::FloatToInt { src, .. } => vec![*src],
+
             VynIROC::CompareEqual { left, right, .. } => vec![*left, *right],
             VynIROC::CompareNotEqual { left, right, .. } => vec![*left, *right],
             VynIROC::CompareLessInt { left, right, .. } => vec![*left, *right],
@@ -204,11 +453,49 @@ impl RegisterAllocator {
             VynIROC::CompareLessEqualFloat { left, right, .. } => vec![*left, *right],
             VynIROC::CompareGreaterEqualInt { left, right, .. } => vec![*left, *right],
             VynIROC::CompareGreaterEqualFloat { left, right, .. } => vec![*left, *right],
+            VynIROC::CompareLessString { left, right, .. } => vec![*left, *right],
+            VynIROC::CompareLessEqualString { left, right, .. } => vec![*left, *right],
+            VynIROC::CompareGreaterString { left, right, .. } => vec![*left, *right],
+            VynIROC::CompareGreaterEqualString { left, right, .. } => vec![*left, *right],
+
+            VynIROC::ConcatString { left, right, .. } => vec![*left, *right],
+            VynIROC::StringLength { src, .. } => vec![*src],
+            VynIROC::StringIndex { string, index, .. } => vec![*string, *index],
+            VynIROC::StringSlice {
+                string, start, end, ..
+            } => vec![*string, *start, *end],
+            VynIROC::ToString { src, .. } => vec![*src],
 
             VynIROC::Move { src, .. } => vec![*src],
+            VynIROC::LoadGlobal { .. } => vec![],
+            VynIROC::StoreGlobal { src, .. } => vec![*src],
             VynIROC::LogAddr { addr } => vec![*addr],
             VynIROC::JumpIfFalse { condition_reg, .. } => vec![*condition_reg],
 
+            VynIROC::NewArray { .. } => vec![],
+            VynIROC::NewSequence { .. } => vec![],
+            VynIROC::ArraySet { array, value, .. } => vec![*array, *value],
+            VynIROC::ArraySetReg {
+                array,
+                index,
+                value,
+            } => vec![*array, *index, *value],
+            VynIROC::ArrayGet { array, index, .. } => vec![*array, *index],
+            VynIROC::ArrayPush { array, value } => vec![*array, *value],
+
+            VynIROC::NewStruct { .. } => vec![],
+            VynIROC::StructSet { target, value, .. } => vec![*target, *value],
+            VynIROC::StructGet { target, .. } => vec![*target],
+
+            VynIROC::NewEnum { .. } => vec![],
+            VynIROC::EnumTag { target, .. } => vec![*target],
+
+            VynIROC::CheckStep { step } => vec![*step],
+            VynIROC::Freeze { value } => vec![*value],
+
+            VynIROC::Call { args, .. } | VynIROC::CallNative { args, .. } => args.clone(),
+            VynIROC::Return { value } => value.iter().copied().collect(),
+
             VynIROC::JumpUncond { .. } => vec![],
             VynIROC::Label(..) => vec![],
             VynIROC::Halt => vec![],
@@ -243,19 +530,26 @@ impl RegisterAllocator {
         inst_index: usize,
         span: Span,
     ) -> Result<u8, VynError> {
+        // Redefinition of an already allocated register (e.g. both arms of
+        // a short-circuit writing the same destination): mark it in use again
         if let Some(&phys) = self.allocation.get(&virtual_reg) {
+            self.used_physical.insert(phys);
             return Ok(phys);
         }
 
-        for phys in 0..self.max_registers {
+        for phys in 0..self.allocatable {
             if !self.used_physical.contains(&phys) {
+                // A dead virtual register may still map to it, drop that
+                // mapping so freeing it again can't release the new owner
+                self.allocation.retain(|_, &mut v| v != phys);
+
                 self.allocation.insert(virtual_reg, phys);
                 self.used_physical.insert(phys);
                 return Ok(phys);
             }
         }
 
-        if let Some(phys) = self.find_spillable_register(inst_index) {
+        if let Some(phys) = self.find_reusable_register(inst_index) {
             self.allocation.retain(|_, &mut v| v != phys);
 
             self.allocation.insert(virtual_reg, phys);
@@ -267,30 +561,98 @@ impl RegisterAllocator {
     }
 
     /*
-     * Finds a physical register that can be reused (spilled)
+     * Pins a virtual register to a specific physical register
+     *
+     * Used for function parameters, which the caller places in the first
+     * registers of the callee's window
      *
-     * Looks for a physical register that currently holds a virtual register
-     * whose value is no longer needed (dead/not live) at the current instruction.
+     * -- Arguments: [&mut self], virtual_reg - virtual register to pin
+     *               physical_reg - physical register holding its value
+     * -- Return value: void
+     * */
+    pub fn bind(&mut self, virtual_reg: u32, physical_reg: u8) {
+        self.allocation.insert(virtual_reg, physical_reg);
+        self.used_physical.insert(physical_reg);
+        self.pinned.insert(virtual_reg);
+    }
+
+    /*
+     * Gets the slot of a spilled virtual register
+     *
+     * -- Arguments: [&self], virtual_reg - the virtual register ID to look up
+     * -- Return value: Some(slot) if plan_spills() spilled it
+     * */
+    pub fn spill_slot(&self, virtual_reg: u32) -> Option<u16> {
+        self.spill_slots.get(&virtual_reg).copied()
+    }
+
+    /*
+     * Hands out a scratch register for the current instruction
+     *
+     * -- Arguments: [&mut self], span - reported if none is left
+     * -- Return value: Result<u8, VynError>
+     * */
+    pub fn take_scratch(&mut self, span: Span) -> Result<u8, VynError> {
+        if self.allocatable + self.scratch_in_use >= self.max_registers {
+            return Err(VynError::RegisterOverflow { span });
+        }
+
+        let phys = self.allocatable + self.scratch_in_use;
+        self.scratch_in_use += 1;
+        Ok(phys)
+    }
+
+    /*
+     * Makes every scratch register available again once an instruction is done
+     * */
+    pub fn release_scratch(&mut self) {
+        self.scratch_in_use = 0;
+    }
+
+    /*
+     * Gets the first physical register above every register in use
+     *
+     * A call's register window starts here so the callee can never
+     * overwrite a value the caller still needs
+     *
+     * -- Arguments: [&self]
+     * -- Return value: usize - first free register index past the highest used one
+     * */
+    pub fn window_start(&self) -> usize {
+        self.used_physical
+            .iter()
+            .max()
+            .map(|&reg| reg as usize + 1)
+            .unwrap_or(0)
+    }
+
+    /*
+     * Finds a physical register that can be reused
+     *
+     * Looks for a physical register that only holds virtual registers
+     * whose values are no longer needed (dead/not live) at the current instruction.
      *
      * -- Arguments: [&self], inst_index - current instruction index
-     * -- Return value: Some(physical_reg_id) if a spillable register is found,
+     * -- Return value: Some(physical_reg_id) if a reusable register is found,
      *                  None if all physical registers hold live values
      *
      * -- Algorithm:
-     * # Check each allocated virtual->physical mapping
-     * # If the virtual register is NOT in live_in[inst_index], its physical
-     *   register can be reused (the value is no longer needed)
+     * # Collect the physical registers of every virtual register in
+     *   live_in[inst_index]
+     * # Any other physical register can be reused, even if a dead virtual
+     *   register still maps to it
      * */
-    fn find_spillable_register(&self, inst_index: usize) -> Option<u8> {
+    fn find_reusable_register(&self, inst_index: usize) -> Option<u8> {
         let live = &self.live_in[inst_index];
 
-        for (&virt, &phys) in &self.allocation {
-            if !live.contains(&virt) {
-                return Some(phys);
-            }
-        }
+        let held: HashSet<u8> = self
+            .allocation
+            .iter()
+            .filter(|(virt, _)| live.contains(virt))
+            .map(|(_, &phys)| phys)
+            .collect();
 
-        None
+        (0..self.allocatable).find(|phys| !held.contains(phys))
     }
 
     /*
@@ -324,32 +686,34 @@ impl RegisterAllocator {
      *
      * -- Arguments: [&mut self],
      *               virtual_reg - the virtual register to potentially free
-     *               inst_index - instruction index to check liveness after
+     *               inst_index - instruction that just read it
      * -- Return value: void
      *
      * -- Notes:
      * # Should be called after compiling instructions that use registers
-     * # Only frees if the virtual register is NOT in live_out[inst_index]
+     * # Only frees if the virtual register is NOT in live_out[inst_index],
+     *   a value the next instruction still reads is live out of this one
      * # The physical register becomes available for allocation again
      *
      * -- Usage pattern:
      * # After compiling "AddInt { dest: 2, left: 0, right: 1 }":
-     *   - free(0, inst_index + 1)  // free left operand if dead after next inst
-     *   - free(1, inst_index + 1)  // free right operand if dead after next inst
+     *   - free(0, inst_index)  // free left operand if dead after this inst
+     *   - free(1, inst_index)  // free right operand if dead after this inst
      *
      * -- Notes:
      * # we keep the mapping in self.allocation for debugging,
-     * # but mark the physical register as free
+     * # but mark the physical register as free. allocate() drops the mapping
+     *   once another virtual register takes the physical one
      * */
     pub fn free(&mut self, virtual_reg: u32, inst_index: usize) {
         if inst_index >= self.live_out.len() {
             return;
         }
 
-        if !self.live_out[inst_index].contains(&virtual_reg) {
-            if let Some(&phys) = self.allocation.get(&virtual_reg) {
-                self.used_physical.remove(&phys);
-            }
+        if !self.live_out[inst_index].contains(&virtual_reg)
+            && let Some(&phys) = self.allocation.get(&virtual_reg)
+        {
+            self.used_physical.remove(&phys);
         }
     }
 
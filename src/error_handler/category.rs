@@ -11,6 +11,12 @@ impl VynError {
             VynError::ExpectedType { .. } => "Syntax",
             VynError::StaticRequiresConstant { .. } => "Syntax",
             VynError::IllegalLoopInterruptToken { .. } => "Syntax",
+            VynError::ReturnOutsideFunction { .. } => "Syntax",
+            VynError::NestedFunctionDeclaration { .. } => "Syntax",
+            VynError::IntegerLiteralOverflow { .. } => "Syntax",
+            VynError::UnterminatedInterpolation { .. } => "Syntax",
+            VynError::TypeRedeclaration { .. } => "Syntax",
+            VynError::InvalidPattern { .. } => "Syntax",
 
             // Type errors
             VynError::TypeMismatch { .. } => "Type",
@@ -28,6 +34,32 @@ impl VynError {
             VynError::ArrayLengthMismatch { .. } => "Type",
             VynError::InvalidUnaryOperator { .. } => "Type",
             VynError::InvalidBinaryOperator { .. } => "Type",
+            VynError::NotCallable { .. } => "Type",
+            VynError::ArgumentCountMismatch { .. } => "Type",
+            VynError::MissingReturn { .. } => "Type",
+            VynError::FunctionAsValue { .. } => "Type",
+            VynError::ZeroLoopStep {
+                is_static: true, ..
+            } => "Type",
+            VynError::InvalidCast { .. } => "Type",
+            VynError::DuplicateField { .. } => "Type",
+            VynError::TooManyFields { .. } => "Type",
+            VynError::UnknownField { .. } => "Type",
+            VynError::MissingField { .. } => "Type",
+            VynError::InvalidFieldAccess { .. } => "Type",
+            VynError::MissingInitializer { .. } => "Type",
+            VynError::UseBeforeAssignment { .. } => "Type",
+            VynError::DuplicateVariant { .. } => "Type",
+            VynError::TooManyVariants { .. } => "Type",
+            VynError::UnknownVariant { .. } => "Type",
+            VynError::PayloadCountMismatch { .. } => "Type",
+            VynError::InvalidMatchTarget { .. } => "Type",
+            VynError::NonExhaustiveMatch { .. } => "Type",
+            VynError::UnreachableMatchArm { .. } => "Type",
+            VynError::PossiblyNil { .. } => "Type",
+            VynError::InvalidSlice { .. } => "Type",
+            VynError::ReadOnlyField { .. } => "Type",
+            VynError::NoMatchingOverload { .. } => "Type",
 
             // Static evaluation errors
             VynError::CircularStaticDependency { .. } => "StaticEval",
@@ -36,15 +68,17 @@ impl VynError {
             VynError::NotStaticExpression { .. } => "StaticEval",
             VynError::InvalidStaticOperation { .. } => "StaticEval",
             VynError::StaticOverflow { .. } => "StaticEval",
-            VynError::NegativeExponent { .. } => "StaticEval",
             VynError::NegativeArraySize { .. } => "StaticEval",
+            VynError::ArraySizeTooLarge { .. } => "StaticEval",
             VynError::ArraySizeNotStatic { .. } => "StaticEval",
 
             // Index errors
             VynError::IndexOutOfBounds { .. } => "Index",
+            VynError::SliceOutOfBounds { .. } => "Index",
 
             // Compiler errors
             VynError::RegisterOverflow { .. } => "Compiler",
+            VynError::TooManyGlobals { .. } => "Compiler",
             VynError::NotImplemented { .. } => "Compiler",
             VynError::UnknownAST { .. } => "Compiler",
             VynError::UndefinedIdentifier { .. } => "Compiler",
@@ -54,6 +88,24 @@ impl VynError {
             VynError::UnaryOperationError { .. } => "Runtime",
             VynError::ComparisonOperationError { .. } => "Runtime",
             VynError::DivisionByZero { .. } => "Runtime",
+            VynError::FrozenMutation { .. } => "Runtime",
+            VynError::ZeroLoopStep {
+                is_static: false, ..
+            } => "Runtime",
+            VynError::NegativeExponent { .. } => "Runtime",
+            VynError::StackOverflow { .. } => "Runtime",
+            VynError::IntegerOverflow { .. } => "Runtime",
+            VynError::InvalidConversion { .. } => "Runtime",
+            VynError::UninitializedGlobal { .. } => "Runtime",
+            VynError::NativeError { .. } => "Runtime",
+
+            // I/O errors
+            VynError::IoError { .. } => "IO",
+
+            // Bytecode file errors
+            VynError::MalformedBytecode { .. } => "Bytecode",
+            VynError::UnsupportedBytecodeVersion { .. } => "Bytecode",
+            VynError::InvalidBytecode { .. } => "Bytecode",
         }
     }
 }
@@ -1,4 +1,10 @@
-use crate::{error_handler::errors::VynError, utils::Span};
+use crate::{
+    error_handler::{
+        errors::VynError,
+        warnings::{Lint, VynWarning},
+    },
+    utils::Span,
+};
 use colored::*;
 
 impl VynError {
@@ -10,9 +16,11 @@ impl VynError {
             VynError::InvalidTypeName { span, .. } => *span,
             VynError::ExpectedType { span, .. } => *span,
             VynError::RegisterOverflow { span, .. } => *span,
+            VynError::TooManyGlobals { span } => *span,
             VynError::NotImplemented { span, .. } => *span,
             VynError::InvalidIndexing { span, .. } => *span,
             VynError::IndexOutOfBounds { span, .. } => *span,
+            VynError::SliceOutOfBounds { span, .. } => *span,
             VynError::StaticRequiresConstant { span, .. } => *span,
 
             VynError::TypeMismatch { span, .. } => *span,
@@ -32,6 +40,7 @@ impl VynError {
             VynError::StaticOverflow { span, .. } => *span,
             VynError::NegativeExponent { span, .. } => *span,
             VynError::NegativeArraySize { span, .. } => *span,
+            VynError::ArraySizeTooLarge { span, .. } => *span,
             VynError::ArraySizeNotStatic { span, .. } => *span,
             VynError::InvalidUnaryOperator { span, .. } => *span,
             VynError::IllegalLoopInterruptToken { span, .. } => *span,
@@ -49,6 +58,49 @@ impl VynError {
             VynError::UnaryOperationError { span, .. } => *span,
             VynError::ComparisonOperationError { span, .. } => *span,
             VynError::DivisionByZero { span } => *span,
+            VynError::ZeroLoopStep { span, .. } => *span,
+            VynError::FrozenMutation { span } => *span,
+            VynError::StackOverflow { span, .. } => *span,
+            VynError::IntegerOverflow { span, .. } => *span,
+            VynError::InvalidConversion { span, .. } => *span,
+            VynError::UninitializedGlobal { span } => *span,
+            VynError::NativeError { span, .. } => *span,
+            VynError::IoError { span, .. } => *span,
+
+            VynError::ReturnOutsideFunction { span } => *span,
+            VynError::NestedFunctionDeclaration { span, .. } => *span,
+            VynError::NotCallable { span, .. } => *span,
+            VynError::ArgumentCountMismatch { span, .. } => *span,
+            VynError::MissingReturn { span, .. } => *span,
+            VynError::FunctionAsValue { span, .. } => *span,
+            VynError::InvalidCast { span, .. } => *span,
+            VynError::IntegerLiteralOverflow { span, .. } => *span,
+            VynError::UnterminatedInterpolation { span } => *span,
+            VynError::TypeRedeclaration { span, .. } => *span,
+            VynError::DuplicateField { span, .. } => *span,
+            VynError::TooManyFields { span, .. } => *span,
+            VynError::UnknownField { span, .. } => *span,
+            VynError::MissingField { span, .. } => *span,
+            VynError::InvalidFieldAccess { span, .. } => *span,
+            VynError::MissingInitializer { span, .. } => *span,
+            VynError::UseBeforeAssignment { span, .. } => *span,
+            VynError::InvalidPattern { span } => *span,
+            VynError::DuplicateVariant { span, .. } => *span,
+            VynError::TooManyVariants { span, .. } => *span,
+            VynError::UnknownVariant { span, .. } => *span,
+            VynError::PayloadCountMismatch { span, .. } => *span,
+            VynError::InvalidMatchTarget { span, .. } => *span,
+            VynError::NonExhaustiveMatch { span, .. } => *span,
+            VynError::UnreachableMatchArm { span } => *span,
+            VynError::PossiblyNil { span, .. } => *span,
+            VynError::InvalidSlice { span, .. } => *span,
+            VynError::ReadOnlyField { span, .. } => *span,
+            VynError::NoMatchingOverload { span, .. } => *span,
+
+            // Bytecode files carry no source location
+            VynError::MalformedBytecode { .. } => Span::default(),
+            VynError::UnsupportedBytecodeVersion { .. } => Span::default(),
+            VynError::InvalidBytecode { span, .. } => *span,
         }
     }
 
@@ -66,16 +118,19 @@ impl VynError {
 
         eprintln!();
 
-        // Error caused by section
-        eprintln!("{}", "Error caused by:".white().dimmed().bold());
+        // Errors that don't point into the source have nothing to show here
+        if span != Span::default() {
+            // Error caused by section
+            eprintln!("{}", "Error caused by:".white().dimmed().bold());
 
-        // Main error location
-        self.print_code_snippet(source, span, true);
+            // Main error location
+            print_code_snippet(source, span, true);
 
-        // Additional context based on error type
-        self.print_additional_context(source);
+            // Additional context based on error type
+            self.print_additional_context(source);
 
-        eprintln!();
+            eprintln!();
+        }
 
         // Hint section
         if let Some(hint_text) = self.hint() {
@@ -83,102 +138,121 @@ impl VynError {
         }
     }
 
-    fn print_code_snippet(&self, source: &str, span: Span, highlight: bool) {
-        let lines: Vec<&str> = source.lines().collect();
-
-        if span.line == 0 || span.line > (lines.len() as u32) {
-            eprintln!(
-                "    {} {} {}",
-                format!("Ln {}:{}", span.line, span.start_column).cyan(),
-                "|".white(),
-                "<source unavailable>".dimmed()
-            );
-            return;
-        }
-
-        let line_content = lines[(span.line - 1) as usize];
-        let line_label = format!("Ln {}:{}", span.line, span.start_column);
-
-        // Print the line
-        if highlight {
-            eprintln!(
-                "    {} {} {}",
-                line_label.cyan().bold(),
-                "|".white(),
-                line_content.bold().bright_white(),
-            );
-        } else {
-            eprintln!(
-                "    {} {} {}",
-                line_label.cyan().bold(),
-                "|".white(),
-                line_content.dimmed(),
-            );
-        }
-
-        // Print the pointer
-        let line_prefix_len = line_label.len();
-        let gutter_padding = " ".repeat(line_prefix_len + 3); // +3 for " | "
-
-        // IMPORTANT: Columns are 1-indexed, so subtract 1 for 0-indexed string positioning
-        // Also need to handle the actual character width correctly
-        let start_pos = (span.start_column as usize).saturating_sub(1);
-        let code_padding = " ".repeat(start_pos);
-
-        let width = (span.end_column.saturating_sub(span.start_column) as usize).max(1);
-        let pointer = if width == 1 {
-            "^".to_string()
-        } else {
-            "~".repeat(width)
-        };
-
-        if highlight {
-            eprintln!(
-                "    {}{}{}",
-                gutter_padding,
-                code_padding,
-                pointer.bright_red().bold()
-            );
-        } else {
-            eprintln!(
-                "    {}{}{}",
-                gutter_padding,
-                code_padding,
-                pointer.cyan().dimmed()
-            );
-        }
-    }
-
     fn print_additional_context(&self, source: &str) {
         match self {
             VynError::VariableRedeclaration { original_span, .. } => {
                 eprintln!();
                 eprintln!("{}", "Originally declared here:".white().dimmed());
-                self.print_code_snippet(source, *original_span, false);
+                print_code_snippet(source, *original_span, false);
             }
             VynError::ImmutableMutation { mutation_span, .. } => {
                 eprintln!();
                 eprintln!("{}", "identifier mutated here".white().dimmed());
-                self.print_code_snippet(source, *mutation_span, false);
+                print_code_snippet(source, *mutation_span, false);
             }
             VynError::StaticMutation { mutator_span, .. } => {
                 eprintln!();
                 eprintln!("{}", "identifier mutated here".white().dimmed());
-                self.print_code_snippet(source, *mutator_span, false);
+                print_code_snippet(source, *mutator_span, false);
+            }
+            VynError::UseBeforeAssignment {
+                declaration_span, ..
+            } => {
+                eprintln!();
+                eprintln!("{}", "Declared without a value here:".white().dimmed());
+                print_code_snippet(source, *declaration_span, false);
             }
             _ => {}
         }
     }
 }
 
+/*
+ * Prints the source line a span points into with a marker under the span
+ *
+ * -- Arguments: source - the whole program, span - the part to mark,
+ *               highlight - draw it as the main location instead of context
+ * -- Return value: void
+ * */
+pub(crate) fn print_code_snippet(source: &str, span: Span, highlight: bool) {
+    let lines: Vec<&str> = source.lines().collect();
+
+    if span.line == 0 || span.line > (lines.len() as u32) {
+        eprintln!(
+            "    {} {} {}",
+            format!("Ln {}:{}", span.line, span.start_column).cyan(),
+            "|".white(),
+            "<source unavailable>".dimmed()
+        );
+        return;
+    }
+
+    let line_content = lines[(span.line - 1) as usize];
+    let line_label = format!("Ln {}:{}", span.line, span.start_column);
+
+    // Print the line
+    if highlight {
+        eprintln!(
+            "    {} {} {}",
+            line_label.cyan().bold(),
+            "|".white(),
+            line_content.bold().bright_white(),
+        );
+    } else {
+        eprintln!(
+            "    {} {} {}",
+            line_label.cyan().bold(),
+            "|".white(),
+            line_content.dimmed(),
+        );
+    }
+
+    // Print the pointer
+    let line_prefix_len = line_label.len();
+    let gutter_padding = " ".repeat(line_prefix_len + 3); // +3 for " | "
+
+    // IMPORTANT: Columns are 1-indexed, so subtract 1 for 0-indexed string positioning
+    // Also need to handle the actual character width correctly
+    let start_pos = (span.start_column as usize).saturating_sub(1);
+    let code_padding = " ".repeat(start_pos);
+
+    let width = (span.end_column.saturating_sub(span.start_column) as usize).max(1);
+    let pointer = if width == 1 {
+        "^".to_string()
+    } else {
+        "~".repeat(width)
+    };
+
+    if highlight {
+        eprintln!(
+            "    {}{}{}",
+            gutter_padding,
+            code_padding,
+            pointer.bright_red().bold()
+        );
+    } else {
+        eprintln!(
+            "    {}{}{}",
+            gutter_padding,
+            code_padding,
+            pointer.cyan().dimmed()
+        );
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ErrorCollector {
     errors: Vec<VynError>,
+    // Reported separately, they never stop compilation on their own
+    warnings: Vec<VynWarning>,
 }
 
 impl ErrorCollector {
     pub fn new() -> Self {
-        Self { errors: Vec::new() }
+        Self {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
     }
 
     pub fn add(&mut self, error: VynError) {
@@ -219,7 +293,38 @@ impl ErrorCollector {
         &self.errors
     }
 
+    pub fn add_warning(&mut self, warning: VynWarning) {
+        self.warnings.push(warning);
+    }
+
+    pub fn warnings(&self) -> &[VynWarning] {
+        &self.warnings
+    }
+
+    /*
+     * Prints every warning whose lint isn't allowed
+     *
+     * -- Arguments: [&self], source - the program, allowed - lints to skip
+     * -- Return value: usize - how many warnings were printed
+     * */
+    pub fn report_warnings(&self, source: &str, allowed: &[Lint]) -> usize {
+        let mut warnings: Vec<&VynWarning> = self
+            .warnings
+            .iter()
+            .filter(|warning| !allowed.contains(&warning.lint()))
+            .collect();
+        warnings.sort_by_key(|warning| (warning.span().line, warning.span().start_column));
+
+        for warning in &warnings {
+            warning.report(source);
+            eprintln!();
+        }
+
+        warnings.len()
+    }
+
     pub fn clear(&mut self) {
-        self.errors.clear()
+        self.errors.clear();
+        self.warnings.clear();
     }
 }
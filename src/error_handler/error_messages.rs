@@ -1,4 +1,8 @@
-use crate::{ast::ast::Node, error_handler::errors::VynError};
+use crate::{
+    ast::ast::Node,
+    error_handler::errors::VynError,
+    vyn_vm::vm::{MAX_ARRAY_LENGTH, MAX_GLOBALS},
+};
 
 impl VynError {
     pub fn message(&self) -> String {
@@ -15,6 +19,9 @@ impl VynError {
             VynError::RegisterOverflow { .. } => {
                 "Register overflow: expression is too complex".to_string()
             }
+            VynError::TooManyGlobals { .. } => {
+                format!("Program declares more than {MAX_GLOBALS} top-level variables")
+            }
             VynError::NotImplemented { feature, .. } => {
                 format!("Feature not yet implemented: {}", feature)
             }
@@ -39,6 +46,10 @@ impl VynError {
                 )
             }
             VynError::DivisionByZero { .. } => "Cannot divide by zero".to_string(),
+            VynError::ZeroLoopStep { .. } => "Index loop cannot step by zero".to_string(),
+            VynError::FrozenMutation { .. } => {
+                "Cannot change a value held by a variable declared without '@'".to_string()
+            }
             VynError::TypeAliasRedeclaration { name, .. } => {
                 format!(
                     "Cannot redeclare type alias '{}' in the current scope",
@@ -125,11 +136,14 @@ impl VynError {
                 "Arithmetic overflow in static expression".to_string()
             }
             VynError::NegativeExponent { .. } => {
-                "Cannot use negative exponent in compile-time expression".to_string()
+                "Cannot raise an integer to a negative exponent".to_string()
             }
             VynError::NegativeArraySize { size, .. } => {
                 format!("Array size cannot be negative, got '{}'", size)
             }
+            VynError::ArraySizeTooLarge { size, .. } => {
+                format!("Array size '{size}' is larger than the maximum of {MAX_ARRAY_LENGTH}")
+            }
             VynError::ArraySizeNotStatic { .. } => {
                 "Array size must be a compile-time constant expression".to_string()
             }
@@ -149,6 +163,69 @@ impl VynError {
             VynError::IllegalLoopInterruptToken { token_type, .. } => {
                 format!("Illegal '{token_type}' token found outside of loops")
             }
+            VynError::ReturnOutsideFunction { .. } => {
+                "Illegal 'return' found outside of a function".to_string()
+            }
+            VynError::NestedFunctionDeclaration { name, .. } => {
+                format!("Function '{name}' must be declared at the top level")
+            }
+            VynError::NotCallable { found, .. } => {
+                format!("Cannot call a value of type '{found}'")
+            }
+            VynError::ArgumentCountMismatch {
+                name,
+                expected,
+                got,
+                ..
+            } => {
+                format!("Function '{name}' expects {expected} argument(s) but got {got}")
+            }
+            VynError::MissingReturn { name, expected, .. } => {
+                format!("Function '{name}' does not return a value of type '{expected}' on every path")
+            }
+            VynError::FunctionAsValue { name, .. } => {
+                format!("Function '{name}' cannot be used as a value")
+            }
+            VynError::IntegerLiteralOverflow { literal, .. } => {
+                format!("Integer literal '{literal}' does not fit in an Int")
+            }
+            VynError::UnterminatedInterpolation { .. } => {
+                "String interpolation is missing its closing '}'".to_string()
+            }
+            VynError::InvalidCast { from, to, .. } => {
+                format!("Cannot convert a value of type '{from}' to '{to}'")
+            }
+            VynError::StackOverflow { depth, .. } => {
+                format!("Stack overflow: exceeded the maximum call depth of {depth}")
+            }
+            VynError::IntegerOverflow { expression, .. } => {
+                format!("Integer overflow: '{expression}' does not fit in an Int")
+            }
+            VynError::InvalidConversion { value, .. } => {
+                format!("Cannot convert Float '{value}' to an Int")
+            }
+            VynError::UninitializedGlobal { .. } => {
+                "Variable was read before it was given a value".to_string()
+            }
+            VynError::NativeError { name, message, .. } => {
+                format!("Call to '{name}' failed: {message}")
+            }
+            VynError::IoError {
+                action,
+                target,
+                reason,
+                ..
+            } => format!("Could not {action} '{target}': {reason}"),
+
+            VynError::MalformedBytecode { reason } => {
+                format!("Malformed bytecode file: {reason}")
+            }
+            VynError::UnsupportedBytecodeVersion { found, expected } => {
+                format!("Bytecode format version {found} is not supported (expected {expected})")
+            }
+            VynError::InvalidBytecode { offset, reason, .. } => {
+                format!("Invalid bytecode at offset {offset:#06x}: {reason}")
+            }
 
             VynError::IndexOutOfBounds { size, idx, .. } => {
                 format!(
@@ -156,9 +233,92 @@ impl VynError {
                     idx, size
                 )
             }
+            VynError::SliceOutOfBounds {
+                size, start, end, ..
+            } => {
+                format!("Cannot slice '{start}..{end}' out of a string with a length of '{size}'")
+            }
             VynError::InvalidIndexing { target, .. } => {
                 format!("Cannot index target type '{}'", target)
             }
+            VynError::TypeRedeclaration { name, .. } => {
+                format!("Type '{name}' is already declared")
+            }
+            VynError::DuplicateField { name, .. } => {
+                format!("Field '{name}' is listed more than once")
+            }
+            VynError::TooManyFields { name, .. } => {
+                format!("'{name}' has more than 255 fields")
+            }
+            VynError::UnknownField { target, field, .. } => {
+                format!("Type '{target}' has no field '{field}'")
+            }
+            VynError::MissingField { target, field, .. } => {
+                format!("Missing field '{field}' in '{target}' literal")
+            }
+            VynError::InvalidFieldAccess { target, .. } => {
+                format!("Cannot access a field on type '{target}'")
+            }
+            VynError::MissingInitializer { name, var_type, .. } => {
+                format!("Variable '{name}' of type '{var_type}' needs an initial value")
+            }
+            VynError::UseBeforeAssignment { name, .. } => {
+                format!("Variable '{name}' may be read before it is assigned")
+            }
+            VynError::InvalidPattern { .. } => {
+                "Expected a pattern like 'Name::Variant' or '_'".to_string()
+            }
+            VynError::DuplicateVariant { name, .. } => {
+                format!("Variant '{name}' is listed more than once")
+            }
+            VynError::TooManyVariants { name, .. } => {
+                format!("Enum '{name}' has more than 256 variants")
+            }
+            VynError::UnknownVariant {
+                target, variant, ..
+            } => {
+                format!("Enum '{target}' has no variant '{variant}'")
+            }
+            VynError::PayloadCountMismatch {
+                variant,
+                expected,
+                got,
+                ..
+            } => {
+                format!("Variant '{variant}' holds {expected} value(s) but got {got}")
+            }
+            VynError::InvalidMatchTarget { target, .. } => {
+                format!("Cannot match on a value of type '{target}'")
+            }
+            VynError::NonExhaustiveMatch {
+                target, missing, ..
+            } => {
+                let missing = missing
+                    .iter()
+                    .map(|variant| format!("'{target}::{variant}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("Match on '{target}' does not cover {missing}")
+            }
+            VynError::UnreachableMatchArm { .. } => "This arm can never be reached".to_string(),
+            VynError::PossiblyNil { expr, inner_type, .. } => {
+                format!("'{expr}' may be nil here, where '{inner_type}' is required")
+            }
+            VynError::InvalidSlice { target, .. } => {
+                format!("Cannot slice target type '{target}'")
+            }
+            VynError::ReadOnlyField { target, field, .. } => {
+                format!("Field '{field}' of type '{target}' is read-only")
+            }
+            VynError::NoMatchingOverload { name, found, .. } => {
+                let found = found
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("No version of '{name}' takes arguments of type ({found})")
+            }
             VynError::ImmutableMutation { identifier, .. } => {
                 format!("Cannot mutate immutable identifier '{}'", identifier)
             }
@@ -34,6 +34,27 @@ pub enum VynError {
         token_type: TokenType,
         span: Span,
     },
+    ReturnOutsideFunction {
+        span: Span,
+    },
+    NestedFunctionDeclaration {
+        name: String,
+        span: Span,
+    },
+    IntegerLiteralOverflow {
+        literal: String,
+        span: Span,
+    },
+    UnterminatedInterpolation {
+        span: Span,
+    },
+    TypeRedeclaration {
+        name: String,
+        span: Span,
+    },
+    InvalidPattern {
+        span: Span,
+    },
 
     // ----- Static Evaluator -----
     CircularStaticDependency {
@@ -69,7 +90,12 @@ pub enum VynError {
     },
 
     NegativeArraySize {
-        size: i32,
+        size: i64,
+        span: Span,
+    },
+
+    ArraySizeTooLarge {
+        size: usize,
         span: Span,
     },
 
@@ -116,6 +142,12 @@ pub enum VynError {
         idx: i64,
         span: Span,
     },
+    SliceOutOfBounds {
+        size: usize,
+        start: i64,
+        end: i64,
+        span: Span,
+    },
     StaticRequiresConstant {
         span: Span,
     },
@@ -156,11 +188,123 @@ pub enum VynError {
         name: String,
         span: Span,
     },
+    NotCallable {
+        found: Type,
+        span: Span,
+    },
+    ArgumentCountMismatch {
+        name: String,
+        expected: usize,
+        got: usize,
+        span: Span,
+    },
+    MissingReturn {
+        name: String,
+        expected: Type,
+        span: Span,
+    },
+    FunctionAsValue {
+        name: String,
+        span: Span,
+    },
+    InvalidCast {
+        from: Type,
+        to: Type,
+        span: Span,
+    },
+    DuplicateField {
+        name: String,
+        span: Span,
+    },
+    TooManyFields {
+        name: String,
+        span: Span,
+    },
+    UnknownField {
+        target: Type,
+        field: String,
+        span: Span,
+    },
+    MissingField {
+        target: Type,
+        field: String,
+        span: Span,
+    },
+    InvalidFieldAccess {
+        target: Type,
+        span: Span,
+    },
+    MissingInitializer {
+        name: String,
+        var_type: Type,
+        span: Span,
+    },
+    UseBeforeAssignment {
+        name: String,
+        declaration_span: Span,
+        span: Span,
+    },
+    DuplicateVariant {
+        name: String,
+        span: Span,
+    },
+    TooManyVariants {
+        name: String,
+        span: Span,
+    },
+    UnknownVariant {
+        target: Type,
+        variant: String,
+        span: Span,
+    },
+    PayloadCountMismatch {
+        variant: String,
+        expected: usize,
+        got: usize,
+        span: Span,
+    },
+    InvalidMatchTarget {
+        target: Type,
+        span: Span,
+    },
+    NonExhaustiveMatch {
+        target: Type,
+        missing: Vec<String>,
+        span: Span,
+    },
+    UnreachableMatchArm {
+        span: Span,
+    },
+    PossiblyNil {
+        expr: String,
+        inner_type: Type,
+        // Whether a nil check on `expr` would narrow it
+        narrowable: bool,
+        span: Span,
+    },
+    InvalidSlice {
+        target: Type,
+        span: Span,
+    },
+    ReadOnlyField {
+        target: Type,
+        field: String,
+        span: Span,
+    },
+    NoMatchingOverload {
+        name: String,
+        found: Vec<Type>,
+        candidates: Vec<String>,
+        span: Span,
+    },
 
     // ----- Compiler -----
     RegisterOverflow {
         span: Span,
     },
+    TooManyGlobals {
+        span: Span,
+    },
     NotImplemented {
         feature: String,
         span: Span,
@@ -195,4 +339,54 @@ pub enum VynError {
         // This can also be compile time
         span: Span,
     },
+    FrozenMutation {
+        span: Span,
+    },
+    ZeroLoopStep {
+        // Set when the type checker already knows the step
+        is_static: bool,
+        span: Span,
+    },
+    StackOverflow {
+        depth: usize,
+        span: Span,
+    },
+    IntegerOverflow {
+        expression: String,
+        span: Span,
+    },
+    InvalidConversion {
+        value: String,
+        span: Span,
+    },
+    UninitializedGlobal {
+        span: Span,
+    },
+    NativeError {
+        name: String,
+        message: String,
+        span: Span,
+    },
+
+    // ----- Runtime I/O Errors -----
+    IoError {
+        action: String,
+        target: String,
+        reason: String,
+        span: Span,
+    },
+
+    // ----- Bytecode Files -----
+    MalformedBytecode {
+        reason: String,
+    },
+    UnsupportedBytecodeVersion {
+        found: u16,
+        expected: u16,
+    },
+    InvalidBytecode {
+        offset: usize,
+        reason: String,
+        span: Span,
+    },
 }
@@ -1,4 +1,4 @@
-use crate::{error_handler::errors::VynError, tokens::TokenType};
+use crate::{error_handler::errors::VynError, tokens::TokenType, type_checker::type_checker::Type};
 
 impl VynError {
     pub fn hint(&self) -> Option<String> {
@@ -16,7 +16,11 @@ impl VynError {
                 "Insert a valid type before '{got}' based on the assigned value"
             )),
             VynError::RegisterOverflow { .. } => Some(
-                "Split this expression into multiple smaller expressions or statements".to_string(),
+                "Pass fewer arguments here, or keep fewer values alive at once".to_string(),
+            ),
+            VynError::TooManyGlobals { .. } => Some(
+                "Move variables into functions, or group related ones into arrays or structs"
+                    .to_string(),
             ),
             VynError::NotImplemented { feature, .. } => Some(format!(
                 "'{}' is planned but not yet available in this version",
@@ -58,12 +62,21 @@ impl VynError {
         VynError::StaticOverflow { .. } => {
             Some("Use smaller values or change the operation to prevent overflow".to_string())
         }
+        VynError::FrozenMutation { .. } => {
+            Some("Values are shared, so every variable holding this one must be declared with 'let @'".to_string())
+        }
+        VynError::ZeroLoopStep { .. } => {
+            Some("A step of 0 never reaches the end of the range, count up or down instead".to_string())
+        }
         VynError::NegativeExponent { .. } => {
-            Some("Exponents in compile-time expressions must be non-negative integers".to_string())
+            Some("Integer exponents must be non-negative, use Float operands for fractional powers".to_string())
         }
         VynError::NegativeArraySize { .. } => {
             Some("Array size must be a positive integer".to_string())
         }
+        VynError::ArraySizeTooLarge { .. } => {
+            Some("Use a sequence that grows as elements are pushed".to_string())
+        }
         VynError::ArraySizeNotStatic { .. } => {
             Some("Use a literal number or static variable for array size".to_string())
         }
@@ -94,8 +107,88 @@ impl VynError {
                 Some("Remove the redeclaration and use it".to_string())
             }
             VynError::LeftHandAssignment { .. } => None,
+            VynError::InvalidIndexing {
+                target: Type::String,
+                ..
+            } => Some("Strings can't be changed, build a new one with slices and '+'".to_string()),
             VynError::InvalidIndexing { .. } => None,
+            VynError::TypeRedeclaration { .. } => {
+                Some("Rename one of the types".to_string())
+            }
+            VynError::DuplicateField { .. } => Some("Remove the repeated field".to_string()),
+            VynError::TooManyFields { .. } => {
+                Some("Group related fields into smaller structs".to_string())
+            }
+            VynError::UnknownField {
+                target: Type::String,
+                ..
+            } => Some("Strings only have a 'length' field".to_string()),
+            VynError::UnknownField { .. } => {
+                Some("Check the field name against the struct declaration".to_string())
+            }
+            VynError::MissingField { field, .. } => {
+                Some(format!("Add '{field}: <value>' to the literal"))
+            }
+            VynError::InvalidFieldAccess { .. } => {
+                Some("Only struct values have fields".to_string())
+            }
+            VynError::MissingInitializer { .. } => {
+                Some("Structs and enums have no default value, assign one with '='".to_string())
+            }
+            VynError::UseBeforeAssignment { name, .. } => Some(format!(
+                "Assign '{name}' on every path that reaches this read, or give it a value where it is declared"
+            )),
+            VynError::InvalidPattern { .. } => {
+                Some("Patterns name an enum variant, use '_' to match anything else".to_string())
+            }
+            VynError::DuplicateVariant { .. } => Some("Remove the repeated variant".to_string()),
+            VynError::TooManyVariants { .. } => {
+                Some("Split the enum into smaller enums".to_string())
+            }
+            VynError::UnknownVariant { .. } => {
+                Some("Check the variant name against the enum declaration".to_string())
+            }
+            VynError::PayloadCountMismatch { expected, .. } => {
+                Some(format!("Give exactly {expected} value(s)"))
+            }
+            VynError::InvalidMatchTarget { .. } => {
+                Some("Only enum values can be matched".to_string())
+            }
+            VynError::NonExhaustiveMatch { missing, .. } => Some(format!(
+                "Add an arm for {}, or a '_' arm to handle the rest",
+                missing
+                    .iter()
+                    .map(|variant| format!("'{variant}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            VynError::UnreachableMatchArm { .. } => {
+                Some("An earlier arm already covers this case, remove this one".to_string())
+            }
+            VynError::PossiblyNil {
+                expr, narrowable, ..
+            } => {
+                if *narrowable {
+                    Some(format!("Use it inside 'if {expr} != nil {{ ... }}'"))
+                } else if expr.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    Some(format!(
+                        "Functions can change '{expr}', copy it into a 'let' and check that against nil"
+                    ))
+                } else {
+                    Some("Store it in a variable and check that against nil first".to_string())
+                }
+            }
+            VynError::InvalidSlice { .. } => Some("Only strings can be sliced".to_string()),
+            VynError::ReadOnlyField { .. } => {
+                Some("Build a new value instead of changing this one".to_string())
+            }
+            VynError::NoMatchingOverload { candidates, .. } => {
+                Some(format!("Available versions: {}", candidates.join(", ")))
+            }
             VynError::IndexOutOfBounds { .. } => None,
+            VynError::SliceOutOfBounds { .. } => {
+                Some("Slice bounds must satisfy 0 <= start <= end <= length".to_string())
+            }
             VynError::ImmutableMutation { identifier, .. } => {
                 Some(format!("Prefix identifier '{identifier}' with '@'"))
             }
@@ -170,6 +263,64 @@ impl VynError {
             VynError::IllegalLoopInterruptToken { token_type, .. } => {
                 Some(format!("Remove the '{token_type}' statement"))
             }
+            VynError::ReturnOutsideFunction { .. } => {
+                Some("Remove the 'return' statement or move it into a function".to_string())
+            }
+            VynError::NestedFunctionDeclaration { .. } => {
+                Some("Move this function declaration out to the top level".to_string())
+            }
+            VynError::NotCallable { .. } => {
+                Some("Only functions declared with 'fn' can be called".to_string())
+            }
+            VynError::ArgumentCountMismatch { expected, .. } => {
+                Some(format!("Pass exactly {expected} argument(s)"))
+            }
+            VynError::MissingReturn { .. } => {
+                Some("Add a 'return' statement at the end of the function".to_string())
+            }
+            VynError::FunctionAsValue { name, .. } => Some(format!("Call it instead: '{name}(...)'")),
+            VynError::IntegerLiteralOverflow { .. } => Some(format!(
+                "Int values range from {} to {}, use a Float literal for larger numbers",
+                i64::MIN,
+                i64::MAX
+            )),
+            VynError::UnterminatedInterpolation { .. } => Some(
+                "Close the expression with '}' on the same line, or write '\\{' for a literal brace"
+                    .to_string(),
+            ),
+            VynError::InvalidCast { .. } => {
+                Some("Only 'Int as Float' and 'Float as Int' conversions are supported".to_string())
+            }
+            VynError::StackOverflow { .. } => {
+                Some("Check for recursion that never reaches its base case".to_string())
+            }
+            VynError::IntegerOverflow { .. } => Some(
+                "Use smaller values, or run with '--wrapping' to wrap around on overflow"
+                    .to_string(),
+            ),
+            VynError::InvalidConversion { .. } => Some(
+                "The Float must be finite and within the Int range to be converted".to_string(),
+            ),
+            VynError::UninitializedGlobal { .. } => Some(
+                "Only call this function once the top-level variables it uses are assigned"
+                    .to_string(),
+            ),
+            VynError::NativeError { .. } => None,
+            // Standard streams, nothing the program can fix
+            VynError::IoError { target, .. } if target.starts_with('<') => None,
+            VynError::IoError { .. } => Some(
+                "Check that the path exists and that you have permission to access it".to_string(),
+            ),
+
+            VynError::MalformedBytecode { .. } => Some(
+                "Make sure the file was produced by 'vyn build' and is not truncated".to_string(),
+            ),
+            VynError::UnsupportedBytecodeVersion { .. } => Some(
+                "Rebuild the program from source with this version of 'vyn build'".to_string(),
+            ),
+            VynError::InvalidBytecode { .. } => Some(
+                "The bytecode is corrupt or was not produced by the Vyn compiler".to_string(),
+            ),
 
             VynError::UnaryOperationError { operation, .. } => match operation {
                 TokenType::Minus => {
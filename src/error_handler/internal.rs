@@ -0,0 +1,132 @@
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, Write},
+    panic::{self, PanicHookInfo},
+};
+
+use colored::*;
+
+use crate::{error_handler::error_collector::print_code_snippet, utils::Span};
+
+/*
+ * What the compiler was doing when it panicked
+ *
+ * Kept up to date by the phases, so an internal compiler error can point at
+ * the phase and the span that triggered it
+ * */
+thread_local! {
+    static PHASE: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static SPAN: Cell<Option<Span>> = const { Cell::new(None) };
+    static SOURCE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Phase entered before handing the bytecode to the VM, a panic past this
+// point is a bug in the VM rather than in the compiler
+pub const RUNNING: &str = "Running";
+
+/*
+ * Replaces Rust's panic message with an internal compiler or VM error report
+ *
+ * A panic is always a bug in Vyn, errors in user programs are reported through
+ * the ErrorCollector. With RUST_BACKTRACE set the default hook runs afterwards
+ *
+ * -- Arguments: none
+ * -- Return value: void
+ * */
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        report_internal_error(info);
+
+        if std::env::var_os("RUST_BACKTRACE").is_some() {
+            default_hook(info);
+        }
+    }));
+}
+
+/*
+ * Marks the start of a phase, the span of the last one no longer applies
+ * */
+pub fn enter_phase(name: &'static str) {
+    PHASE.with(|phase| phase.set(Some(name)));
+    SPAN.with(|current| current.set(None));
+}
+
+/*
+ * Records the span currently being processed
+ * */
+pub fn track_span(span: Span) {
+    SPAN.with(|current| current.set(Some(span)));
+}
+
+/*
+ * Records the source the spans point into
+ * */
+pub fn set_source(source: &str) {
+    SOURCE.with(|current| *current.borrow_mut() = Some(source.to_string()));
+}
+
+fn report_internal_error(info: &PanicHookInfo) {
+    let message = match info.payload().downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match info.payload().downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "unknown panic".to_string(),
+        },
+    };
+
+    // Phase progress is printed without a newline, finish it first
+    let _ = io::stdout().flush();
+
+    let phase = PHASE.with(Cell::get);
+    let component = match phase {
+        Some(RUNNING) => "VM",
+        _ => "compiler",
+    };
+
+    // Header: Internal::Error -> internal compiler error: message
+    eprintln!();
+    eprintln!(
+        "{}{}{}{}",
+        "Internal".bright_white().bold(),
+        "::".white().dimmed(),
+        "Error".red().dimmed().bold(),
+        format!(" -> internal {component} error: {message}").bright_red()
+    );
+
+    if let Some(phase) = phase {
+        eprintln!();
+        eprintln!(
+            "{} {}",
+            "While:".white().dimmed().bold(),
+            phase.bright_white()
+        );
+    }
+
+    if let Some(span) = SPAN.with(Cell::get) {
+        SOURCE.with(|source| {
+            if let Some(source) = source.borrow().as_deref() {
+                eprintln!();
+                eprintln!("{}", "Processing:".white().dimmed().bold());
+                print_code_snippet(source, span, true);
+            }
+        });
+    }
+
+    eprintln!();
+    eprintln!(
+        "{} {}",
+        "Hint:".bright_yellow(),
+        "This is a bug in Vyn, not in your program. Please report it along with the code above"
+            .bright_white()
+    );
+
+    if let Some(location) = info.location() {
+        eprintln!(
+            "{} {}",
+            "Note:".dimmed(),
+            format!("panicked at {location}").dimmed()
+        );
+    }
+}
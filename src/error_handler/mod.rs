@@ -3,3 +3,5 @@ pub mod error_collector;
 pub mod error_messages;
 pub mod errors;
 pub mod hints;
+pub mod internal;
+pub mod warnings;
@@ -0,0 +1,146 @@
+use clap::ValueEnum;
+use colored::*;
+
+use crate::{error_handler::error_collector::print_code_snippet, utils::Span};
+
+/*
+ * Groups of warnings that can be silenced together with `--allow`
+ * */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lint {
+    UnusedVariable,
+    UnusedMut,
+    Shadowing,
+    UnreachableCode,
+}
+
+impl Lint {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::UnusedMut => "unused-mut",
+            Lint::Shadowing => "shadowing",
+            Lint::UnreachableCode => "unreachable-code",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum VynWarning {
+    UnusedVariable {
+        name: String,
+        span: Span,
+    },
+    UnusedMut {
+        name: String,
+        span: Span,
+    },
+    Shadowing {
+        name: String,
+        original_span: Span,
+        span: Span,
+    },
+    UnreachableCode {
+        // Keyword of the statement that jumps away, or of the `if`, `match`
+        // or `loop` no path leaves normally
+        after: String,
+        span: Span,
+    },
+}
+
+impl VynWarning {
+    pub fn lint(&self) -> Lint {
+        match self {
+            VynWarning::UnusedVariable { .. } => Lint::UnusedVariable,
+            VynWarning::UnusedMut { .. } => Lint::UnusedMut,
+            VynWarning::Shadowing { .. } => Lint::Shadowing,
+            VynWarning::UnreachableCode { .. } => Lint::UnreachableCode,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            VynWarning::UnusedVariable { span, .. } => *span,
+            VynWarning::UnusedMut { span, .. } => *span,
+            VynWarning::Shadowing { span, .. } => *span,
+            VynWarning::UnreachableCode { span, .. } => *span,
+        }
+    }
+
+    pub fn category(&self) -> &str {
+        match self {
+            VynWarning::UnusedVariable { .. } => "Unused",
+            VynWarning::UnusedMut { .. } => "Unused",
+            VynWarning::Shadowing { .. } => "Scope",
+            VynWarning::UnreachableCode { .. } => "Flow",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            VynWarning::UnusedVariable { name, .. } => {
+                format!("Variable '{name}' is never used")
+            }
+            VynWarning::UnusedMut { name, .. } => {
+                format!("Variable '{name}' is declared mutable but never assigned")
+            }
+            VynWarning::Shadowing { name, .. } => {
+                format!("'{name}' shadows a variable from an outer scope")
+            }
+            VynWarning::UnreachableCode { after, .. } => match after.as_str() {
+                "break" | "continue" | "return" => {
+                    format!("Statement after '{after}' is never run")
+                }
+                _ => format!("Statement is never run, the '{after}' before it never falls through"),
+            },
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            VynWarning::UnusedVariable { name, .. } => Some(format!(
+                "Remove it, or name it '_{name}' if it is meant to be unused"
+            )),
+            VynWarning::UnusedMut { .. } => Some("Remove the '@' from the declaration".to_string()),
+            VynWarning::Shadowing { .. } => Some(
+                "Rename the inner variable, the outer one can't be reached while it exists"
+                    .to_string(),
+            ),
+            VynWarning::UnreachableCode { .. } => {
+                Some("Remove the statement or move it before the jump".to_string())
+            }
+        }
+    }
+
+    pub fn report(&self, source: &str) {
+        // Header: Category::Warning -> message
+        eprintln!(
+            "{}{}{}{}",
+            self.category().bright_white().bold(),
+            "::".white().dimmed(),
+            "Warning".yellow().dimmed().bold(),
+            format!(" -> {}", self.message()).bright_yellow()
+        );
+
+        eprintln!();
+        eprintln!("{}", "Warning caused by:".white().dimmed().bold());
+        print_code_snippet(source, self.span(), true);
+
+        if let VynWarning::Shadowing { original_span, .. } = self {
+            eprintln!();
+            eprintln!("{}", "Outer variable declared here:".white().dimmed());
+            print_code_snippet(source, *original_span, false);
+        }
+
+        eprintln!();
+
+        if let Some(hint_text) = self.hint() {
+            eprintln!("{} {}", "Hint:".bright_yellow(), hint_text.bright_white());
+        }
+        eprintln!(
+            "{} {}",
+            "Note:".dimmed(),
+            format!("silence this with '--allow {}'", self.lint().name()).dimmed()
+        );
+    }
+}
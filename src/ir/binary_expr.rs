@@ -2,7 +2,7 @@ use crate::{
     ast::ast::{Expr, Expression},
     ir::{builder::VynIRBuilder, ir_instr::VynIROC},
     tokens::Token,
-    type_checker::type_checker::Type,
+    type_checker::{static_evaluator::StaticValue, type_checker::Type},
 };
 
 impl VynIRBuilder<'_> {
@@ -13,22 +13,29 @@ impl VynIRBuilder<'_> {
         right: &Box<Expression>,
         expr: &Expression,
     ) -> Option<u32> {
+        if matches!(operator, Token::And | Token::Or) {
+            return self.build_logical_expr(left, operator, right, expr);
+        }
+
         let b_left = self.build_expr(left.as_ref())?;
         let b_right = self.build_expr(right.as_ref())?;
         let dest = self.allocate_vreg();
 
-        let expr_type = self.get_expr_type(expr)?;
+        // The type checker proved both operands have the same type
+        let operand_type = self.get_expr_type(left)?;
         let opcode = match operator {
             // Arithmetic
             Token::Plus | Token::Minus | Token::Asterisk | Token::Slash | Token::Caret => {
-                self.build_arith_expr(expr_type, b_left, operator, b_right, dest)
+                self.build_arith_expr(operand_type, b_left, operator, b_right, dest)
             }
             Token::LessThan
             | Token::GreaterThan
             | Token::LessThanEqual
             | Token::GreaterThanEqual
             | Token::Equal
-            | Token::NotEqual => self.build_comp_expr(expr_type, b_left, operator, b_right, dest),
+            | Token::NotEqual => {
+                self.build_comp_expr(operand_type, b_left, operator, b_right, dest)
+            }
 
             _ => unreachable!(),
         };
@@ -38,26 +45,181 @@ impl VynIRBuilder<'_> {
         Some(dest)
     }
 
-    fn get_expr_type(&mut self, expr: &Expression) -> Option<Type> {
+    /*
+     * Lowers `and` / `or` with short-circuit evaluation
+     *
+     *   and:                         or:
+     *     dest = left                  dest = left
+     *     if !dest jump end            if !dest jump rhs
+     *     dest = right                 jump end
+     *   end:                         rhs:
+     *                                  dest = right
+     *                                end:
+     *
+     * The right operand is only evaluated when the left one does not
+     * already decide the result.
+     *
+     * -- Arguments: [&mut self], left, right - operands
+     *               operator - Token::And or Token::Or
+     *               expr - the whole binary expression (for spans)
+     * -- Return value: Some(vreg) holding the resulting Bool
+     * */
+    fn build_logical_expr(
+        &mut self,
+        left: &Expression,
+        operator: &Token,
+        right: &Expression,
+        expr: &Expression,
+    ) -> Option<u32> {
+        let dest = self.allocate_vreg();
+        let end_label = self.next_label();
+
+        let b_left = self.build_expr(left)?;
+        self.emit(VynIROC::Move { dest, src: b_left }.spanned(left.span));
+
+        match operator {
+            Token::And => {
+                self.emit(
+                    VynIROC::JumpIfFalse {
+                        condition_reg: dest,
+                        label: end_label,
+                    }
+                    .spanned(expr.span),
+                );
+            }
+            Token::Or => {
+                let rhs_label = self.next_label();
+                self.emit(
+                    VynIROC::JumpIfFalse {
+                        condition_reg: dest,
+                        label: rhs_label,
+                    }
+                    .spanned(expr.span),
+                );
+                self.emit(VynIROC::JumpUncond { label: end_label }.spanned(expr.span));
+                self.emit_label(rhs_label);
+            }
+            _ => unreachable!(),
+        }
+
+        let b_right = self.build_expr(right)?;
+        self.emit(VynIROC::Move { dest, src: b_right }.spanned(right.span));
+        self.emit_label(end_label);
+
+        Some(dest)
+    }
+
+    /*
+     * Finds the type of an already checked expression
+     *
+     * Optionals are reported as their inner type, the type checker proved
+     * they are non-nil wherever the type decides which instruction to emit
+     * */
+    pub(crate) fn get_expr_type(&mut self, expr: &Expression) -> Option<Type> {
+        self.get_declared_expr_type(expr).map(Type::without_nil)
+    }
+
+    fn get_declared_expr_type(&mut self, expr: &Expression) -> Option<Type> {
         match &expr.node {
             Expr::IntegerLiteral(_) => Some(Type::Integer),
             Expr::FloatLiteral(_) => Some(Type::Float),
             Expr::BooleanLiteral(_) => Some(Type::Bool),
             Expr::StringLiteral(_) => Some(Type::String),
+            Expr::NilLiteral => Some(Type::Nil),
 
             Expr::Identifier(name) => {
+                if let Some(value) = self.resolve_static(name) {
+                    return match value {
+                        StaticValue::Int(_) => Some(Type::Integer),
+                        StaticValue::Float(_) => Some(Type::Float),
+                        StaticValue::Bool(_) => Some(Type::Bool),
+                        StaticValue::String(_) => Some(Type::String),
+                        StaticValue::Nil => Some(Type::Nil),
+                    };
+                }
+
                 let symbol =
                     self.symbol_table
                         .resolve_symbol(name, expr.span, &mut self.error_collector)?;
                 Some(symbol.symbol_type.clone())
             }
 
-            Expr::BinaryOperation { left, .. } => {
-                // Binary expr type = left operand type (type checker validated they match)
-                self.get_expr_type(left)
+            Expr::Unary { operator, right } => match operator {
+                Token::Not | Token::Bang => Some(Type::Bool),
+                _ => self.get_expr_type(right),
+            },
+
+            Expr::BinaryOperation { left, operator, .. } => match operator {
+                Token::LessThan
+                | Token::LessThanEqual
+                | Token::GreaterThan
+                | Token::GreaterThanEqual
+                | Token::Equal
+                | Token::NotEqual
+                | Token::And
+                | Token::Or => Some(Type::Bool),
+                // Arithmetic type = left operand type (type checker validated they match)
+                _ => self.get_expr_type(left),
+            },
+
+            Expr::ArrayLiteral { elements } => {
+                let first = elements.first()?;
+                Some(Type::Sequence(Box::new(self.get_expr_type(first)?)))
             }
 
-            _ => None, // Shouldn't reach here for arithmetic/comparison
+            Expr::Index { target, .. } | Expr::IndexAssignment { target, .. } => {
+                match self.get_expr_type(target)? {
+                    Type::Array(element_type, _) | Type::Sequence(element_type) => {
+                        Some(*element_type)
+                    }
+                    Type::String => Some(Type::String),
+                    _ => None,
+                }
+            }
+
+            Expr::VariableAssignment { identifier, .. } => self.get_expr_type(identifier),
+
+            Expr::Call { callee, arguments } => match &callee.node {
+                Expr::Identifier(name) if !self.function_labels.contains_key(name) => self
+                    .resolve_native(name, arguments)
+                    .map(|(_, native)| native.return_type.clone()),
+                Expr::Identifier(name) => self
+                    .function_signature(name, callee.span)
+                    .map(|(_, return_type)| return_type),
+                _ => None,
+            },
+
+            Expr::Cast { target, .. } => Some(Type::from_anotated_type(
+                target,
+                self.static_eval,
+                &mut self.error_collector,
+            )),
+
+            Expr::StructLiteral { struct_type, .. } => Some(Type::from_anotated_type(
+                struct_type,
+                self.static_eval,
+                &mut self.error_collector,
+            )),
+
+            Expr::FieldAccess { target, field } | Expr::FieldAssignment { target, field, .. } => {
+                let target_type = self.get_expr_type(target)?;
+                if target_type == Type::String {
+                    return Some(Type::Integer);
+                }
+
+                let (_, field_type) = target_type.field(&field.node.to_string())?;
+                Some(field_type.clone())
+            }
+
+            Expr::Slice { .. } | Expr::StringInterpolation { .. } => Some(Type::String),
+
+            Expr::EnumVariant { enum_type, .. } => Some(Type::from_anotated_type(
+                enum_type,
+                self.static_eval,
+                &mut self.error_collector,
+            )),
+
+            Expr::Match { subject, arms } => self.match_expr_type(subject, arms),
         }
     }
 
@@ -72,6 +234,11 @@ impl VynIRBuilder<'_> {
         let is_op_int = matches!(expr_type, Type::Integer);
 
         match operator {
+            Token::Plus if expr_type == Type::String => VynIROC::ConcatString {
+                dest,
+                left: b_left,
+                right: b_right,
+            },
             Token::Plus => {
                 if is_op_int {
                     VynIROC::AddInt {
@@ -162,6 +329,12 @@ impl VynIRBuilder<'_> {
     ) -> VynIROC {
         let is_op_int = matches!(expr_type, Type::Integer);
 
+        if expr_type == Type::String
+            && let Some(opcode) = Self::build_string_comp_expr(b_left, operator, b_right, dest)
+        {
+            return opcode;
+        }
+
         match operator {
             Token::LessThan => {
                 if is_op_int {
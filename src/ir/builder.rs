@@ -1,22 +1,24 @@
-use std::mem;
+use std::{collections::HashMap, mem};
 
 use crate::{
     ast::ast::{Expr, Expression, Program, Statement, Stmt},
     bytecode::bytecode::OpCode,
-    error_handler::error_collector::ErrorCollector,
+    error_handler::{error_collector::ErrorCollector, internal},
     ir::{
-        ir_instr::{Label, VReg, VynIROC, VynIROpCode},
+        ir_instr::{Label, VReg, VynIRFunction, VynIROC, VynIROpCode},
         symbol_ir_table::{SymbolScope, SymbolTable},
     },
     type_checker::{
-        static_evaluator::StaticEvaluator, symbol_type_table::SymbolTypeTable, type_checker::Type,
+        static_evaluator::{StaticEvaluator, StaticValue},
+        symbol_type_table::SymbolTypeTable,
+        type_checker::{Type, TypeChecker},
     },
     utils::Span,
 };
 
 pub struct VynIRBuilder<'a> {
-    instructions: Vec<VynIROpCode>,
-    next_register: VReg,
+    pub(crate) instructions: Vec<VynIROpCode>,
+    pub(crate) next_register: VReg,
     label_counter: usize,
     pub(crate) error_collector: ErrorCollector,
     pub(crate) static_eval: &'a StaticEvaluator,
@@ -24,12 +26,33 @@ pub struct VynIRBuilder<'a> {
     pub(crate) symbol_table: SymbolTable,
 
     // Loop context
-    break_jump_pos: Option<Label>,
-    continue_jump_pos: Option<Label>,
+    pub(crate) break_jump_pos: Option<Label>,
+    pub(crate) continue_jump_pos: Option<Label>,
+
+    // Function name -> entry label, filled before any body is lowered
+    pub(crate) function_labels: HashMap<String, Label>,
+    pub(crate) functions: Vec<VynIRFunction>,
+    // Declared return type of the function being lowered
+    pub(crate) return_type: Option<Type>,
 }
 
 pub struct VynIR {
     pub instructions: Vec<VynIROpCode>,
+    pub functions: Vec<VynIRFunction>,
+}
+
+/*
+ * Builder state that outlives a single program
+ *
+ * The REPL builds every input with the state left by the previous one so
+ * variables and functions keep their virtual registers and labels
+ * */
+#[derive(Default, Clone)]
+pub struct IRBuilderState {
+    pub symbol_table: SymbolTable,
+    pub next_register: VReg,
+    pub label_counter: usize,
+    pub function_labels: HashMap<String, Label>,
 }
 
 impl<'a> VynIRBuilder<'a> {
@@ -44,10 +67,50 @@ impl<'a> VynIRBuilder<'a> {
             continue_jump_pos: None,
             symbol_type_table,
             symbol_table: SymbolTable::new(),
+            function_labels: HashMap::new(),
+            functions: Vec::new(),
+            return_type: None,
+        }
+    }
+
+    /*
+     * Creates a builder that continues from a previous program's state
+     *
+     * -- Arguments: static_eval, symbol_type_table - checked program info
+     *               state - state returned by a previous `.take_state()`
+     * -- Return value: VynIRBuilder instance
+     * */
+    pub fn with_state(
+        static_eval: &'a StaticEvaluator,
+        symbol_type_table: &'a SymbolTypeTable,
+        state: IRBuilderState,
+    ) -> Self {
+        let mut builder = Self::new(static_eval, symbol_type_table);
+        builder.symbol_table = state.symbol_table;
+        builder.next_register = state.next_register;
+        builder.label_counter = state.label_counter;
+        builder.function_labels = state.function_labels;
+        builder
+    }
+
+    /*
+     * Hands out the state needed to build a following program
+     *
+     * -- Arguments: [&mut self]
+     * -- Return value: IRBuilderState
+     * */
+    pub fn take_state(&mut self) -> IRBuilderState {
+        IRBuilderState {
+            symbol_table: mem::take(&mut self.symbol_table),
+            next_register: self.next_register,
+            label_counter: self.label_counter,
+            function_labels: mem::take(&mut self.function_labels),
         }
     }
 
     pub fn build_ir(&mut self, program: &Program) -> Result<VynIR, ErrorCollector> {
+        self.declare_functions(&program.statements);
+
         for stmt in &program.statements {
             self.build_stmt(stmt, stmt.span);
         }
@@ -61,7 +124,9 @@ impl<'a> VynIRBuilder<'a> {
         }
     }
 
-    fn build_stmt(&mut self, stmt: &Statement, span: Span) -> Option<()> {
+    pub(crate) fn build_stmt(&mut self, stmt: &Statement, span: Span) -> Option<()> {
+        internal::track_span(span);
+
         match &stmt.node {
             Stmt::Expression { expression } => {
                 self.build_expr(expression);
@@ -80,30 +145,73 @@ impl<'a> VynIRBuilder<'a> {
 
                 let symbol_type = Type::from_anotated_type(
                     annotated_type,
-                    &mut self.static_eval,
+                    self.static_eval,
                     &mut self.error_collector,
                 );
 
-                let value_vreg = if let Some(val) = value {
-                    self.build_expr(val)?
+                // Top-level variables live in the global table
+                if self.symbol_table.is_global_scope() {
+                    // Without a value the slot stays empty until the first
+                    // assignment, so a function reading it earlier fails
+                    // instead of seeing a default. Optionals start as nil
+                    let value_vreg = match value {
+                        Some(val) => Some(self.build_typed_expr(val, &symbol_type)?),
+                        None if matches!(symbol_type, Type::Optional(_)) => {
+                            let value = Type::get_type_default_value(&symbol_type);
+                            Some(self.build_typed_expr(&value, &symbol_type)?)
+                        }
+                        None => None,
+                    };
+
+                    if let Some(value_vreg) = value_vreg {
+                        self.freeze_binding(value_vreg, &symbol_type, *mutable, span);
+                    }
+
+                    let index = self.symbol_table.declare_global(
+                        symbol_type,
+                        var_name,
+                        *mutable,
+                        span,
+                        &mut self.error_collector,
+                    )?;
+
+                    if let Some(src) = value_vreg {
+                        self.emit(VynIROC::StoreGlobal { index, src }.spanned(span));
+                    }
                 } else {
-                    let val_type = Type::from_anotated_type(
-                        annotated_type,
-                        &self.static_eval,
+                    let value_vreg = if let Some(val) = value {
+                        self.build_typed_expr(val, &symbol_type)?
+                    } else {
+                        let value = Type::get_type_default_value(&symbol_type);
+                        self.build_typed_expr(&value, &symbol_type)?
+                    };
+                    self.freeze_binding(value_vreg, &symbol_type, *mutable, span);
+
+                    // `let y = x` gets its own copy, otherwise assigning to
+                    // either variable would change both
+                    let register = if self.symbol_table.holds_register(value_vreg) {
+                        let copy = self.allocate_vreg();
+                        self.emit(
+                            VynIROC::Move {
+                                dest: copy,
+                                src: value_vreg,
+                            }
+                            .spanned(span),
+                        );
+                        copy
+                    } else {
+                        value_vreg
+                    };
+
+                    self.symbol_table.declare_ident_with_register(
+                        symbol_type,
+                        var_name,
+                        *mutable,
+                        register,
+                        span,
                         &mut self.error_collector,
                     );
-                    let value = Type::get_type_default_value(&val_type);
-                    self.build_expr(&value)?
-                };
-
-                self.symbol_table.declare_ident_with_register(
-                    symbol_type,
-                    var_name.clone(),
-                    *mutable,
-                    value_vreg as u8,
-                    span,
-                    &mut self.error_collector,
-                );
+                }
             }
 
             Stmt::Loop { body } => {
@@ -127,6 +235,11 @@ impl<'a> VynIRBuilder<'a> {
             }
 
             Stmt::WhenLoop { body, condition } => {
+                let static_condition = self.static_condition(condition);
+                if static_condition == Some(false) {
+                    return Some(());
+                }
+
                 let loop_start = self.next_label();
                 let loop_end = self.next_label();
 
@@ -137,15 +250,19 @@ impl<'a> VynIRBuilder<'a> {
                 self.continue_jump_pos = Some(loop_start);
 
                 self.emit_label(loop_start);
-                let cond_reg = self.build_expr(condition)?;
 
-                self.emit(
-                    VynIROC::JumpIfFalse {
-                        condition_reg: cond_reg,
-                        label: loop_end,
-                    }
-                    .spanned(span),
-                );
+                // A static `true` only leaves through `break`
+                if static_condition.is_none() {
+                    let cond_reg = self.build_expr(condition)?;
+
+                    self.emit(
+                        VynIROC::JumpIfFalse {
+                            condition_reg: cond_reg,
+                            label: loop_end,
+                        }
+                        .spanned(span),
+                    );
+                }
 
                 self.build_stmt(body, span)?;
 
@@ -170,6 +287,11 @@ impl<'a> VynIRBuilder<'a> {
                 self.symbol_table.enter_scope();
                 for stmt in statements {
                     self.build_stmt(stmt, stmt.span);
+
+                    // The type checker warns about anything past this point
+                    if self.is_terminating_stmt(stmt) {
+                        break;
+                    }
                 }
                 self.symbol_table.exit_scope();
             }
@@ -179,6 +301,18 @@ impl<'a> VynIRBuilder<'a> {
                 consequence,
                 alternate,
             } => {
+                // Only the branch a static condition picks is built
+                match self.static_condition(condition) {
+                    Some(true) => return self.build_stmt(consequence, span),
+                    Some(false) => {
+                        return match alternate.as_ref() {
+                            Some(else_block) => self.build_stmt(else_block, span),
+                            None => Some(()),
+                        };
+                    }
+                    None => {}
+                }
+
                 let condition_reg = self.build_expr(condition)?;
                 let else_label = self.next_label();
                 let if_end_label = self.next_label();
@@ -213,13 +347,53 @@ impl<'a> VynIRBuilder<'a> {
                 self.emit(VynIROC::LogAddr { addr: vreg }.spanned(span));
             }
 
-            unknown => todo!("Implement stmt {:?} at IR", unknown),
+            Stmt::IndexLoop {
+                init,
+                start_range,
+                end_range,
+                steps,
+                body,
+            } => self.build_index_loop(
+                init,
+                start_range,
+                end_range,
+                steps.as_ref(),
+                body,
+                span,
+            )?,
+
+            Stmt::FunctionDeclaration { .. } => self.build_function_decl(stmt)?,
+
+            Stmt::Return { value } => {
+                let value = match value {
+                    Some(expr) => {
+                        let return_type = self.return_type.clone()?;
+                        Some(self.build_typed_expr(expr, &return_type)?)
+                    }
+                    None => None,
+                };
+
+                self.emit(VynIROC::Return { value }.spanned(span));
+            }
+
+            // Statics are folded by the StaticEvaluator, aliases, structs and enums
+            // are resolved by the parser, none of them produce instructions
+            Stmt::StaticVariableDeclaration { .. }
+            | Stmt::TypeAliasDeclaration { .. }
+            | Stmt::StructDeclaration { .. }
+            | Stmt::EnumDeclaration { .. } => {}
+
+            Stmt::Match { subject, arms } => self.build_match_stmt(subject, arms, span)?,
+
+            Stmt::Block { .. } => unreachable!("The parser never produces block statements"),
         }
 
         Some(())
     }
 
     pub(crate) fn build_expr(&mut self, expr: &Expression) -> Option<VReg> {
+        internal::track_span(expr.span);
+
         let dest = match &expr.node {
             Expr::IntegerLiteral(i) => {
                 let dest = self.allocate_vreg();
@@ -239,6 +413,12 @@ impl<'a> VynIRBuilder<'a> {
                 dest
             }
 
+            Expr::NilLiteral => {
+                let dest = self.allocate_vreg();
+                self.emit(VynIROC::LoadNil { dest }.spanned(expr.span));
+                dest
+            }
+
             Expr::StringLiteral(s) => {
                 let dest = self.allocate_vreg();
                 self.emit(
@@ -255,13 +435,18 @@ impl<'a> VynIRBuilder<'a> {
                 identifier,
                 new_value,
             } => {
-                let new_value_vreg = self.build_expr(&new_value)?;
-
                 let var_name = match &identifier.node {
                     Expr::Identifier(n) => n,
                     _ => unreachable!(),
                 };
 
+                let symbol_type = self
+                    .symbol_table
+                    .resolve_symbol(var_name, expr.span, &mut self.error_collector)?
+                    .symbol_type
+                    .clone();
+                let new_value_vreg = self.build_typed_expr(new_value, &symbol_type)?;
+
                 let symbol = self.symbol_table.resolve_symbol(
                     var_name,
                     expr.span,
@@ -272,24 +457,45 @@ impl<'a> VynIRBuilder<'a> {
                     SymbolScope::Register(dest_reg) => {
                         self.emit(
                             VynIROC::Move {
-                                dest: dest_reg as VReg,
+                                dest: dest_reg,
                                 src: new_value_vreg,
                             }
                             .spanned(expr.span),
                         );
 
-                        dest_reg as VReg
+                        dest_reg
+                    }
+                    SymbolScope::Global(index) => {
+                        self.emit(
+                            VynIROC::StoreGlobal {
+                                index,
+                                src: new_value_vreg,
+                            }
+                            .spanned(expr.span),
+                        );
+
+                        new_value_vreg
                     }
                 }
             }
 
             Expr::Identifier(name) => {
+                if let Some(value) = self.resolve_static(name) {
+                    let value = value.clone();
+                    return Some(self.build_static_value(value, expr.span));
+                }
+
                 let symbol =
                     self.symbol_table
                         .resolve_symbol(name, expr.span, &mut self.error_collector)?;
 
                 match symbol.scope {
-                    SymbolScope::Register(reg) => reg as VReg,
+                    SymbolScope::Register(reg) => reg,
+                    SymbolScope::Global(index) => {
+                        let dest = self.allocate_vreg();
+                        self.emit(VynIROC::LoadGlobal { dest, index }.spanned(expr.span));
+                        dest
+                    }
                 }
             }
 
@@ -299,26 +505,285 @@ impl<'a> VynIRBuilder<'a> {
                 right,
             } => self.build_binary_expr(left, operator, right, expr)?,
 
-            unknown => todo!("Implement expr {:?} at IR", unknown),
+            Expr::Unary { operator, right } => self.build_unary_expr(operator, right, expr)?,
+
+            Expr::Cast { value, target } => self.build_cast_expr(value, target, expr)?,
+
+            Expr::ArrayLiteral { elements } => self.build_array_literal(elements, None, expr.span)?,
+
+            Expr::Index { target, property } => {
+                if self.get_expr_type(target)? == Type::String {
+                    return self.build_string_index(target, property, expr.span);
+                }
+
+                let array = self.build_expr(target)?;
+                let index = self.build_expr(property)?;
+                let dest = self.allocate_vreg();
+
+                self.emit(VynIROC::ArrayGet { dest, array, index }.spanned(expr.span));
+                dest
+            }
+
+            Expr::IndexAssignment {
+                target,
+                property,
+                new_value,
+            } => {
+                let element_type = match self.get_expr_type(target)? {
+                    Type::Array(t, _) | Type::Sequence(t) => *t,
+                    _ => unreachable!(),
+                };
+
+                let array = self.build_expr(target)?;
+
+                // Literal indices are encoded directly in the instruction
+                let const_index = match property.node {
+                    Expr::IntegerLiteral(i) if i >= 0 => Some(i as usize),
+                    _ => None,
+                };
+
+                match const_index {
+                    Some(index) => {
+                        let value = self.build_typed_expr(new_value, &element_type)?;
+                        self.emit(
+                            VynIROC::ArraySet {
+                                array,
+                                index,
+                                value,
+                            }
+                            .spanned(expr.span),
+                        );
+                        value
+                    }
+                    None => {
+                        let index = self.build_expr(property)?;
+                        let value = self.build_typed_expr(new_value, &element_type)?;
+                        self.emit(
+                            VynIROC::ArraySetReg {
+                                array,
+                                index,
+                                value,
+                            }
+                            .spanned(expr.span),
+                        );
+                        value
+                    }
+                }
+            }
+
+            Expr::Call { callee, arguments } => self.build_call_expr(callee, arguments, expr.span)?,
+
+            Expr::StructLiteral {
+                struct_type,
+                fields,
+            } => self.build_struct_literal(struct_type, fields, expr.span)?,
+
+            Expr::FieldAccess { target, field } => {
+                self.build_field_access(target, field, expr.span)?
+            }
+
+            Expr::FieldAssignment {
+                target,
+                field,
+                new_value,
+            } => self.build_field_assignment(target, field, new_value, expr.span)?,
+
+            Expr::EnumVariant {
+                enum_type,
+                variant,
+                arguments,
+            } => self.build_enum_variant(enum_type, variant, arguments, expr.span)?,
+
+            Expr::Match { subject, arms } => self.build_match_expr(subject, arms, expr.span)?,
+
+            Expr::Slice { target, start, end } => self.build_slice(target, start, end, expr.span)?,
+
+            Expr::StringInterpolation { parts } => {
+                self.build_string_interpolation(parts, expr.span)?
+            }
         };
 
         Some(dest)
     }
 
+    /*
+     * Builds an expression whose type is already known from context
+     *
+     * Declarations and assignments know the type of the value they expect,
+     * which is what decides whether an array literal becomes a fixed array
+     * or a sequence
+     *
+     * -- Arguments: [&mut self], expr - expression to build
+     *               expected_type - type the expression must produce
+     * -- Return value: Some(vreg) holding the result
+     * */
+    pub(crate) fn build_typed_expr(
+        &mut self,
+        expr: &Expression,
+        expected_type: &Type,
+    ) -> Option<VReg> {
+        match &expr.node {
+            Expr::ArrayLiteral { elements } => {
+                self.build_array_literal(elements, Some(expected_type), expr.span)
+            }
+            _ => self.build_expr(expr),
+        }
+    }
+
+    /*
+     * Lowers an array literal into a heap allocation followed by element stores
+     *
+     * Fixed arrays are allocated with their full size and filled with ArraySet,
+     * everything else becomes a sequence filled with ArrayPush
+     *
+     * -- Arguments: [&mut self], elements - literal elements
+     *               array_type - Array/Sequence type of the literal, if known
+     *               span - span of the literal
+     * -- Return value: Some(vreg) holding the array
+     * */
+    fn build_array_literal(
+        &mut self,
+        elements: &[Box<Expression>],
+        array_type: Option<&Type>,
+        span: Span,
+    ) -> Option<VReg> {
+        let dest = self.allocate_vreg();
+
+        match array_type {
+            Some(Type::Array(element_type, size)) => {
+                self.emit(VynIROC::NewArray { dest, size: *size }.spanned(span));
+
+                for (index, elem) in elements.iter().enumerate() {
+                    let value = self.build_typed_expr(elem, element_type)?;
+                    self.emit(
+                        VynIROC::ArraySet {
+                            array: dest,
+                            index,
+                            value,
+                        }
+                        .spanned(elem.span),
+                    );
+                }
+            }
+
+            _ => {
+                let element_type = match array_type {
+                    Some(Type::Sequence(t)) => Some(t.as_ref().clone()),
+                    _ => None,
+                };
+
+                self.emit(
+                    VynIROC::NewSequence {
+                        dest,
+                        capacity: elements.len(),
+                    }
+                    .spanned(span),
+                );
+
+                for elem in elements {
+                    let value = match &element_type {
+                        Some(t) => self.build_typed_expr(elem, t)?,
+                        None => self.build_expr(elem)?,
+                    };
+                    self.emit(VynIROC::ArrayPush { array: dest, value }.spanned(elem.span));
+                }
+            }
+        }
+
+        Some(dest)
+    }
+
+    /*
+     * Looks up a static value by name
+     *
+     * -- Arguments: [&self], name - identifier name
+     * -- Return value: Some(value) if the name refers to a static that is
+     *                  not shadowed by a runtime variable
+     * */
+    pub(crate) fn resolve_static(&self, name: &str) -> Option<&StaticValue> {
+        if self.symbol_table.is_declared(name) {
+            return None;
+        }
+
+        self.static_eval.get_static(name)
+    }
+
+    /*
+     * Loads an already evaluated static value into a fresh register
+     * */
+    fn build_static_value(&mut self, value: StaticValue, span: Span) -> VReg {
+        let dest = self.allocate_vreg();
+
+        let opcode = match value {
+            StaticValue::Int(value) => VynIROC::LoadConstInt { dest, value },
+            StaticValue::Float(value) => VynIROC::LoadConstFloat { dest, value },
+            StaticValue::Bool(value) => VynIROC::LoadBool { dest, value },
+            StaticValue::String(value) => VynIROC::LoadString { dest, value },
+            StaticValue::Nil => VynIROC::LoadNil { dest },
+        };
+
+        self.emit(opcode.spanned(span));
+        dest
+    }
+
+    /*
+     * Makes the value of a `let` binding read-only
+     *
+     * Heap values are shared, so an immutable binding would otherwise
+     * change whenever a mutable name for the same value is written through
+     * */
+    fn freeze_binding(&mut self, value: VReg, symbol_type: &Type, mutable: bool, span: Span) {
+        if !mutable && symbol_type.is_heap_value() {
+            self.emit(VynIROC::Freeze { value }.spanned(span));
+        }
+    }
+
     pub(crate) fn allocate_vreg(&mut self) -> VReg {
         let reg = self.next_register;
         self.next_register += 1;
         reg
     }
 
-    fn is_terminating_stmt(&self, stmt: &Statement) -> bool {
-        match &stmt.node {
-            Stmt::Break | Stmt::Continue => true,
-            Stmt::Scope { statements } => statements
-                .last()
-                .map(|s| self.is_terminating_stmt(s))
-                .unwrap_or(false),
-            _ => false,
+    /*
+     * Checks whether control never reaches the statement after this one,
+     * with the same rules the type checker uses for unreachable code
+     * */
+    pub(crate) fn is_terminating_stmt(&self, stmt: &Statement) -> bool {
+        TypeChecker::always_jumps(stmt)
+    }
+
+    /*
+     * Evaluates a condition made only of literals and statics
+     *
+     * -- Arguments: [&self], condition - an `if` or `for when` condition
+     * -- Return value: Some(value) if it is known at compile time, None if
+     *                  it reads a variable or can't be evaluated statically
+     * */
+    fn static_condition(&self, condition: &Expression) -> Option<bool> {
+        if !self.is_static_expr(condition) {
+            return None;
+        }
+
+        let mut errors = ErrorCollector::new();
+        self.static_eval
+            .evaluate_static_expr(condition, &mut errors)
+            .ok()?
+            .as_bool()
+    }
+
+    /*
+     * Checks that every identifier in an expression names a static, so a
+     * runtime variable shadowing one is never read as its static value
+     * */
+    fn is_static_expr(&self, expr: &Expression) -> bool {
+        match &expr.node {
+            Expr::Identifier(name) => self.resolve_static(name).is_some(),
+            Expr::Unary { right, .. } => self.is_static_expr(right),
+            Expr::BinaryOperation { left, right, .. } => {
+                self.is_static_expr(left) && self.is_static_expr(right)
+            }
+            Expr::Cast { value, .. } => self.is_static_expr(value),
+            _ => true,
         }
     }
 
@@ -340,6 +805,7 @@ impl<'a> VynIRBuilder<'a> {
     fn finish(&mut self) -> VynIR {
         VynIR {
             instructions: mem::take(&mut self.instructions),
+            functions: mem::take(&mut self.functions),
         }
     }
 }
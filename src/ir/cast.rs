@@ -0,0 +1,35 @@
+use crate::{
+    ast::{ast::Expression, type_annotation::TypeAnnotation},
+    ir::{builder::VynIRBuilder, ir_instr::VynIROC},
+    type_checker::type_checker::Type,
+};
+
+impl VynIRBuilder<'_> {
+    pub(crate) fn build_cast_expr(
+        &mut self,
+        value: &Expression,
+        target: &TypeAnnotation,
+        expr: &Expression,
+    ) -> Option<u32> {
+        let src = self.build_expr(value)?;
+        let source_type = self.get_expr_type(value)?;
+
+        // Casting to the same type is a no-op
+        let is_int_to_float = match (source_type, target) {
+            (Type::Integer, TypeAnnotation::FloatType) => true,
+            (Type::Float, TypeAnnotation::IntegerType) => false,
+            _ => return Some(src),
+        };
+
+        let dest = self.allocate_vreg();
+        let opcode = if is_int_to_float {
+            VynIROC::IntToFloat { dest, src }
+        } else {
+            VynIROC::FloatToInt { dest, src }
+        };
+
+        self.emit(opcode.spanned(expr.span));
+
+        Some(dest)
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    ast::{
+        ast::{Expression, MatchArm, MatchExprArm, Pattern},
+        type_annotation::TypeAnnotation,
+    },
+    ir::{
+        builder::VynIRBuilder,
+        ir_instr::{Label, VReg, VynIROC},
+    },
+    type_checker::type_checker::Type,
+    utils::{Span, Spanned},
+};
+
+impl VynIRBuilder<'_> {
+    /*
+     * Lowers `Name::Variant(values)` into a heap allocation followed by
+     * payload stores
+     *
+     * -- Arguments: [&mut self], enum_type - annotated enum type
+     *               variant - variant name, arguments - payload values
+     *               span - whole expression
+     * -- Return value: Some(vreg) holding the enum value
+     * */
+    pub(crate) fn build_enum_variant(
+        &mut self,
+        enum_type: &TypeAnnotation,
+        variant: &Expression,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Option<VReg> {
+        let enum_type =
+            Type::from_anotated_type(enum_type, self.static_eval, &mut self.error_collector);
+        let variant_name = variant.node.to_string();
+        let (tag, payload) = enum_type.variant(&variant_name)?;
+        let payload = payload.to_vec();
+
+        let dest = self.allocate_vreg();
+        self.emit(
+            VynIROC::NewEnum {
+                dest,
+                name: format!("{enum_type}::{variant_name}"),
+                tag,
+                size: payload.len(),
+            }
+            .spanned(span),
+        );
+
+        for (slot, (argument, payload_type)) in arguments.iter().zip(&payload).enumerate() {
+            let value = self.build_typed_expr(argument, payload_type)?;
+
+            self.emit(
+                VynIROC::StructSet {
+                    target: dest,
+                    field: slot,
+                    value,
+                }
+                .spanned(argument.span),
+            );
+        }
+
+        Some(dest)
+    }
+
+    /*
+     * Lowers a `match` statement into a chain of tag tests
+     *
+     * Each arm compares the subject's tag against its variant and jumps to
+     * the next arm on a mismatch. The type checker proved the match
+     * exhaustive, so the last arm runs without a test
+     *
+     * -- Arguments: [&mut self], subject - matched value, arms - match arms
+     *               span - `match` keyword up to the subject
+     * -- Return value: Some(()) on success
+     * */
+    pub(crate) fn build_match_stmt(
+        &mut self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        span: Span,
+    ) -> Option<()> {
+        let subject_type = self.get_expr_type(subject)?;
+        let (subject_reg, tag_reg) = self.build_match_subject(subject, span)?;
+        let match_end = self.next_label();
+
+        for (i, arm) in arms.iter().enumerate() {
+            let is_last = i + 1 == arms.len();
+            let next_arm = self.next_label();
+
+            self.build_arm_test(tag_reg, &arm.pattern, &subject_type, next_arm, is_last);
+
+            self.symbol_table.enter_scope();
+            let built = self
+                .bind_pattern(subject_reg, &arm.pattern, &subject_type)
+                .and_then(|_| self.build_stmt(&arm.body, arm.body.span));
+            self.symbol_table.exit_scope();
+            built?;
+
+            if !is_last && !self.is_terminating_stmt(&arm.body) {
+                self.emit(VynIROC::JumpUncond { label: match_end }.spanned(span));
+            }
+            self.emit_label(next_arm);
+        }
+
+        self.emit_label(match_end);
+        Some(())
+    }
+
+    /*
+     * Lowers a `match` expression like a match statement, with every arm
+     * moving its value into a shared result register
+     *
+     * -- Arguments: [&mut self], subject - matched value, arms - match arms
+     *               span - whole expression
+     * -- Return value: Some(vreg) holding the value of the arm that ran
+     * */
+    pub(crate) fn build_match_expr(
+        &mut self,
+        subject: &Expression,
+        arms: &[MatchExprArm],
+        span: Span,
+    ) -> Option<VReg> {
+        let subject_type = self.get_expr_type(subject)?;
+        let (subject_reg, tag_reg) = self.build_match_subject(subject, span)?;
+        let match_end = self.next_label();
+        let dest = self.allocate_vreg();
+
+        for (i, arm) in arms.iter().enumerate() {
+            let is_last = i + 1 == arms.len();
+            let next_arm = self.next_label();
+
+            self.build_arm_test(tag_reg, &arm.pattern, &subject_type, next_arm, is_last);
+
+            self.symbol_table.enter_scope();
+            let built = self
+                .bind_pattern(subject_reg, &arm.pattern, &subject_type)
+                .and_then(|_| {
+                    let value_type = self.get_expr_type(&arm.value);
+                    match value_type {
+                        Some(value_type) => self.build_typed_expr(&arm.value, &value_type),
+                        None => self.build_expr(&arm.value),
+                    }
+                });
+            self.symbol_table.exit_scope();
+
+            self.emit(VynIROC::Move { dest, src: built? }.spanned(arm.value.span));
+
+            if !is_last {
+                self.emit(VynIROC::JumpUncond { label: match_end }.spanned(span));
+            }
+            self.emit_label(next_arm);
+        }
+
+        self.emit_label(match_end);
+        Some(dest)
+    }
+
+    /*
+     * Finds the type a match expression produces
+     *
+     * Arms may use their bindings, so the first arm's value is inspected
+     * with them in scope
+     * */
+    pub(crate) fn match_expr_type(
+        &mut self,
+        subject: &Expression,
+        arms: &[MatchExprArm],
+    ) -> Option<Type> {
+        let subject_type = self.get_expr_type(subject)?;
+        let arm = arms.first()?;
+
+        self.symbol_table.enter_scope();
+        let value_type = self
+            .declare_bindings(&arm.pattern, &subject_type, |builder, _| {
+                builder.allocate_vreg()
+            })
+            .and_then(|_| self.get_expr_type(&arm.value));
+        self.symbol_table.exit_scope();
+
+        value_type
+    }
+
+    fn build_match_subject(&mut self, subject: &Expression, span: Span) -> Option<(VReg, VReg)> {
+        let subject_reg = self.build_expr(subject)?;
+        let tag_reg = self.allocate_vreg();
+
+        self.emit(
+            VynIROC::EnumTag {
+                dest: tag_reg,
+                target: subject_reg,
+            }
+            .spanned(span),
+        );
+
+        Some((subject_reg, tag_reg))
+    }
+
+    /*
+     * Jumps to `next_arm` unless the subject's tag matches the pattern
+     *
+     * Wildcards and the last arm match unconditionally
+     * */
+    fn build_arm_test(
+        &mut self,
+        tag_reg: VReg,
+        pattern: &Spanned<Pattern>,
+        subject_type: &Type,
+        next_arm: Label,
+        is_last: bool,
+    ) {
+        if is_last {
+            return;
+        }
+
+        let Pattern::Variant { variant, .. } = &pattern.node else {
+            return;
+        };
+        let Some((tag, _)) = subject_type.variant(&variant.node.to_string()) else {
+            return;
+        };
+
+        let expected_tag = self.allocate_vreg();
+        self.emit(
+            VynIROC::LoadConstInt {
+                dest: expected_tag,
+                value: tag as i64,
+            }
+            .spanned(pattern.span),
+        );
+
+        let is_match = self.allocate_vreg();
+        self.emit(
+            VynIROC::CompareEqual {
+                dest: is_match,
+                left: tag_reg,
+                right: expected_tag,
+            }
+            .spanned(pattern.span),
+        );
+
+        self.emit(
+            VynIROC::JumpIfFalse {
+                condition_reg: is_match,
+                label: next_arm,
+            }
+            .spanned(pattern.span),
+        );
+    }
+
+    /*
+     * Loads the payload values a pattern binds into fresh registers
+     * */
+    fn bind_pattern(
+        &mut self,
+        subject_reg: VReg,
+        pattern: &Spanned<Pattern>,
+        subject_type: &Type,
+    ) -> Option<()> {
+        self.declare_bindings(pattern, subject_type, |builder, slot| {
+            let dest = builder.allocate_vreg();
+            builder.emit(
+                VynIROC::StructGet {
+                    dest,
+                    target: subject_reg,
+                    field: slot,
+                }
+                .spanned(pattern.span),
+            );
+            dest
+        })
+    }
+
+    /*
+     * Declares every named binding of a pattern in the current scope
+     *
+     * -- Arguments: [&mut self], pattern - arm pattern
+     *               subject_type - enum being matched
+     *               load - gives the register holding the payload slot
+     * -- Return value: Some(()) on success
+     * */
+    fn declare_bindings(
+        &mut self,
+        pattern: &Spanned<Pattern>,
+        subject_type: &Type,
+        mut load: impl FnMut(&mut Self, usize) -> VReg,
+    ) -> Option<()> {
+        let Pattern::Variant {
+            variant, bindings, ..
+        } = &pattern.node
+        else {
+            return Some(());
+        };
+        let (_, payload) = subject_type.variant(&variant.node.to_string())?;
+        let payload = payload.to_vec();
+
+        for (slot, (binding, payload_type)) in bindings.iter().zip(payload).enumerate() {
+            let name = binding.node.to_string();
+            if name == "_" {
+                continue;
+            }
+
+            let register = load(self, slot);
+            self.symbol_table.declare_ident_with_register(
+                payload_type,
+                name,
+                false,
+                register,
+                binding.span,
+                &mut self.error_collector,
+            )?;
+        }
+
+        Some(())
+    }
+}
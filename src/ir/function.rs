@@ -0,0 +1,240 @@
+use std::mem;
+
+use crate::{
+    ast::ast::{Expr, Expression, Statement, Stmt},
+    ir::{
+        builder::VynIRBuilder,
+        ir_instr::{VReg, VynIRFunction, VynIROC},
+    },
+    type_checker::type_checker::Type,
+    utils::Span,
+    vyn_vm::natives::{self, NativeFn},
+};
+
+impl VynIRBuilder<'_> {
+    /*
+     * Assigns an entry label to every top-level function
+     *
+     * Runs before any statement is lowered so calls can target functions
+     * declared further down the file
+     *
+     * -- Arguments: [&mut self], statements - top-level program statements
+     * -- Return value: void
+     * */
+    pub(crate) fn declare_functions(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            if let Stmt::FunctionDeclaration { identifier, .. } = &stmt.node {
+                let name = match &identifier.node {
+                    Expr::Identifier(n) => n.clone(),
+                    _ => unreachable!(),
+                };
+
+                let label = self.next_label();
+                self.function_labels.insert(name, label);
+            }
+        }
+    }
+
+    /*
+     * Lowers a function declaration into its own VynIRFunction
+     *
+     * The body is built with a fresh instruction stream, symbol table (that
+     * only sees globals) and virtual register counter, the parameters taking
+     * v0..vN-1. A function that can fall off the end of its body returns nil.
+     *
+     * -- Arguments: [&mut self], stmt - the Stmt::FunctionDeclaration
+     * -- Return value: Some(()) if lowering succeeds
+     * */
+    pub(crate) fn build_function_decl(&mut self, stmt: &Statement) -> Option<()> {
+        let Stmt::FunctionDeclaration {
+            identifier,
+            parameters,
+            return_type,
+            body,
+        } = &stmt.node
+        else {
+            unreachable!()
+        };
+
+        let name = match &identifier.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => unreachable!(),
+        };
+
+        let label = *self.function_labels.get(&name)?;
+        let return_type = match return_type {
+            Some(an_type) => {
+                Type::from_anotated_type(an_type, self.static_eval, &mut self.error_collector)
+            }
+            None => Type::Nil,
+        };
+
+        let outer_instructions = mem::take(&mut self.instructions);
+        let function_table = self.symbol_table.enter_function_scope();
+        let outer_symbol_table = mem::replace(&mut self.symbol_table, function_table);
+        let outer_next_register = mem::replace(&mut self.next_register, 0);
+        let outer_break = self.break_jump_pos.take();
+        let outer_continue = self.continue_jump_pos.take();
+        let outer_return_type = self.return_type.replace(return_type);
+
+        self.emit_label(label);
+
+        let mut parameter_regs = Vec::with_capacity(parameters.len());
+        for param in parameters {
+            let param_name = match &param.identifier.node {
+                Expr::Identifier(n) => n.clone(),
+                _ => unreachable!(),
+            };
+
+            let param_type = Type::from_anotated_type(
+                &param.annotated_type,
+                self.static_eval,
+                &mut self.error_collector,
+            );
+
+            let vreg = self.allocate_vreg();
+            self.symbol_table.declare_ident_with_register(
+                param_type,
+                param_name,
+                param.mutable,
+                vreg,
+                param.identifier.span,
+                &mut self.error_collector,
+            );
+            parameter_regs.push(vreg);
+        }
+
+        let result = self.build_stmt(body, body.span);
+
+        let falls_through = !matches!(
+            self.instructions.last().map(|inst| &inst.node),
+            Some(VynIROC::Return { .. })
+        );
+        if falls_through {
+            self.emit(VynIROC::Return { value: None }.spanned(body.span));
+        }
+
+        let instructions = mem::replace(&mut self.instructions, outer_instructions);
+        self.symbol_table = outer_symbol_table;
+        self.next_register = outer_next_register;
+        self.break_jump_pos = outer_break;
+        self.continue_jump_pos = outer_continue;
+        self.return_type = outer_return_type;
+
+        result?;
+
+        self.functions.push(VynIRFunction {
+            name,
+            label,
+            parameters: parameter_regs,
+            instructions,
+        });
+
+        Some(())
+    }
+
+    /*
+     * Lowers a call into argument evaluation followed by a single Call
+     *
+     * -- Arguments: [&mut self], callee - called function identifier
+     *               arguments - call arguments, in order
+     *               span - span of the whole call
+     * -- Return value: Some(vreg) holding the returned value
+     * */
+    pub(crate) fn build_call_expr(
+        &mut self,
+        callee: &Expression,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Option<VReg> {
+        let name = match &callee.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => unreachable!("Type checker only allows calling functions by name"),
+        };
+
+        let Some(&function) = self.function_labels.get(&name) else {
+            return self.build_native_call(&name, arguments, span);
+        };
+        let (parameter_types, _) = self.function_signature(&name, callee.span)?;
+
+        let mut args = Vec::with_capacity(arguments.len());
+        for (arg, param_type) in arguments.iter().zip(&parameter_types) {
+            args.push(self.build_typed_expr(arg, param_type)?);
+        }
+
+        let dest = self.allocate_vreg();
+        self.emit(
+            VynIROC::Call {
+                dest,
+                function,
+                args,
+            }
+            .spanned(span),
+        );
+
+        Some(dest)
+    }
+
+    fn build_native_call(
+        &mut self,
+        name: &str,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Option<VReg> {
+        let (native, _) = self.resolve_native(name, arguments)?;
+
+        let mut args = Vec::with_capacity(arguments.len());
+        for arg in arguments {
+            args.push(self.build_expr(arg)?);
+        }
+
+        let dest = self.allocate_vreg();
+        self.emit(VynIROC::CallNative { dest, native, args }.spanned(span));
+
+        Some(dest)
+    }
+
+    /*
+     * Finds the native entry a call refers to, the same way the type
+     * checker picked it
+     *
+     * -- Return value: Some((index into NATIVES, entry)), None when `name`
+     *                  is a declared function
+     * */
+    pub(crate) fn resolve_native(
+        &mut self,
+        name: &str,
+        arguments: &[Box<Expression>],
+    ) -> Option<(usize, &'static NativeFn)> {
+        if self.function_labels.contains_key(name) {
+            return None;
+        }
+
+        let mut argument_types = Vec::with_capacity(arguments.len());
+        for arg in arguments {
+            argument_types.push(self.get_expr_type(arg)?);
+        }
+
+        natives::resolve(name, &argument_types)
+    }
+
+    /*
+     * Looks up a function's parameter and return types
+     *
+     * -- Return value: Some((parameters, return_type)) if `name` is a function
+     * */
+    pub(crate) fn function_signature(&mut self, name: &str, span: Span) -> Option<(Vec<Type>, Type)> {
+        let symbol = self
+            .symbol_type_table
+            .resolve_identifier(name, span, &mut self.error_collector)
+            .ok()?;
+
+        match &symbol.symbol_type {
+            Type::Function {
+                parameters,
+                return_type,
+            } => Some((parameters.clone(), *return_type.clone())),
+            _ => None,
+        }
+    }
+}
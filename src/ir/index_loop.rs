@@ -0,0 +1,282 @@
+use crate::{
+    ast::ast::{Expr, Expression, Statement, Stmt},
+    ir::{
+        builder::VynIRBuilder,
+        ir_instr::{VReg, VynIROC},
+    },
+    tokens::Token,
+    type_checker::{static_evaluator::StaticValue, type_checker::Type},
+    utils::Span,
+};
+
+impl VynIRBuilder<'_> {
+    /*
+     * Lowers `for let @i: T from start..end steps n { body }`
+     *
+     * The range is end-exclusive and `start`, `end` and `steps` are evaluated
+     * exactly once. The condition is checked at the bottom of the loop so the
+     * bound registers stay live for the whole body:
+     *
+     *     i = start; end; step
+     *     jump cond
+     *   body:
+     *     ...
+     *   continue:
+     *     i = i + step
+     *   cond:
+     *     if i < end (or i > end when step is negative) jump body
+     *   end:
+     *
+     * When the sign of `steps` is not known at compile time, the direction
+     * is decided at runtime from a flag computed before entering the loop,
+     * after checking that the step is not 0.
+     *
+     * -- Arguments: [&mut self], init - loop variable declaration
+     *               start_range, end_range, steps - range expressions
+     *               body - loop body
+     *               span - span of the whole loop
+     * -- Return value: Some(()) if lowering succeeds
+     * */
+    pub(crate) fn build_index_loop(
+        &mut self,
+        init: &Statement,
+        start_range: &Expression,
+        end_range: &Expression,
+        steps: Option<&Expression>,
+        body: &Statement,
+        span: Span,
+    ) -> Option<()> {
+        let (identifier, annotated_type, mutable) = match &init.node {
+            Stmt::VariableDeclaration {
+                identifier,
+                annotated_type,
+                mutable,
+                ..
+            } => (identifier, annotated_type, *mutable),
+            _ => unreachable!(),
+        };
+
+        let var_name = match &identifier.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => unreachable!(),
+        };
+
+        let var_type =
+            Type::from_anotated_type(annotated_type, self.static_eval, &mut self.error_collector);
+        let is_int = matches!(var_type, Type::Integer);
+
+        // Bounds are evaluated once, before the loop
+        let start_vreg = self.build_expr(start_range)?;
+        let loop_var = self.allocate_vreg();
+        self.emit(
+            VynIROC::Move {
+                dest: loop_var,
+                src: start_vreg,
+            }
+            .spanned(start_range.span),
+        );
+
+        let end_vreg = self.build_expr(end_range)?;
+        let step_vreg = match steps {
+            Some(step) => self.build_expr(step)?,
+            None => self.build_number(is_int, 1, span),
+        };
+
+        let descending = match steps {
+            Some(step) => self.static_step_direction(step),
+            None => Some(false),
+        };
+
+        // Unknown direction: stop on a 0 step, then compute `step < 0` once
+        // and branch on it
+        let descending_flag = match descending {
+            Some(_) => None,
+            None => {
+                let step_span = steps.map_or(span, |step| step.span);
+                self.emit(VynIROC::CheckStep { step: step_vreg }.spanned(step_span));
+
+                let zero = self.build_number(is_int, 0, span);
+                let dest = self.allocate_vreg();
+                let opcode = if is_int {
+                    VynIROC::CompareLessInt {
+                        dest,
+                        left: step_vreg,
+                        right: zero,
+                    }
+                } else {
+                    VynIROC::CompareLessFloat {
+                        dest,
+                        left: step_vreg,
+                        right: zero,
+                    }
+                };
+                self.emit(opcode.spanned(span));
+                Some(dest)
+            }
+        };
+
+        self.symbol_table.enter_scope();
+        self.symbol_table.declare_ident_with_register(
+            var_type,
+            var_name,
+            mutable,
+            loop_var,
+            init.span,
+            &mut self.error_collector,
+        );
+
+        let body_label = self.next_label();
+        let continue_label = self.next_label();
+        let cond_label = self.next_label();
+        let end_label = self.next_label();
+
+        let prev_break_jump_pos = self.break_jump_pos;
+        self.break_jump_pos = Some(end_label);
+
+        let prev_continue_jump_pos = self.continue_jump_pos;
+        self.continue_jump_pos = Some(continue_label);
+
+        self.emit(VynIROC::JumpUncond { label: cond_label }.spanned(span));
+
+        self.emit_label(body_label);
+        let body_result = self.build_stmt(body, span);
+
+        self.break_jump_pos = prev_break_jump_pos;
+        self.continue_jump_pos = prev_continue_jump_pos;
+        self.symbol_table.exit_scope();
+        body_result?;
+
+        // i = i + step
+        self.emit_label(continue_label);
+        let next = self.allocate_vreg();
+        let add = if is_int {
+            VynIROC::AddInt {
+                dest: next,
+                left: loop_var,
+                right: step_vreg,
+            }
+        } else {
+            VynIROC::AddFloat {
+                dest: next,
+                left: loop_var,
+                right: step_vreg,
+            }
+        };
+        self.emit(add.spanned(span));
+        self.emit(
+            VynIROC::Move {
+                dest: loop_var,
+                src: next,
+            }
+            .spanned(span),
+        );
+
+        // Loop condition
+        self.emit_label(cond_label);
+        let condition = match (descending, descending_flag) {
+            (Some(desc), _) => self.build_range_check(is_int, desc, loop_var, end_vreg, span),
+            (None, Some(flag)) => {
+                let check_label = self.next_label();
+                let ascending = self.build_range_check(is_int, false, loop_var, end_vreg, span);
+
+                self.emit(
+                    VynIROC::JumpIfFalse {
+                        condition_reg: flag,
+                        label: check_label,
+                    }
+                    .spanned(span),
+                );
+
+                let descending = self.build_range_check(is_int, true, loop_var, end_vreg, span);
+                self.emit(
+                    VynIROC::Move {
+                        dest: ascending,
+                        src: descending,
+                    }
+                    .spanned(span),
+                );
+
+                self.emit_label(check_label);
+                ascending
+            }
+            (None, None) => unreachable!(),
+        };
+
+        self.emit(
+            VynIROC::JumpIfFalse {
+                condition_reg: condition,
+                label: end_label,
+            }
+            .spanned(span),
+        );
+        self.emit(VynIROC::JumpUncond { label: body_label }.spanned(span));
+        self.emit_label(end_label);
+
+        Some(())
+    }
+
+    /*
+     * Emits `i < end` for ascending loops or `i > end` for descending ones
+     * */
+    fn build_range_check(
+        &mut self,
+        is_int: bool,
+        descending: bool,
+        loop_var: VReg,
+        end: VReg,
+        span: Span,
+    ) -> VReg {
+        let dest = self.allocate_vreg();
+        let (left, right) = (loop_var, end);
+
+        let opcode = match (is_int, descending) {
+            (true, false) => VynIROC::CompareLessInt { dest, left, right },
+            (true, true) => VynIROC::CompareGreaterInt { dest, left, right },
+            (false, false) => VynIROC::CompareLessFloat { dest, left, right },
+            (false, true) => VynIROC::CompareGreaterFloat { dest, left, right },
+        };
+
+        self.emit(opcode.spanned(span));
+        dest
+    }
+
+    fn build_number(&mut self, is_int: bool, value: i64, span: Span) -> VReg {
+        let dest = self.allocate_vreg();
+
+        let opcode = if is_int {
+            VynIROC::LoadConstInt { dest, value }
+        } else {
+            VynIROC::LoadConstFloat {
+                dest,
+                value: value as f64,
+            }
+        };
+
+        self.emit(opcode.spanned(span));
+        dest
+    }
+
+    /*
+     * Determines the loop direction from the `steps` expression when possible
+     *
+     * -- Return value: Some(true) if the step is known to be negative,
+     *                  Some(false) if it is known to be positive,
+     *                  None if it can only be decided at runtime
+     * */
+    fn static_step_direction(&self, step: &Expression) -> Option<bool> {
+        match &step.node {
+            Expr::IntegerLiteral(n) => Some(*n < 0),
+            Expr::FloatLiteral(f) => Some(*f < 0.0),
+            Expr::Unary {
+                operator: Token::Minus,
+                right,
+            } => self.static_step_direction(right).map(|desc| !desc),
+            Expr::Identifier(name) => match self.resolve_static(name)? {
+                StaticValue::Int(n) => Some(*n < 0),
+                StaticValue::Float(f) => Some(*f < 0.0),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
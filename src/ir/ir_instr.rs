@@ -5,6 +5,20 @@ pub type VReg = u32;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Label(pub usize);
 
+/*
+ * A function lowered into its own instruction stream
+ *
+ * Parameters occupy the first virtual registers of the function, in
+ * declaration order, and the stream always starts with `Label(label)`
+ * */
+#[derive(Debug, Clone)]
+pub struct VynIRFunction {
+    pub name: String,
+    pub label: Label,
+    pub parameters: Vec<VReg>,
+    pub instructions: Vec<VynIROpCode>,
+}
+
 #[derive(Debug, Clone)]
 pub struct VynIROpCode {
     pub node: VynIROC,
@@ -14,10 +28,11 @@ pub struct VynIROpCode {
 #[derive(Debug, Clone)]
 pub enum VynIROC {
     // Load constants
-    LoadConstInt { dest: VReg, value: i32 },
+    LoadConstInt { dest: VReg, value: i64 },
     LoadConstFloat { dest: VReg, value: f64 },
     LoadString { dest: VReg, value: String },
     LoadBool { dest: VReg, value: bool },
+    LoadNil { dest: VReg },
 
     // Arithmetic
     AddInt { dest: VReg, left: VReg, right: VReg },
@@ -31,6 +46,15 @@ pub enum VynIROC {
     ExpInt { dest: VReg, left: VReg, right: VReg },
     ExpFloat { dest: VReg, left: VReg, right: VReg },
 
+    // Unary
+    NegateInt { dest: VReg, src: VReg },
+    NegateFloat { dest: VReg, src: VReg },
+    Not { dest: VReg, src: VReg },
+
+    // Conversions
+    IntToFloat { dest: VReg, src: VReg },
+    FloatToInt { dest: VReg, src: VReg },
+
     // Comparisons
     CompareEqual { dest: VReg, left: VReg, right: VReg },
     CompareNotEqual { dest: VReg, left: VReg, right: VReg },
@@ -42,6 +66,10 @@ pub enum VynIROC {
     CompareGreaterFloat { dest: VReg, left: VReg, right: VReg },
     CompareGreaterEqualInt { dest: VReg, left: VReg, right: VReg },
     CompareGreaterEqualFloat { dest: VReg, left: VReg, right: VReg },
+    CompareLessString { dest: VReg, left: VReg, right: VReg },
+    CompareLessEqualString { dest: VReg, left: VReg, right: VReg },
+    CompareGreaterString { dest: VReg, left: VReg, right: VReg },
+    CompareGreaterEqualString { dest: VReg, left: VReg, right: VReg },
 
     // Control flow
     JumpIfFalse { condition_reg: VReg, label: Label },
@@ -51,6 +79,45 @@ pub enum VynIROC {
     // Register operations
     Move { dest: VReg, src: VReg },
 
+    // Globals
+    LoadGlobal { dest: VReg, index: usize },
+    StoreGlobal { index: usize, src: VReg },
+
+    // Functions
+    Call { dest: VReg, function: Label, args: Vec<VReg> },
+    CallNative { dest: VReg, native: usize, args: Vec<VReg> },
+    Return { value: Option<VReg> },
+
+    // Arrays
+    NewArray { dest: VReg, size: usize },
+    NewSequence { dest: VReg, capacity: usize },
+    ArraySet { array: VReg, index: usize, value: VReg },
+    ArraySetReg { array: VReg, index: VReg, value: VReg },
+    ArrayGet { dest: VReg, array: VReg, index: VReg },
+    ArrayPush { array: VReg, value: VReg },
+
+    // Structs, StructSet/StructGet also access enum payload slots
+    NewStruct { dest: VReg, layout: String, size: usize },
+    StructSet { target: VReg, field: usize, value: VReg },
+    StructGet { dest: VReg, target: VReg, field: usize },
+
+    // Strings, indices count characters
+    ConcatString { dest: VReg, left: VReg, right: VReg },
+    StringLength { dest: VReg, src: VReg },
+    StringIndex { dest: VReg, string: VReg, index: VReg },
+    StringSlice { dest: VReg, string: VReg, start: VReg, end: VReg },
+    ToString { dest: VReg, src: VReg },
+
+    // Enums, `name` is the `Enum::Variant` text used when printing
+    NewEnum { dest: VReg, name: String, tag: usize, size: usize },
+    EnumTag { dest: VReg, target: VReg },
+
+    // Index loops, stops the program when the step is 0
+    CheckStep { step: VReg },
+
+    // `let` bindings, the value and everything it holds can no longer change
+    Freeze { value: VReg },
+
     // I/O
     LogAddr { addr: VReg },
 
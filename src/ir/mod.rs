@@ -1,4 +1,11 @@
 pub mod binary_expr;
 pub mod builder;
+pub mod cast;
+pub mod enums;
+pub mod function;
+pub mod index_loop;
 pub mod ir_instr;
+pub mod strings;
+pub mod structs;
 pub mod symbol_ir_table;
+pub mod unary;
@@ -0,0 +1,151 @@
+use crate::{
+    ast::ast::Expression,
+    ir::{
+        builder::VynIRBuilder,
+        ir_instr::{VReg, VynIROC},
+    },
+    tokens::Token,
+    type_checker::type_checker::Type,
+    utils::Span,
+};
+
+impl VynIRBuilder<'_> {
+    /*
+     * Picks the ordering instruction for two strings
+     *
+     * -- Return value: None for `==` and `!=`, which compare every type
+     *                  with the same instructions
+     * */
+    pub(crate) fn build_string_comp_expr(
+        left: VReg,
+        operator: &Token,
+        right: VReg,
+        dest: VReg,
+    ) -> Option<VynIROC> {
+        let opcode = match operator {
+            Token::LessThan => VynIROC::CompareLessString { dest, left, right },
+            Token::LessThanEqual => VynIROC::CompareLessEqualString { dest, left, right },
+            Token::GreaterThan => VynIROC::CompareGreaterString { dest, left, right },
+            Token::GreaterThanEqual => VynIROC::CompareGreaterEqualString { dest, left, right },
+            _ => return None,
+        };
+
+        Some(opcode)
+    }
+
+    pub(crate) fn build_string_length(&mut self, target: &Expression, span: Span) -> Option<VReg> {
+        let src = self.build_expr(target)?;
+        let dest = self.allocate_vreg();
+
+        self.emit(VynIROC::StringLength { dest, src }.spanned(span));
+        Some(dest)
+    }
+
+    pub(crate) fn build_string_index(
+        &mut self,
+        target: &Expression,
+        property: &Expression,
+        span: Span,
+    ) -> Option<VReg> {
+        let string = self.build_expr(target)?;
+        let index = self.build_expr(property)?;
+        let dest = self.allocate_vreg();
+
+        self.emit(
+            VynIROC::StringIndex {
+                dest,
+                string,
+                index,
+            }
+            .spanned(span),
+        );
+        Some(dest)
+    }
+
+    pub(crate) fn build_slice(
+        &mut self,
+        target: &Expression,
+        start: &Expression,
+        end: &Expression,
+        span: Span,
+    ) -> Option<VReg> {
+        let string = self.build_expr(target)?;
+        let start = self.build_expr(start)?;
+        let end = self.build_expr(end)?;
+        let dest = self.allocate_vreg();
+
+        self.emit(
+            VynIROC::StringSlice {
+                dest,
+                string,
+                start,
+                end,
+            }
+            .spanned(span),
+        );
+        Some(dest)
+    }
+
+    /*
+     * Lowers `"a {b} c"` into a chain of concatenations
+     *
+     * Parts that aren't strings are converted with ToString first
+     *
+     * -- Arguments: [&mut self], parts - text and interpolated values in
+     *               order, span - whole literal
+     * -- Return value: Some(vreg) holding the joined string
+     * */
+    pub(crate) fn build_string_interpolation(
+        &mut self,
+        parts: &[Expression],
+        span: Span,
+    ) -> Option<VReg> {
+        let mut joined: Option<VReg> = None;
+
+        for part in parts {
+            let mut value = self.build_expr(part)?;
+
+            if self.get_expr_type(part)? != Type::String {
+                let text = self.allocate_vreg();
+                self.emit(
+                    VynIROC::ToString {
+                        dest: text,
+                        src: value,
+                    }
+                    .spanned(part.span),
+                );
+                value = text;
+            }
+
+            joined = Some(match joined {
+                Some(left) => {
+                    let dest = self.allocate_vreg();
+                    self.emit(
+                        VynIROC::ConcatString {
+                            dest,
+                            left,
+                            right: value,
+                        }
+                        .spanned(span),
+                    );
+                    dest
+                }
+                None => value,
+            });
+        }
+
+        if let Some(joined) = joined {
+            return Some(joined);
+        }
+
+        let dest = self.allocate_vreg();
+        self.emit(
+            VynIROC::LoadString {
+                dest,
+                value: String::new(),
+            }
+            .spanned(span),
+        );
+        Some(dest)
+    }
+}
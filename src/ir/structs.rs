@@ -0,0 +1,136 @@
+use crate::{
+    ast::{
+        ast::{Expression, StructLiteralField},
+        type_annotation::TypeAnnotation,
+    },
+    ir::{
+        builder::VynIRBuilder,
+        ir_instr::{VReg, VynIROC},
+    },
+    type_checker::type_checker::Type,
+    utils::Span,
+};
+
+impl VynIRBuilder<'_> {
+    /*
+     * Lowers a struct literal into a heap allocation followed by field stores
+     *
+     * Fields are evaluated in the order they are written, each store goes
+     * to the field's declared slot
+     *
+     * -- Arguments: [&mut self], struct_type - annotated struct type
+     *               fields - given fields, span - whole literal
+     * -- Return value: Some(vreg) holding the struct
+     * */
+    pub(crate) fn build_struct_literal(
+        &mut self,
+        struct_type: &TypeAnnotation,
+        fields: &[StructLiteralField],
+        span: Span,
+    ) -> Option<VReg> {
+        let struct_type =
+            Type::from_anotated_type(struct_type, self.static_eval, &mut self.error_collector);
+        let Type::Struct {
+            name,
+            fields: declared,
+        } = &struct_type
+        else {
+            return None;
+        };
+
+        let dest = self.allocate_vreg();
+        self.emit(
+            VynIROC::NewStruct {
+                dest,
+                layout: struct_layout(name, declared),
+                size: declared.len(),
+            }
+            .spanned(span),
+        );
+
+        for field in fields {
+            let (slot, field_type) = struct_type.field(&field.identifier.node.to_string())?;
+            let value = self.build_typed_expr(&field.value, field_type)?;
+
+            self.emit(
+                VynIROC::StructSet {
+                    target: dest,
+                    field: slot,
+                    value,
+                }
+                .spanned(field.value.span),
+            );
+        }
+
+        Some(dest)
+    }
+
+    pub(crate) fn build_field_access(
+        &mut self,
+        target: &Expression,
+        field: &Expression,
+        span: Span,
+    ) -> Option<VReg> {
+        let target_type = self.get_expr_type(target)?;
+        if target_type == Type::String {
+            return self.build_string_length(target, span);
+        }
+
+        let (slot, _) = target_type.field(&field.node.to_string())?;
+
+        let target = self.build_expr(target)?;
+        let dest = self.allocate_vreg();
+
+        self.emit(
+            VynIROC::StructGet {
+                dest,
+                target,
+                field: slot,
+            }
+            .spanned(span),
+        );
+
+        Some(dest)
+    }
+
+    pub(crate) fn build_field_assignment(
+        &mut self,
+        target: &Expression,
+        field: &Expression,
+        new_value: &Expression,
+        span: Span,
+    ) -> Option<VReg> {
+        let target_type = self.get_expr_type(target)?;
+        let (slot, field_type) = target_type.field(&field.node.to_string())?;
+        let field_type = field_type.clone();
+
+        let target = self.build_expr(target)?;
+        let value = self.build_typed_expr(new_value, &field_type)?;
+
+        self.emit(
+            VynIROC::StructSet {
+                target,
+                field: slot,
+                value,
+            }
+            .spanned(span),
+        );
+
+        Some(value)
+    }
+}
+
+/*
+ * Describes a struct for the VM as its name followed by its field names,
+ * separated by spaces, which is all it needs to print one
+ * */
+fn struct_layout(name: &str, fields: &[(String, Type)]) -> String {
+    let mut layout = name.to_string();
+
+    for (field, _) in fields {
+        layout.push(' ');
+        layout.push_str(field);
+    }
+
+    layout
+}
@@ -2,14 +2,19 @@ use std::collections::HashMap;
 
 use crate::{
     error_handler::{error_collector::ErrorCollector, errors::VynError},
+    ir::ir_instr::VReg,
     type_checker::type_checker::Type,
     utils::Span,
 };
 
+#[derive(Clone)]
 pub enum SymbolScope {
-    Register(u8),
+    Register(VReg),
+    // Index into the VM's global table
+    Global(usize),
 }
 
+#[derive(Clone)]
 pub struct Symbol {
     pub name: String,
     pub symbol_type: Type,
@@ -18,13 +23,20 @@ pub struct Symbol {
     pub scope: SymbolScope,
 }
 
-#[derive(Default)]
+#[derive(Clone)]
 pub struct SymbolTable {
     pub symbol_scopes: Vec<HashMap<String, Symbol>>,
     scope_depth: usize,
-    next_register: u8,
+    next_register: VReg,
     // Track the highest register used at each scope level
-    scope_register_watermarks: Vec<u8>,
+    scope_register_watermarks: Vec<VReg>,
+    next_global: usize,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolTable {
@@ -34,7 +46,71 @@ impl SymbolTable {
             scope_depth: 0,
             next_register: 0,
             scope_register_watermarks: vec![0],
+            next_global: 0,
+        }
+    }
+
+    /*
+     * Creates the root scope of a function body
+     *
+     * Only globals are visible from the program, and the body starts one
+     * scope deep so its own variables never become globals
+     * */
+    pub fn enter_function_scope(&self) -> SymbolTable {
+        let globals = self.symbol_scopes[0]
+            .iter()
+            .filter(|(_, symbol)| matches!(symbol.scope, SymbolScope::Global(_)))
+            .map(|(name, symbol)| (name.clone(), symbol.clone()))
+            .collect();
+
+        let mut function_scope = SymbolTable {
+            symbol_scopes: vec![globals],
+            ..SymbolTable::new()
+        };
+        function_scope.enter_scope();
+        function_scope
+    }
+
+    /*
+     * Checks whether declarations go to the global table
+     * */
+    pub fn is_global_scope(&self) -> bool {
+        self.scope_depth == 0
+    }
+
+    pub fn declare_global(
+        &mut self,
+        symbol_type: Type,
+        name: String,
+        mutable: bool,
+        span: Span,
+        error_collector: &mut ErrorCollector,
+    ) -> Option<usize> {
+        if self.current_scope().contains_key(&name) {
+            let original_span = self.current_scope().get(&name).unwrap().span;
+            error_collector.add(VynError::VariableRedeclaration {
+                name,
+                original_span,
+                redeclaration_span: span,
+            });
+            return None;
         }
+
+        let index = self.next_global;
+        self.next_global += 1;
+
+        self.current_scope().insert(
+            name.clone(),
+            Symbol {
+                symbol_type,
+                scope: SymbolScope::Global(index),
+                name,
+                span,
+                mutable,
+            },
+        );
+
+        Some(index)
     }
 
     pub fn declare_ident_with_register(
@@ -42,7 +118,7 @@ impl SymbolTable {
         symbol_type: Type,
         name: String,
         mutable: bool,
-        register: u8,
+        register: VReg,
         span: Span,
         error_collector: &mut ErrorCollector,
     ) -> Option<()> {
@@ -82,7 +158,7 @@ impl SymbolTable {
         mutable: bool,
         span: Span,
         error_collector: &mut ErrorCollector,
-    ) -> Option<u8> {
+    ) -> Option<VReg> {
         if self.current_scope().contains_key(&name) {
             let original_span = self.current_scope().get(&name).unwrap().span;
             error_collector.add(VynError::VariableRedeclaration {
@@ -128,7 +204,7 @@ impl SymbolTable {
         }
     }
 
-    fn allocate_register(&mut self) -> u8 {
+    fn allocate_register(&mut self) -> VReg {
         let reg = self.next_register;
         self.next_register += 1;
         reg
@@ -138,6 +214,23 @@ impl SymbolTable {
         &mut self.symbol_scopes[self.scope_depth]
     }
 
+    /*
+     * Checks whether a variable in scope lives in `register`
+     * */
+    pub fn holds_register(&self, register: VReg) -> bool {
+        self.symbol_scopes.iter().any(|scope| {
+            scope
+                .values()
+                .any(|symbol| matches!(symbol.scope, SymbolScope::Register(reg) if reg == register))
+        })
+    }
+
+    pub fn is_declared(&self, name: &str) -> bool {
+        self.symbol_scopes
+            .iter()
+            .any(|scope| scope.contains_key(name))
+    }
+
     pub fn resolve_symbol(
         &self,
         name: &str,
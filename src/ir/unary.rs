@@ -0,0 +1,40 @@
+use crate::{
+    ast::ast::Expression,
+    ir::{builder::VynIRBuilder, ir_instr::VynIROC},
+    tokens::Token,
+    type_checker::type_checker::Type,
+};
+
+impl VynIRBuilder<'_> {
+    pub(crate) fn build_unary_expr(
+        &mut self,
+        operator: &Token,
+        right: &Expression,
+        expr: &Expression,
+    ) -> Option<u32> {
+        let src = self.build_expr(right)?;
+
+        // Unary plus is a no-op
+        if matches!(operator, Token::Plus) {
+            return Some(src);
+        }
+
+        let dest = self.allocate_vreg();
+        let opcode = match operator {
+            Token::Minus => {
+                if matches!(self.get_expr_type(right)?, Type::Integer) {
+                    VynIROC::NegateInt { dest, src }
+                } else {
+                    VynIROC::NegateFloat { dest, src }
+                }
+            }
+            Token::Not | Token::Bang => VynIROC::Not { dest, src },
+
+            _ => unreachable!(),
+        };
+
+        self.emit(opcode.spanned(expr.span));
+
+        Some(dest)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    error_handler::{error_collector::ErrorCollector, errors::VynError},
     tokens::{Token, TokenInfo},
     utils::Span,
 };
@@ -9,6 +10,10 @@ pub struct Lexer {
     line: u32,
     column: u32,
     last_token: Option<Token>,
+    errors: ErrorCollector,
+    // One entry per string whose `{...}` is being lexed: its quote, how
+    // many '{' inside the interpolation are still open and where it started
+    interpolations: Vec<(char, u32, Span)>,
 }
 
 impl Lexer {
@@ -19,6 +24,8 @@ impl Lexer {
             line: 1,
             column: 1,
             last_token: None,
+            errors: ErrorCollector::new(),
+            interpolations: Vec::new(),
         }
     }
 
@@ -63,6 +70,7 @@ impl Lexer {
     }
 
     fn read_number(&mut self) -> Token {
+        let start_col = self.column;
         let mut num = String::new();
         let mut is_float = false;
 
@@ -82,7 +90,24 @@ impl Lexer {
         if is_float {
             Token::Float(num.parse().unwrap_or(0.0))
         } else {
-            Token::Integer(num.parse().unwrap_or(0))
+            match num.parse() {
+                Ok(n) => Token::Integer(n),
+                // Only fits as the operand of a unary minus, the parser folds
+                // the two into i64::MIN and reports any other use
+                Err(_) if num.parse() == Ok(i64::MIN.unsigned_abs()) => Token::Integer(i64::MIN),
+                Err(_) => {
+                    // Keep lexing so later errors are reported too
+                    self.errors.add(VynError::IntegerLiteralOverflow {
+                        literal: num,
+                        span: Span {
+                            line: self.line,
+                            start_column: start_col,
+                            end_column: self.column,
+                        },
+                    });
+                    Token::Integer(0)
+                }
+            }
         }
     }
 
@@ -102,15 +127,51 @@ impl Lexer {
     }
 
     fn read_string(&mut self) -> Token {
+        let quote = self.current().unwrap_or('"');
         self.advance(); // skip opening quote
+        self.read_string_part(quote, false)
+    }
+
+    /*
+     * Reads string text up to the closing quote or the next '{'
+     *
+     * -- Arguments: [&mut self], quote - quote the string was opened with
+     *               continued - whether this part follows an interpolation
+     * -- Return value: Token::String for a plain string, otherwise the
+     *                  StringHead / StringMiddle / StringTail piece
+     * */
+    fn read_string_part(&mut self, quote: char, continued: bool) -> Token {
         let mut s = String::new();
 
         while let Some(ch) = self.current() {
-            if ch == '"' || ch == '\'' {
+            if ch == quote {
                 self.advance(); // skip closing quote
+
+                if continued {
+                    self.interpolations.pop();
+                    return Token::StringTail(s);
+                }
                 return Token::String(s);
             }
 
+            if ch == '{' {
+                let open_span = Span {
+                    line: self.line,
+                    start_column: self.column,
+                    end_column: self.column + 1,
+                };
+                self.advance();
+
+                if continued {
+                    if let Some((_, _, span)) = self.interpolations.last_mut() {
+                        *span = open_span;
+                    }
+                    return Token::StringMiddle(s);
+                }
+                self.interpolations.push((quote, 0, open_span));
+                return Token::StringHead(s);
+            }
+
             if ch == '\\' {
                 self.advance();
                 if let Some(escaped) = self.current() {
@@ -121,6 +182,8 @@ impl Lexer {
                         '"' => s.push('"'),
                         '\'' => s.push('\''),
                         '\\' => s.push('\\'),
+                        '{' => s.push('{'),
+                        '}' => s.push('}'),
                         _ => {
                             s.push('\\');
                             s.push(escaped);
@@ -134,9 +197,25 @@ impl Lexer {
             }
         }
 
+        // A string inside `{...}` that never ends usually means the `}` is
+        // missing and the closing quote was taken for a nested string
+        self.close_interpolations();
         Token::Illegal('"')
     }
 
+    /*
+     * Reports the innermost interpolation still open and forgets all of
+     * them, so one missing `}` is reported once
+     *
+     * */
+    fn close_interpolations(&mut self) {
+        if let Some(&(_, _, span)) = self.interpolations.last() {
+            self.errors
+                .add(VynError::UnterminatedInterpolation { span });
+            self.interpolations.clear();
+        }
+    }
+
     pub fn next_token(&mut self) -> TokenInfo {
         self.skip_whitespace();
 
@@ -153,6 +232,9 @@ impl Lexer {
             None => Token::EndOfFile,
 
             Some('\n') => {
+                // An interpolation ends on the line it starts
+                self.close_interpolations();
+
                 // Skip consecutive newlines
                 if matches!(self.last_token, Some(Token::Newline)) {
                     self.advance();
@@ -173,11 +255,25 @@ impl Lexer {
             }
             Some('{') => {
                 self.advance();
+                if let Some((_, depth, _)) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
                 Token::LeftBrace
             }
             Some('}') => {
                 self.advance();
-                Token::RightBrace
+                match self.interpolations.last_mut() {
+                    // Closes an interpolation, the string continues
+                    Some((quote, 0, _)) => {
+                        let quote = *quote;
+                        self.read_string_part(quote, true)
+                    }
+                    Some((_, depth, _)) => {
+                        *depth -= 1;
+                        Token::RightBrace
+                    }
+                    None => Token::RightBrace,
+                }
             }
             Some('[') => {
                 self.advance();
@@ -199,10 +295,6 @@ impl Lexer {
                 self.advance();
                 Token::Plus
             }
-            Some('-') => {
-                self.advance();
-                Token::Minus
-            }
             Some('*') => {
                 self.advance();
                 Token::Asterisk
@@ -223,6 +315,10 @@ impl Lexer {
                 self.advance();
                 Token::Caret
             }
+            Some('?') => {
+                self.advance();
+                Token::Question
+            }
 
             // Two-char tokens
             Some(':') => {
@@ -235,6 +331,16 @@ impl Lexer {
                 }
             }
 
+            Some('-') => {
+                self.advance();
+                if self.current() == Some('>') {
+                    self.advance();
+                    Token::Arrow
+                } else {
+                    Token::Minus
+                }
+            }
+
             Some('.') => {
                 self.advance();
                 if self.current() == Some('.') {
@@ -250,6 +356,9 @@ impl Lexer {
                 if self.current() == Some('=') {
                     self.advance();
                     Token::Equal
+                } else if self.current() == Some('>') {
+                    self.advance();
+                    Token::FatArrow
                 } else {
                     Token::Assign
                 }
@@ -314,7 +423,14 @@ impl Lexer {
         TokenInfo { token, span }
     }
 
-    pub fn tokenize(&mut self) -> Vec<TokenInfo> {
+    /*
+     * Splits the whole input into tokens
+     *
+     * -- Arguments: [&mut self]
+     * -- Return value: Result<Vec<TokenInfo>, ErrorCollector> - the tokens,
+     *                  or every malformed literal found in the input
+     * */
+    pub fn tokenize(&mut self) -> Result<Vec<TokenInfo>, ErrorCollector> {
         let mut tokens = Vec::new();
 
         loop {
@@ -333,6 +449,10 @@ impl Lexer {
             }
         }
 
-        tokens
+        if self.errors.has_errors() {
+            Err(std::mem::take(&mut self.errors))
+        } else {
+            Ok(tokens)
+        }
     }
 }
@@ -13,6 +13,7 @@ pub enum Precedence {
     Comparison,
     Additive,
     Multiplicative,
+    Cast,
     Unary,
     Exponent,
     Call,
@@ -33,8 +34,11 @@ impl Precedence {
             | TokenType::GreaterThanEqual => Some(Precedence::Comparison),
             TokenType::Plus | TokenType::Minus => Some(Precedence::Additive),
             TokenType::Asterisk | TokenType::Slash => Some(Precedence::Multiplicative),
+            TokenType::As => Some(Precedence::Cast),
             TokenType::Caret => Some(Precedence::Exponent),
-            TokenType::LeftParenthesis | TokenType::BoxColon => Some(Precedence::Call),
+            TokenType::LeftParenthesis | TokenType::BoxColon | TokenType::Dot => {
+                Some(Precedence::Call)
+            }
             TokenType::Not => Some(Precedence::Unary),
             _ => None,
         }
@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use crate::{
     ast::{
-        ast::{Expr, Expression, Program, Statement, Stmt},
+        ast::{
+            EnumVariant, Expr, Expression, FunctionParameter, MatchArm, MatchExprArm, Pattern,
+            Program, Statement, Stmt, StructField, StructLiteralField,
+        },
         type_annotation::TypeAnnotation,
     },
     error_handler::{error_collector::ErrorCollector, errors::VynError},
@@ -51,11 +54,13 @@ impl Parser {
         parser.register_nud(TokenType::True, Parser::parse_bool_literal);
         parser.register_nud(TokenType::Identifier, Parser::parse_identifier_literal);
         parser.register_nud(TokenType::String, Parser::parse_string_literal);
+        parser.register_nud(TokenType::StringHead, Parser::parse_interpolated_string);
         parser.register_nud(TokenType::Nil, Parser::parse_nil_literal);
         parser.register_nud(TokenType::LeftBracket, Parser::parse_array_literal);
 
         parser.register_nud(TokenType::Minus, Parser::parse_unary_expr);
         parser.register_nud(TokenType::LeftParenthesis, Parser::parse_grouping_expr);
+        parser.register_nud(TokenType::Match, Parser::parse_match_expr);
 
         parser.register_nud(TokenType::Not, Parser::parse_unary_expr);
         parser.register_led(TokenType::And, Parser::parse_binary_expr);
@@ -75,16 +80,24 @@ impl Parser {
         parser.register_led(TokenType::NotEqual, Parser::parse_binary_expr);
         parser.register_led(TokenType::Assign, Parser::parse_assignment_expr);
         parser.register_led(TokenType::BoxColon, Parser::parse_index_expr);
+        parser.register_led(TokenType::LeftParenthesis, Parser::parse_call_expr);
+        parser.register_led(TokenType::As, Parser::parse_cast_expr);
+        parser.register_led(TokenType::Dot, Parser::parse_field_access_expr);
 
         parser.register_stmt(TokenType::Let, Parser::parse_variable_decl);
         parser.register_stmt(TokenType::Static, Parser::parse_static_variable_decl);
         parser.register_stmt(TokenType::Type, Parser::parse_type_alias_decl);
+        parser.register_stmt(TokenType::Struct, Parser::parse_struct_decl);
+        parser.register_stmt(TokenType::Enum, Parser::parse_enum_decl);
+        parser.register_stmt(TokenType::Match, Parser::parse_match_stmt);
         parser.register_stmt(TokenType::Stdout, Parser::parse_stdout_log_decl);
         parser.register_stmt(TokenType::If, Parser::parse_if_stmt_decl);
         parser.register_stmt(TokenType::Loop, Parser::parse_loop_stmt_decl);
         parser.register_stmt(TokenType::Break, Parser::parse_loop_interrupt_stmt);
         parser.register_stmt(TokenType::Continue, Parser::parse_loop_interrupt_stmt);
         parser.register_stmt(TokenType::For, Parser::parse_for_loop_stmt);
+        parser.register_stmt(TokenType::Function, Parser::parse_function_decl);
+        parser.register_stmt(TokenType::Return, Parser::parse_return_stmt);
 
         parser
     }
@@ -306,8 +319,18 @@ impl Parser {
 impl Parser {
     // ------------------- Null Denoted Expressions -------------------
     pub fn parse_integer_literal(&mut self) -> Option<Expression> {
-        let token_info = self.current_token();
+        let token_info = self.current_token().clone();
         let value = match token_info.token {
+            // The lexer's stand-in for 9223372036854775808, which only
+            // fits when parse_unary_expr negates it
+            Token::Integer(i64::MIN) => {
+                // Keep parsing so later errors are reported too
+                self.errors.add(VynError::IntegerLiteralOverflow {
+                    literal: i64::MIN.unsigned_abs().to_string(),
+                    span: token_info.span,
+                });
+                0
+            }
             Token::Integer(n) => n,
             _ => unreachable!(),
         };
@@ -355,6 +378,169 @@ impl Parser {
         let expr = Expr::Identifier(ident).spanned(token_info.span);
 
         self.advance();
+
+        // `Name {` starts a struct literal when `Name` is a struct type
+        if self.current_token_type() == TokenType::LeftBrace
+            && let Expr::Identifier(name) = &expr.node
+            && let Some(struct_type @ TypeAnnotation::StructType { .. }) =
+                self.type_table.resolve(name)
+        {
+            let struct_type = struct_type.clone();
+            return self.parse_struct_literal(struct_type, expr.span);
+        }
+
+        // `Name::Variant` builds an enum value when `Name` is an enum type
+        if self.current_token_type() == TokenType::BoxColon
+            && let Expr::Identifier(name) = &expr.node
+            && let Some(enum_type @ TypeAnnotation::EnumType { .. }) =
+                self.type_table.resolve(name)
+        {
+            let enum_type = enum_type.clone();
+            return self.parse_enum_variant(enum_type, expr.span);
+        }
+
+        Some(expr)
+    }
+
+    /*
+     * Parses an identifier without treating it as the start of a literal
+     *
+     * Declarations use it for the names they introduce, which may shadow or
+     * repeat a type name
+     *
+     * -- Return value: Some(Identifier expression)
+     * */
+    fn parse_plain_identifier(&mut self) -> Option<Expression> {
+        let token_info = self.current_token().clone();
+
+        let Token::Identifier(name) = token_info.token else {
+            self.errors.add(VynError::ExpectedToken {
+                expected: TokenType::Identifier,
+                got: self.current_token_type(),
+                span: token_info.span,
+            });
+            return None;
+        };
+
+        self.advance();
+        Some(Expr::Identifier(name).spanned(token_info.span))
+    }
+
+    /*
+     * Parses the `::Variant` or `::Variant(values)` part of an enum value
+     *
+     * -- Arguments: [&mut self], enum_type - type named before the `::`
+     *               name_span - span of that name
+     * -- Return value: Some(EnumVariant expression)
+     * */
+    fn parse_enum_variant(
+        &mut self,
+        enum_type: TypeAnnotation,
+        name_span: Span,
+    ) -> Option<Expression> {
+        self.advance(); // Eat '::'
+
+        let variant = self.parse_plain_identifier()?;
+        let mut end_column = variant.span.end_column;
+
+        let arguments = if self.current_token_type() == TokenType::LeftParenthesis {
+            let (arguments, rp_span) = self.parse_arguments()?;
+            end_column = rp_span.end_column;
+            arguments
+        } else {
+            Vec::new()
+        };
+
+        let full_span = Span {
+            line: name_span.line,
+            start_column: name_span.start_column,
+            end_column,
+        };
+
+        let expr = Expr::EnumVariant {
+            enum_type: Box::new(enum_type),
+            variant: Box::new(variant),
+            arguments,
+        }
+        .spanned(full_span);
+
+        Some(expr)
+    }
+
+    /*
+     * Parses the `{ field: value, ... }` part of a struct literal
+     *
+     * -- Arguments: [&mut self], struct_type - type named before the brace
+     *               name_span - span of that name
+     * -- Return value: Some(StructLiteral expression)
+     * */
+    fn parse_struct_literal(
+        &mut self,
+        struct_type: TypeAnnotation,
+        name_span: Span,
+    ) -> Option<Expression> {
+        self.advance(); // Eat '{'
+        self.delimiter_stack.push(TokenType::LeftBrace);
+        self.skip_newlines_in_delimiters();
+
+        let mut fields = Vec::new();
+
+        while self.current_token_type() != TokenType::RightBrace {
+            if self.current_token_type() != TokenType::Identifier {
+                self.errors.add(VynError::ExpectedToken {
+                    expected: TokenType::Identifier,
+                    got: self.current_token_type(),
+                    span: self.current_token().span,
+                });
+                self.delimiter_stack.pop();
+                return None;
+            }
+
+            let identifier = self.parse_identifier_literal()?;
+
+            if !self.expect(TokenType::Colon) {
+                self.delimiter_stack.pop();
+                return None;
+            }
+
+            let value = match self.try_parse_expression(Precedence::Default.into()) {
+                Some(v) => v,
+                None => {
+                    self.delimiter_stack.pop();
+                    return None;
+                }
+            };
+            fields.push(StructLiteralField { identifier, value });
+
+            self.skip_newlines_in_delimiters();
+
+            if self.current_token_type() != TokenType::Comma {
+                break;
+            }
+
+            self.advance(); // Eat ','
+            self.skip_newlines_in_delimiters();
+        }
+
+        self.delimiter_stack.pop();
+
+        let rb_token_info = self.current_token().clone();
+        if !self.expect(TokenType::RightBrace) {
+            return None;
+        }
+
+        let full_span = Span {
+            line: name_span.line,
+            start_column: name_span.start_column,
+            end_column: rb_token_info.span.end_column,
+        };
+
+        let expr = Expr::StructLiteral {
+            struct_type: Box::new(struct_type),
+            fields,
+        }
+        .spanned(full_span);
+
         Some(expr)
     }
 
@@ -371,6 +557,57 @@ impl Parser {
         Some(expr)
     }
 
+    /*
+     * Parses "text {value} text" from its StringHead, StringMiddle and
+     * StringTail pieces
+     *
+     * -- Return value: Some(Expr::StringInterpolation), empty text pieces
+     *                  are left out
+     * */
+    pub fn parse_interpolated_string(&mut self) -> Option<Expression> {
+        let head = self.current_token().clone();
+        let mut parts = Vec::new();
+        let mut text_info = head.clone();
+
+        loop {
+            let text = match &text_info.token {
+                Token::StringHead(s) | Token::StringMiddle(s) | Token::StringTail(s) => s.clone(),
+                _ => unreachable!("Called on string pieces only"),
+            };
+            if !text.is_empty() {
+                parts.push(Expr::StringLiteral(text).spanned(text_info.span));
+            }
+
+            self.advance();
+            if text_info.token.get_token_type() == TokenType::StringTail {
+                break;
+            }
+
+            parts.push(self.try_parse_expression(Precedence::Default.into())?);
+
+            text_info = self.current_token().clone();
+            if !matches!(
+                text_info.token.get_token_type(),
+                TokenType::StringMiddle | TokenType::StringTail
+            ) {
+                self.errors.add(VynError::ExpectedToken {
+                    expected: TokenType::StringTail,
+                    got: text_info.token.get_token_type(),
+                    span: text_info.span,
+                });
+                return None;
+            }
+        }
+
+        let full_span = Span {
+            line: head.span.line,
+            start_column: head.span.start_column,
+            end_column: text_info.span.end_column,
+        };
+
+        Some(Expr::StringInterpolation { parts }.spanned(full_span))
+    }
+
     pub fn parse_nil_literal(&mut self) -> Option<Expression> {
         let token_info = self.current_token();
         let expr = Expr::NilLiteral.spanned(token_info.span);
@@ -413,6 +650,10 @@ impl Parser {
         let operator_info = self.current_token().clone();
         self.advance(); // Eat operator
 
+        if let Some(min) = self.parse_int_min(&operator_info) {
+            return Some(min);
+        }
+
         let value = self.try_parse_expression(Precedence::Unary.into())?;
         let val_span = value.span;
 
@@ -429,6 +670,39 @@ impl Parser {
         Some(expr)
     }
 
+    /*
+     * Folds `-9223372036854775808` into a single literal, its magnitude
+     * doesn't fit in an Int on its own
+     *
+     * Only done when nothing binds tighter than the minus, in
+     * `-9223372036854775808 ^ 2` the literal is still out of range
+     *
+     * -- Arguments: [&mut self], operator_info - the unary operator, already eaten
+     * -- Return value: Some(literal) if folded, None to parse as usual
+     * */
+    fn parse_int_min(&mut self, operator_info: &TokenInfo) -> Option<Expression> {
+        let literal_info = self.current_token().clone();
+        if operator_info.token != Token::Minus || literal_info.token != Token::Integer(i64::MIN) {
+            return None;
+        }
+
+        let next_prec = self
+            .tokens
+            .get(self.current + 1)
+            .and_then(|next| Precedence::get_token_precedence(&next.token.get_token_type()))
+            .unwrap_or(Precedence::Default);
+        if next_prec > Precedence::Unary {
+            return None;
+        }
+
+        self.advance(); // Eat literal
+        Some(Expr::IntegerLiteral(i64::MIN).spanned(Span {
+            line: operator_info.span.line,
+            start_column: operator_info.span.start_column,
+            end_column: literal_info.span.end_column,
+        }))
+    }
+
     pub fn parse_grouping_expr(&mut self) -> Option<Expression> {
         let left_paren_span = self.current_token().span;
         self.advance(); // Eat '('
@@ -558,6 +832,15 @@ impl Parser {
                 .spanned(full_span);
                 return Some(expr);
             }
+            Expr::FieldAccess { target, field } => {
+                let expr = Expr::FieldAssignment {
+                    target,
+                    field,
+                    new_value: Box::new(right),
+                }
+                .spanned(full_span);
+                return Some(expr);
+            }
             _ => {
                 let expr = Expr::VariableAssignment {
                     identifier: Box::new(left),
@@ -577,6 +860,27 @@ impl Parser {
 
         // Parse right associatively
         let right = self.try_parse_expression(bc_precedence)?;
+
+        // `target::start..end` slices instead of indexing
+        if self.current_token_type() == TokenType::RangeDot {
+            self.advance();
+            let end = self.try_parse_expression(bc_precedence)?;
+
+            let full_span = Span {
+                line: bc_token_info.span.line,
+                start_column: left.span.start_column,
+                end_column: end.span.end_column,
+            };
+
+            let expr = Expr::Slice {
+                target: Box::new(left),
+                start: Box::new(right),
+                end: Box::new(end),
+            }
+            .spanned(full_span);
+            return Some(expr);
+        }
+
         let right_span = right.span;
         let left_span = left.span;
 
@@ -593,6 +897,116 @@ impl Parser {
         .spanned(full_span);
         Some(expr)
     }
+
+    pub fn parse_field_access_expr(&mut self, left: Expression) -> Option<Expression> {
+        self.advance(); // Eat '.'
+
+        if self.current_token_type() != TokenType::Identifier {
+            self.errors.add(VynError::ExpectedToken {
+                expected: TokenType::Identifier,
+                got: self.current_token_type(),
+                span: self.current_token().span,
+            });
+            return None;
+        }
+
+        let field = self.parse_identifier_literal()?;
+
+        let full_span = Span {
+            line: left.span.line,
+            start_column: left.span.start_column,
+            end_column: field.span.end_column,
+        };
+
+        let expr = Expr::FieldAccess {
+            target: Box::new(left),
+            field: Box::new(field),
+        }
+        .spanned(full_span);
+
+        Some(expr)
+    }
+
+    pub fn parse_call_expr(&mut self, left: Expression) -> Option<Expression> {
+        let (arguments, rp_span) = self.parse_arguments()?;
+
+        let full_span = Span {
+            line: left.span.line,
+            start_column: left.span.start_column,
+            end_column: rp_span.end_column,
+        };
+
+        let expr = Expr::Call {
+            callee: Box::new(left),
+            arguments,
+        }
+        .spanned(full_span);
+
+        Some(expr)
+    }
+
+    /*
+     * Parses a parenthesized, comma separated list of expressions
+     *
+     * -- Return value: Some((expressions, span of the closing parenthesis))
+     * */
+    pub fn parse_arguments(&mut self) -> Option<(Vec<Box<Expression>>, Span)> {
+        self.advance(); // Eat '('
+        self.delimiter_stack.push(TokenType::LeftParenthesis);
+        self.skip_newlines_in_delimiters();
+
+        let mut arguments: Vec<Box<Expression>> = Vec::new();
+
+        while self.current_token_type() != TokenType::RightParenthesis {
+            let arg = match self.try_parse_expression(Precedence::Default.into()) {
+                Some(a) => a,
+                None => {
+                    self.delimiter_stack.pop();
+                    return None;
+                }
+            };
+            arguments.push(Box::new(arg));
+
+            self.skip_newlines_in_delimiters();
+
+            if self.current_token_type() != TokenType::Comma {
+                break;
+            }
+
+            self.advance(); // Eat ','
+            self.skip_newlines_in_delimiters();
+        }
+
+        self.delimiter_stack.pop();
+
+        let rp_token_info = self.current_token().clone();
+        if !self.expect(TokenType::RightParenthesis) {
+            return None;
+        }
+
+        Some((arguments, rp_token_info.span))
+    }
+
+    pub fn parse_cast_expr(&mut self, left: Expression) -> Option<Expression> {
+        self.advance(); // Eat 'as'
+
+        let type_span = self.current_token().span;
+        let target = self.try_parse_type()?;
+
+        let full_span = Span {
+            line: left.span.line,
+            start_column: left.span.start_column,
+            end_column: type_span.end_column,
+        };
+
+        let expr = Expr::Cast {
+            value: Box::new(left),
+            target: Box::new(target),
+        }
+        .spanned(full_span);
+
+        Some(expr)
+    }
 }
 
 // Statements
@@ -770,6 +1184,352 @@ impl Parser {
         Some(stmt)
     }
 
+    pub fn parse_struct_decl(&mut self) -> Option<Statement> {
+        let struct_tok_info = self.current_token().clone();
+        self.advance(); // Eat 'struct'
+
+        // A redeclared name would otherwise start a struct literal
+        let identifier = self.parse_plain_identifier()?;
+
+        if !self.expect(TokenType::LeftBrace) {
+            return None;
+        }
+
+        self.skip_delimiters();
+
+        let mut fields = Vec::new();
+
+        while self.current_token_type() != TokenType::RightBrace {
+            if self.current_token_type() != TokenType::Identifier {
+                self.errors.add(VynError::ExpectedToken {
+                    expected: TokenType::Identifier,
+                    got: self.current_token_type(),
+                    span: self.current_token().span,
+                });
+                return None;
+            }
+
+            let field_identifier = self.parse_identifier_literal()?;
+
+            if !self.expect(TokenType::Colon) {
+                return None;
+            }
+
+            let annotated_type = self.try_parse_type()?;
+
+            fields.push(StructField {
+                identifier: field_identifier,
+                annotated_type,
+            });
+
+            // Fields are separated by commas, newlines or both
+            if self.current_token_type() == TokenType::Comma {
+                self.advance();
+            }
+            self.skip_delimiters();
+        }
+
+        if !self.expect(TokenType::RightBrace) {
+            return None;
+        }
+
+        let struct_type = TypeAnnotation::StructType {
+            name: identifier.node.to_string(),
+            fields: fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.identifier.node.to_string(),
+                        field.annotated_type.clone(),
+                    )
+                })
+                .collect(),
+        };
+        self.enroll_declared_type(&identifier, struct_type)?;
+
+        let full_span = Span {
+            line: struct_tok_info.span.line,
+            start_column: struct_tok_info.span.start_column,
+            end_column: identifier.span.end_column,
+        };
+
+        let stmt = Stmt::StructDeclaration { identifier, fields }.spanned(full_span);
+
+        Some(stmt)
+    }
+
+    pub fn parse_enum_decl(&mut self) -> Option<Statement> {
+        let enum_tok_info = self.current_token().clone();
+        self.advance(); // Eat 'enum'
+
+        let identifier = self.parse_plain_identifier()?;
+
+        if !self.expect(TokenType::LeftBrace) {
+            return None;
+        }
+
+        self.skip_delimiters();
+
+        let mut variants = Vec::new();
+
+        while self.current_token_type() != TokenType::RightBrace {
+            let variant_identifier = self.parse_plain_identifier()?;
+            let mut payload = Vec::new();
+
+            if self.current_token_type() == TokenType::LeftParenthesis {
+                self.advance(); // Eat '('
+
+                while self.current_token_type() != TokenType::RightParenthesis {
+                    payload.push(self.try_parse_type()?);
+
+                    if self.current_token_type() != TokenType::Comma {
+                        break;
+                    }
+                    self.advance(); // Eat ','
+                }
+
+                if !self.expect(TokenType::RightParenthesis) {
+                    return None;
+                }
+            }
+
+            variants.push(EnumVariant {
+                identifier: variant_identifier,
+                payload,
+            });
+
+            // Variants are separated by commas, newlines or both
+            if self.current_token_type() == TokenType::Comma {
+                self.advance();
+            }
+            self.skip_delimiters();
+        }
+
+        if !self.expect(TokenType::RightBrace) {
+            return None;
+        }
+
+        let enum_type = TypeAnnotation::EnumType {
+            name: identifier.node.to_string(),
+            variants: variants
+                .iter()
+                .map(|variant| (variant.identifier.node.to_string(), variant.payload.clone()))
+                .collect(),
+        };
+        self.enroll_declared_type(&identifier, enum_type)?;
+
+        let full_span = Span {
+            line: enum_tok_info.span.line,
+            start_column: enum_tok_info.span.start_column,
+            end_column: identifier.span.end_column,
+        };
+
+        let stmt = Stmt::EnumDeclaration {
+            identifier,
+            variants,
+        }
+        .spanned(full_span);
+
+        Some(stmt)
+    }
+
+    /*
+     * Parses `match subject { pattern => body ... }` in statement position
+     *
+     * Arm bodies are blocks or single statements, one arm per line
+     *
+     * -- Return value: Some(Stmt::Match)
+     * */
+    pub fn parse_match_stmt(&mut self) -> Option<Statement> {
+        let (subject, span) = self.parse_match_header()?;
+
+        let mut arms = Vec::new();
+
+        while self.current_token_type() != TokenType::RightBrace {
+            let pattern = self.parse_match_pattern()?;
+
+            if !self.expect(TokenType::FatArrow) {
+                return None;
+            }
+
+            let body = if self.current_token_type() == TokenType::LeftBrace {
+                self.parse_scope_stmt()?
+            } else {
+                self.try_parse_statement()?
+            };
+
+            arms.push(MatchArm {
+                pattern,
+                body: Box::new(body),
+            });
+
+            if self.current_token_type() == TokenType::Comma {
+                self.advance();
+            }
+            self.skip_delimiters();
+        }
+
+        if !self.expect(TokenType::RightBrace) {
+            return None;
+        }
+
+        Some(Stmt::Match { subject, arms }.spanned(span))
+    }
+
+    /*
+     * Parses `match subject { pattern => value, ... }` in expression position
+     *
+     * -- Return value: Some(Expr::Match)
+     * */
+    pub fn parse_match_expr(&mut self) -> Option<Expression> {
+        let (subject, span) = self.parse_match_header()?;
+
+        // Arms may span several lines
+        self.delimiter_stack.push(TokenType::LeftBrace);
+
+        let mut arms = Vec::new();
+
+        while self.current_token_type() != TokenType::RightBrace {
+            let arm = self.parse_match_pattern().and_then(|pattern| {
+                if !self.expect(TokenType::FatArrow) {
+                    return None;
+                }
+
+                let value = self.try_parse_expression(Precedence::Default.into())?;
+                Some(MatchExprArm { pattern, value })
+            });
+
+            let Some(arm) = arm else {
+                self.delimiter_stack.pop();
+                return None;
+            };
+            arms.push(arm);
+
+            self.skip_newlines_in_delimiters();
+
+            if self.current_token_type() == TokenType::Comma {
+                self.advance();
+            }
+            self.skip_newlines_in_delimiters();
+        }
+
+        self.delimiter_stack.pop();
+
+        let rb_token_info = self.current_token().clone();
+        if !self.expect(TokenType::RightBrace) {
+            return None;
+        }
+
+        let full_span = Span {
+            line: span.line,
+            start_column: span.start_column,
+            end_column: rb_token_info.span.end_column,
+        };
+
+        let expr = Expr::Match {
+            subject: Box::new(subject),
+            arms,
+        }
+        .spanned(full_span);
+
+        Some(expr)
+    }
+
+    /*
+     * Parses `match subject {`, shared by both forms of match
+     *
+     * -- Return value: Some((subject, span from 'match' to the subject))
+     * */
+    fn parse_match_header(&mut self) -> Option<(Expression, Span)> {
+        let match_tok_info = self.current_token().clone();
+        self.advance(); // Eat 'match'
+
+        let subject = self.try_parse_expression(Precedence::Default.into())?;
+
+        if !self.expect(TokenType::LeftBrace) {
+            return None;
+        }
+
+        self.skip_delimiters();
+
+        let span = Span {
+            line: match_tok_info.span.line,
+            start_column: match_tok_info.span.start_column,
+            end_column: subject.span.end_column,
+        };
+
+        Some((subject, span))
+    }
+
+    /*
+     * Parses a match arm pattern: `_`, `Name::Variant` or
+     * `Name::Variant(a, b)`
+     *
+     * -- Return value: Some(pattern)
+     * */
+    fn parse_match_pattern(&mut self) -> Option<Spanned<Pattern>> {
+        let identifier = self.parse_plain_identifier()?;
+        let name = identifier.node.to_string();
+
+        if name == "_" {
+            return Some(Spanned {
+                node: Pattern::Wildcard,
+                span: identifier.span,
+            });
+        }
+
+        let enum_type = match self.type_table.resolve(&name) {
+            Some(enum_type @ TypeAnnotation::EnumType { .. }) => enum_type.clone(),
+            _ => {
+                self.errors.add(VynError::InvalidPattern {
+                    span: identifier.span,
+                });
+                return None;
+            }
+        };
+
+        if !self.expect(TokenType::BoxColon) {
+            return None;
+        }
+
+        let variant = self.parse_plain_identifier()?;
+        let mut end_column = variant.span.end_column;
+        let mut bindings = Vec::new();
+
+        if self.current_token_type() == TokenType::LeftParenthesis {
+            self.advance(); // Eat '('
+
+            while self.current_token_type() != TokenType::RightParenthesis {
+                bindings.push(self.parse_plain_identifier()?);
+
+                if self.current_token_type() != TokenType::Comma {
+                    break;
+                }
+                self.advance(); // Eat ','
+            }
+
+            end_column = self.current_token().span.end_column;
+            if !self.expect(TokenType::RightParenthesis) {
+                return None;
+            }
+        }
+
+        let span = Span {
+            line: identifier.span.line,
+            start_column: identifier.span.start_column,
+            end_column,
+        };
+
+        Some(Spanned {
+            node: Pattern::Variant {
+                enum_type,
+                variant,
+                bindings,
+            },
+            span,
+        })
+    }
+
     pub fn parse_stdout_log_decl(&mut self) -> Option<Statement> {
         let stdout_tok_info = self.current_token().clone();
         self.advance();
@@ -991,4 +1751,128 @@ impl Parser {
             }
         }
     }
+
+    pub fn parse_function_decl(&mut self) -> Option<Statement> {
+        let fn_tok_info = self.current_token().clone();
+        self.advance(); // Eat 'fn'
+
+        if self.current_token_type() != TokenType::Identifier {
+            self.errors.add(VynError::ExpectedToken {
+                expected: TokenType::Identifier,
+                got: self.current_token_type(),
+                span: self.current_token().span,
+            });
+            return None;
+        }
+
+        let identifier = self.parse_identifier_literal()?;
+
+        if !self.expect(TokenType::LeftParenthesis) {
+            return None;
+        }
+
+        self.delimiter_stack.push(TokenType::LeftParenthesis);
+        let parameters = self.parse_function_params();
+        self.delimiter_stack.pop();
+
+        let parameters = parameters?;
+
+        if !self.expect(TokenType::RightParenthesis) {
+            return None;
+        }
+
+        let mut return_type = None;
+        if self.current_token_type() == TokenType::Arrow {
+            self.advance(); // Eat '->'
+            return_type = Some(self.try_parse_type()?);
+        }
+
+        let body = self.parse_scope_stmt()?;
+
+        let full_span = Span {
+            line: fn_tok_info.span.line,
+            start_column: fn_tok_info.span.start_column,
+            end_column: identifier.span.end_column,
+        };
+
+        let stmt = Stmt::FunctionDeclaration {
+            identifier,
+            parameters,
+            return_type,
+            body: Box::new(body),
+        }
+        .spanned(full_span);
+
+        Some(stmt)
+    }
+
+    fn parse_function_params(&mut self) -> Option<Vec<FunctionParameter>> {
+        let mut parameters = Vec::new();
+        self.skip_newlines_in_delimiters();
+
+        while self.current_token_type() != TokenType::RightParenthesis {
+            let mut mutable = false;
+            if self.current_token_is(TokenType::At) {
+                self.advance();
+                mutable = true;
+            }
+
+            if self.current_token_type() != TokenType::Identifier {
+                self.errors.add(VynError::ExpectedToken {
+                    expected: TokenType::Identifier,
+                    got: self.current_token_type(),
+                    span: self.current_token().span,
+                });
+                return None;
+            }
+
+            let identifier = self.parse_identifier_literal()?;
+
+            if !self.expect(TokenType::Colon) {
+                return None;
+            }
+
+            let annotated_type = self.try_parse_type()?;
+
+            parameters.push(FunctionParameter {
+                identifier,
+                annotated_type,
+                mutable,
+            });
+
+            self.skip_newlines_in_delimiters();
+
+            if self.current_token_type() != TokenType::Comma {
+                break;
+            }
+
+            self.advance(); // Eat ','
+            self.skip_newlines_in_delimiters();
+        }
+
+        Some(parameters)
+    }
+
+    pub fn parse_return_stmt(&mut self) -> Option<Statement> {
+        let return_tok_info = self.current_token().clone();
+        self.advance(); // Eat 'return'
+
+        let mut value = None;
+        let mut full_span = return_tok_info.span;
+
+        if !self.current_token_type().is_delimiter()
+            && self.current_token_type() != TokenType::RightBrace
+        {
+            let expr = self.try_parse_expression(Precedence::Default.into())?;
+            full_span.end_column = expr.span.end_column;
+            value = Some(expr);
+        }
+
+        // `return` may be the last thing before a closing brace
+        if self.current_token_type() != TokenType::RightBrace && !self.expect_delimiter() {
+            return None;
+        }
+
+        Some(Stmt::Return { value }.spanned(full_span))
+    }
 }
@@ -10,16 +10,27 @@ use crate::{
     tokens::TokenType,
 };
 
+#[derive(Clone, Default)]
 pub struct TypeTable {
     pub aliases: HashMap<String, TypeAnnotation>,
+    // Structs and enums
+    pub declared: HashMap<String, TypeAnnotation>,
 }
 
 impl TypeTable {
     pub fn new() -> Self {
         Self {
             aliases: HashMap::new(),
+            declared: HashMap::new(),
         }
     }
+
+    /*
+     * Looks up a user-declared type name, either an alias, a struct or an enum
+     * */
+    pub fn resolve(&self, name: &str) -> Option<&TypeAnnotation> {
+        self.aliases.get(name).or_else(|| self.declared.get(name))
+    }
 }
 
 impl Parser {
@@ -28,10 +39,19 @@ impl Parser {
         let current_token_type = current_token.token.get_token_type();
 
         // Dispatch table for special type syntax
-        match current_token_type {
+        let parsed_type = match current_token_type {
             TokenType::LeftBracket => self.parse_array_type(),
             _ => self.parse_simple_type(),
+        }?;
+
+        // A trailing `?` makes the type optional, `[]Int?` is a sequence of
+        // optional Ints
+        if self.current_token_type() == TokenType::Question {
+            self.advance();
+            return Some(TypeAnnotation::OptionalType(Box::new(parsed_type)));
         }
+
+        Some(parsed_type)
     }
 
     fn parse_simple_type(&mut self) -> Option<TypeAnnotation> {
@@ -60,8 +80,8 @@ impl Parser {
                 Some(t.clone())
             }
             None => {
-                // Check if it's a type alias
-                if let Some(al_type) = self.type_table.aliases.get(type_name) {
+                // Check if it's a type alias or a struct
+                if let Some(al_type) = self.type_table.resolve(type_name) {
                     let result = al_type.clone();
                     self.advance();
                     Some(result)
@@ -119,4 +139,34 @@ impl Parser {
         self.type_table.aliases.insert(name, aliased_type)?;
         Some(())
     }
+
+    /*
+     * Registers a struct or enum so later type annotations and literals can
+     * use it
+     *
+     * -- Return value: None if the name is already taken by another type
+     * */
+    pub fn enroll_declared_type(
+        &mut self,
+        ident: &Expression,
+        declared_type: TypeAnnotation,
+    ) -> Option<()> {
+        let name = match &ident.node {
+            Expr::Identifier(s) => s.clone(),
+            _ => unreachable!(),
+        };
+
+        if TypeAnnotation::from_identifier(&name).is_some()
+            || self.type_table.resolve(&name).is_some()
+        {
+            self.errors.add(VynError::TypeRedeclaration {
+                name,
+                span: ident.span,
+            });
+            return None;
+        }
+
+        self.type_table.declared.insert(name, declared_type);
+        Some(())
+    }
 }
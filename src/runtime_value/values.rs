@@ -4,12 +4,14 @@ use std::io::{self, Write};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RuntimeValue {
-    IntegerLiteral(i32),
+    IntegerLiteral(i64),
     FloatLiteral(f64),
     BooleanLiteral(bool),
     StringLiteral(usize),   // pointer to a string in the string table
     ArrayLiteral(usize),    // points to a fixed array in heap table
     SequenceLiteral(usize), // points to a dynamic array in heap table
+    StructLiteral(usize),   // points to a struct in heap table
+    EnumLiteral(usize),     // points to an enum value in heap table
     NilLiteral,
 }
 
@@ -20,6 +22,8 @@ pub enum RuntimeType {
     Boolean,
     String,
     Array,
+    Struct,
+    Enum,
     Nil,
 }
 
@@ -31,6 +35,8 @@ impl Display for RuntimeType {
             RuntimeType::Boolean => write!(f, "Boolean"),
             RuntimeType::String => write!(f, "String"),
             RuntimeType::Array => write!(f, "Array"),
+            RuntimeType::Struct => write!(f, "Struct"),
+            RuntimeType::Enum => write!(f, "Enum"),
             RuntimeType::Nil => write!(f, "Nil"),
         }
     }
@@ -44,13 +50,15 @@ impl RuntimeType {
             RuntimeType::Boolean => "boolean",
             RuntimeType::String => "string",
             RuntimeType::Array => "array",
+            RuntimeType::Struct => "struct",
+            RuntimeType::Enum => "enum",
             RuntimeType::Nil => "nil",
         }
     }
 }
 
 impl RuntimeValue {
-    pub fn as_int(&self) -> Option<i32> {
+    pub fn as_int(&self) -> Option<i64> {
         match self {
             RuntimeValue::IntegerLiteral(n) => Some(*n),
             _ => None,
@@ -97,6 +105,8 @@ impl RuntimeValue {
             RuntimeValue::BooleanLiteral(_) => RuntimeType::Boolean,
             RuntimeValue::StringLiteral(_) => RuntimeType::String,
             RuntimeValue::ArrayLiteral(_) | RuntimeValue::SequenceLiteral(_) => RuntimeType::Array,
+            RuntimeValue::StructLiteral(_) => RuntimeType::Struct,
+            RuntimeValue::EnumLiteral(_) => RuntimeType::Enum,
             RuntimeValue::NilLiteral => RuntimeType::Nil,
         }
     }
@@ -118,7 +128,12 @@ impl RuntimeValue {
 }
 
 impl RuntimeValue {
-    pub fn write_to<W: Write>(&self, out: &mut W, string_table: &[String]) -> io::Result<()> {
+    pub fn write_to<W: Write>(
+        &self,
+        out: &mut W,
+        string_table: &[String],
+        heap_table: &[HeapObject],
+    ) -> io::Result<()> {
         match self {
             RuntimeValue::IntegerLiteral(n) => write!(out, "{n}"),
             RuntimeValue::FloatLiteral(n) => write!(out, "{n}"),
@@ -127,41 +142,108 @@ impl RuntimeValue {
                 let value = &string_table[*idx];
                 out.write_all(value.as_bytes())
             }
-            RuntimeValue::ArrayLiteral(idx) => out.write_all(b"arr_unimplemented"),
-            //     let elements = match &heap_table[*idx] {
-            //         HeapObject::Array { elements, .. } => elements,
-            //         _ => unreachable!(),
-            //     };
-            //
-            //     out.write_all(b"[")?;
-            //
-            //     for (i, elem) in elements.iter().enumerate() {
-            //         elem.write_to(out, heap_table)?;
-            //         if i != elements.len() - 1 {
-            //             out.write_all(b", ")?;
-            //         }
-            //     }
-            //
-            //     out.write_all(b"]")
-            // }
-            RuntimeValue::SequenceLiteral(idx) => out.write_all(b"seq_unimplemented"),
-            //     let elements = match &heap_table[*idx] {
-            //         HeapObject::Sequence { elements, .. } => elements,
-            //         obj => unreachable!("expected SequenceLiteral, got {:?}", obj),
-            //     };
-            //
-            //     out.write_all(b"[")?;
-            //
-            //     for (i, elem) in elements.iter().enumerate() {
-            //         elem.write_to(out, heap_table)?;
-            //         if i != elements.len() - 1 {
-            //             out.write_all(b", ")?;
-            //         }
-            //     }
-            //
-            //     out.write_all(b"]")
-            // }
+            RuntimeValue::ArrayLiteral(idx) | RuntimeValue::SequenceLiteral(idx) => {
+                let elements = heap_table[*idx].elements();
+
+                out.write_all(b"[")?;
+
+                for (i, elem) in elements.iter().enumerate() {
+                    elem.write_to(out, string_table, heap_table)?;
+                    if i != elements.len() - 1 {
+                        out.write_all(b", ")?;
+                    }
+                }
+
+                out.write_all(b"]")
+            }
+            RuntimeValue::StructLiteral(idx) => {
+                let HeapObject::Struct { layout, fields } = &heap_table[*idx] else {
+                    unreachable!("Expected a struct at heap index {idx}");
+                };
+
+                // The layout is the struct name followed by its field names
+                let mut names = string_table[*layout].split(' ');
+                let struct_name = names.next().unwrap_or_default();
+
+                write!(out, "{struct_name} {{ ")?;
+
+                for (i, (name, field)) in names.zip(fields).enumerate() {
+                    if i != 0 {
+                        out.write_all(b", ")?;
+                    }
+                    write!(out, "{name}: ")?;
+                    field.write_to(out, string_table, heap_table)?;
+                }
+
+                out.write_all(b" }")
+            }
+            RuntimeValue::EnumLiteral(idx) => {
+                let HeapObject::Enum { name, payload, .. } = &heap_table[*idx] else {
+                    unreachable!("Expected an enum value at heap index {idx}");
+                };
+
+                out.write_all(string_table[*name].as_bytes())?;
+
+                if payload.is_empty() {
+                    return Ok(());
+                }
+
+                out.write_all(b"(")?;
+                for (i, value) in payload.iter().enumerate() {
+                    if i != 0 {
+                        out.write_all(b", ")?;
+                    }
+                    value.write_to(out, string_table, heap_table)?;
+                }
+                out.write_all(b")")
+            }
             RuntimeValue::NilLiteral => out.write_all(b"nil"),
         }
     }
 }
+
+/*
+ * Objects living in the VM heap table
+ *
+ * RuntimeValue::ArrayLiteral, RuntimeValue::SequenceLiteral,
+ * RuntimeValue::StructLiteral and RuntimeValue::EnumLiteral hold an index
+ * into this table instead of the elements themselves
+ * */
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeapObject {
+    Array {
+        elements: Vec<RuntimeValue>,
+    },
+    Sequence {
+        elements: Vec<RuntimeValue>,
+    },
+    // layout: string table index of the struct name and field names
+    Struct {
+        layout: usize,
+        fields: Vec<RuntimeValue>,
+    },
+    // name: string table index of the `Enum::Variant` text
+    Enum {
+        name: usize,
+        tag: usize,
+        payload: Vec<RuntimeValue>,
+    },
+}
+
+impl HeapObject {
+    pub fn elements(&self) -> &[RuntimeValue] {
+        match self {
+            HeapObject::Array { elements } | HeapObject::Sequence { elements } => elements,
+            HeapObject::Struct { fields, .. } => fields,
+            HeapObject::Enum { payload, .. } => payload,
+        }
+    }
+
+    pub fn elements_mut(&mut self) -> &mut Vec<RuntimeValue> {
+        match self {
+            HeapObject::Array { elements } | HeapObject::Sequence { elements } => elements,
+            HeapObject::Struct { fields, .. } => fields,
+            HeapObject::Enum { payload, .. } => payload,
+        }
+    }
+}
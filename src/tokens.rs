@@ -10,9 +10,13 @@ pub struct TokenInfo {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     // Literals
-    Integer(i32),
+    Integer(i64),
     Float(f64),
     String(String),
+    // Pieces of an interpolated string: "head{a}middle{b}tail"
+    StringHead(String),
+    StringMiddle(String),
+    StringTail(String),
     Identifier(String),
 
     // Operators
@@ -42,6 +46,9 @@ pub enum Token {
     Dot,
     RangeDot,
     BoxColon, // ::
+    Arrow,    // ->
+    FatArrow, // =>
+    Question, // ?
 
     // Grouping
     LeftParenthesis,
@@ -77,6 +84,10 @@ pub enum Token {
     When,
     From,
     Steps,
+    As,
+    Struct,
+    Enum,
+    Match,
 }
 
 impl fmt::Display for Token {
@@ -86,6 +97,9 @@ impl fmt::Display for Token {
             Token::Integer(n) => write!(f, "{}", n),
             Token::Float(fl) => write!(f, "{}", fl),
             Token::String(s) => write!(f, "\"{}\"", s),
+            Token::StringHead(s) => write!(f, "\"{}{{", s),
+            Token::StringMiddle(s) => write!(f, "}}{}{{", s),
+            Token::StringTail(s) => write!(f, "}}{}\"", s),
             Token::Identifier(name) => write!(f, "{}", name),
 
             // For everything else, delegate to TokenType's Display
@@ -100,6 +114,9 @@ pub enum TokenType {
     Integer,
     Float,
     String,
+    StringHead,
+    StringMiddle,
+    StringTail,
     Identifier,
 
     // Operators
@@ -129,6 +146,9 @@ pub enum TokenType {
     Dot,
     RangeDot,
     BoxColon, // ::
+    Arrow,    // ->
+    FatArrow, // =>
+    Question, // ?
 
     // Grouping
     LeftParenthesis,
@@ -164,6 +184,10 @@ pub enum TokenType {
     When,
     From,
     Steps,
+    As,
+    Struct,
+    Enum,
+    Match,
 }
 
 impl TokenType {
@@ -182,6 +206,9 @@ impl fmt::Display for TokenType {
             TokenType::Integer => write!(f, "Integer"),
             TokenType::Float => write!(f, "Float"),
             TokenType::String => write!(f, "String"),
+            TokenType::StringHead | TokenType::StringMiddle | TokenType::StringTail => {
+                write!(f, "interpolated string")
+            }
             TokenType::Identifier => write!(f, "Identifier"),
 
             // Operators
@@ -211,6 +238,9 @@ impl fmt::Display for TokenType {
             TokenType::Dot => write!(f, "."),
             TokenType::RangeDot => write!(f, ".."),
             TokenType::BoxColon => write!(f, "::"),
+            TokenType::Arrow => write!(f, "->"),
+            TokenType::FatArrow => write!(f, "=>"),
+            TokenType::Question => write!(f, "?"),
 
             // Grouping
             TokenType::LeftParenthesis => write!(f, "("),
@@ -246,6 +276,10 @@ impl fmt::Display for TokenType {
             TokenType::When => write!(f, "when"),
             TokenType::From => write!(f, "from"),
             TokenType::Steps => write!(f, "steps"),
+            TokenType::As => write!(f, "as"),
+            TokenType::Struct => write!(f, "struct"),
+            TokenType::Enum => write!(f, "enum"),
+            TokenType::Match => write!(f, "match"),
         }
     }
 }
@@ -274,6 +308,10 @@ impl Token {
             "when" => Token::When,
             "from" => Token::From,
             "steps" => Token::Steps,
+            "as" => Token::As,
+            "struct" => Token::Struct,
+            "enum" => Token::Enum,
+            "match" => Token::Match,
             _ => Token::Identifier(identifier.to_string()),
         }
     }
@@ -284,6 +322,9 @@ impl Token {
             Token::Integer(_) => TokenType::Integer,
             Token::Float(_) => TokenType::Float,
             Token::String(_) => TokenType::String,
+            Token::StringHead(_) => TokenType::StringHead,
+            Token::StringMiddle(_) => TokenType::StringMiddle,
+            Token::StringTail(_) => TokenType::StringTail,
             Token::Identifier(_) => TokenType::Identifier,
 
             // Operators
@@ -313,6 +354,9 @@ impl Token {
             Token::Dot => TokenType::Dot,
             Token::RangeDot => TokenType::RangeDot,
             Token::BoxColon => TokenType::BoxColon,
+            Token::Arrow => TokenType::Arrow,
+            Token::FatArrow => TokenType::FatArrow,
+            Token::Question => TokenType::Question,
 
             // Grouping
             Token::LeftParenthesis => TokenType::LeftParenthesis,
@@ -348,6 +392,10 @@ impl Token {
             Token::When => TokenType::When,
             Token::From => TokenType::From,
             Token::Steps => TokenType::Steps,
+            Token::As => TokenType::As,
+            Token::Struct => TokenType::Struct,
+            Token::Enum => TokenType::Enum,
+            Token::Match => TokenType::Match,
         }
     }
 }
@@ -0,0 +1,179 @@
+use std::{mem, rc::Rc};
+
+use crate::{
+    ast::ast::Expression,
+    error_handler::{error_collector::ErrorCollector, errors::VynError},
+    type_checker::{
+        symbol_type_table::{SymbolType, SymbolUsage},
+        type_checker::TypeChecker,
+    },
+    utils::Span,
+};
+
+/*
+ * Which variables declared without a value are still unassigned at one
+ * point of the program, one flag per entry of `uninitialized`
+ * */
+pub(crate) type AssignmentState = Vec<bool>;
+
+impl TypeChecker<'_> {
+    /*
+     * Starts tracking a variable declared without a value
+     *
+     * Until every path reaching a read has assigned it, reading it is an
+     * error. Optionals are exempt, they start as nil
+     * */
+    pub(crate) fn track_uninitialized(&mut self, usage: Rc<SymbolUsage>) {
+        usage.unassigned.set(true);
+        self.uninitialized.push(usage);
+    }
+
+    /*
+     * Reports a read of a variable some path leaves unassigned
+     *
+     * The variable counts as assigned afterwards, so it is reported once
+     * */
+    pub(crate) fn check_assigned(
+        &mut self,
+        name: &str,
+        symbol: &SymbolType,
+        span: Span,
+    ) -> Result<(), ()> {
+        if !symbol.usage.unassigned.replace(false) {
+            return Ok(());
+        }
+
+        self.throw_error(VynError::UseBeforeAssignment {
+            name: name.to_string(),
+            declaration_span: symbol.span,
+            span,
+        });
+        Err(())
+    }
+
+    pub(crate) fn assignment_state(&self) -> AssignmentState {
+        self.uninitialized
+            .iter()
+            .map(|usage| usage.unassigned.get())
+            .collect()
+    }
+
+    /*
+     * Goes back to an earlier state, variables declared since then are out
+     * of scope and keep theirs
+     * */
+    pub(crate) fn restore_assignment_state(&mut self, state: &AssignmentState) {
+        for (usage, unassigned) in self.uninitialized.iter().zip(state) {
+            usage.unassigned.set(*unassigned);
+        }
+    }
+
+    /*
+     * Merges the states of paths that meet again, a variable stays
+     * unassigned if any of them left it unassigned
+     *
+     * -- Arguments: [&mut self], paths - state at the end of each path that
+     *               falls through, when none does the current one is kept
+     * -- Return value: void
+     * */
+    pub(crate) fn join_assignment_states(&mut self, paths: &[AssignmentState]) {
+        let Some((first, rest)) = paths.split_first() else {
+            return;
+        };
+
+        let mut joined = first.clone();
+        for path in rest {
+            for (unassigned, other) in joined.iter_mut().zip(path) {
+                *unassigned |= *other;
+            }
+        }
+
+        self.restore_assignment_state(&joined);
+    }
+
+    /*
+     * Checks the body of a loop
+     *
+     * The state at every `break` is recorded. A loop only left through
+     * `break` continues with their join, any other loop may run zero times
+     * and continues with the state it started with
+     *
+     * -- Arguments: [&mut self], exits_by_break - `loop` and `for when true`,
+     *               check - checks the body
+     * -- Return value: the body's result
+     * */
+    pub(crate) fn check_loop_body(
+        &mut self,
+        exits_by_break: bool,
+        check: impl FnOnce(&mut Self) -> Result<(), ()>,
+    ) -> Result<(), ()> {
+        let before = self.assignment_state();
+
+        self.break_states.push(Vec::new());
+        let result = check(self);
+        let breaks = self.break_states.pop().unwrap_or_default();
+
+        if exits_by_break {
+            self.join_assignment_states(&breaks);
+        } else {
+            self.restore_assignment_state(&before);
+        }
+
+        result
+    }
+
+    pub(crate) fn record_break_state(&mut self) {
+        let state = self.assignment_state();
+        if let Some(breaks) = self.break_states.last_mut() {
+            breaks.push(state);
+        }
+    }
+
+    /*
+     * Runs a check in which only globals are visible, like a function body
+     *
+     * A function can be called after any assignment to a global, so every
+     * global counts as assigned inside it
+     * */
+    pub(crate) fn with_globals_assigned<T>(&mut self, check: impl FnOnce(&mut Self) -> T) -> T {
+        let outer_state = self.assignment_state();
+        let outer_breaks = mem::take(&mut self.break_states);
+
+        self.restore_assignment_state(&vec![false; outer_state.len()]);
+        let result = check(self);
+
+        self.restore_assignment_state(&outer_state);
+        self.break_states = outer_breaks;
+        result
+    }
+
+    /*
+     * Checks an optional branch, like the right side of `and` / `or`,
+     * whose assignments can't be relied on afterwards
+     * */
+    pub(crate) fn check_conditional_expr<T>(
+        &mut self,
+        check: impl FnOnce(&mut Self) -> Result<T, ()>,
+    ) -> Result<T, ()> {
+        let before = self.assignment_state();
+        let result = check(self);
+        self.restore_assignment_state(&before);
+        result
+    }
+
+    /*
+     * -- Return value: Some(value) of a condition made only of literals and
+     *                  statics, None if it depends on a runtime value
+     * */
+    pub(crate) fn static_condition(&self, condition: &Expression) -> Option<bool> {
+        if !self.is_value_static(condition, None) {
+            return None;
+        }
+
+        let mut errors = ErrorCollector::new();
+        self.static_eval
+            .evaluate_static_expr(condition, &mut errors)
+            .ok()?
+            .as_bool()
+    }
+}
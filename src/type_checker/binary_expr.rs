@@ -2,7 +2,10 @@ use crate::{
     ast::ast::Expression,
     error_handler::errors::VynError,
     tokens::{Token, TokenType},
-    type_checker::type_checker::{Type, TypeChecker},
+    type_checker::{
+        optional::NilChecks,
+        type_checker::{Type, TypeChecker},
+    },
 };
 
 impl TypeChecker<'_> {
@@ -14,9 +17,48 @@ impl TypeChecker<'_> {
         span: crate::utils::Span,
     ) -> Result<Type, ()> {
         let left_type = self.check_expression(left, None)?;
-        let right_type = self.check_expression(right, None)?;
+
+        // The right operand of `and` / `or` only runs when the left one did
+        // not decide the result, so nil checks on the left narrow the right
+        let nil_checks = NilChecks::from_condition(left);
+        let narrowed = match operator.get_token_type() {
+            TokenType::And => nil_checks.when_true,
+            TokenType::Or => nil_checks.when_false,
+            _ => Vec::new(),
+        };
+        let right_type = match operator.get_token_type() {
+            // Assignments on the right side may never happen
+            TokenType::And | TokenType::Or => self.check_conditional_expr(|checker| {
+                checker.check_narrowed(&narrowed, |checker| checker.check_expression(right, None))
+            })?,
+            _ => self.check_narrowed(&narrowed, |checker| checker.check_expression(right, None))?,
+        };
 
         match operator.get_token_type() {
+            // Optionals compare against nil and against values of their
+            // inner type
+            TokenType::Equal | TokenType::NotEqual
+                if is_nil_comparison(&left_type, &right_type) =>
+            {
+                Ok(Type::Bool)
+            }
+            TokenType::Plus
+            | TokenType::Minus
+            | TokenType::Asterisk
+            | TokenType::Slash
+            | TokenType::Caret
+            | TokenType::GreaterThan
+            | TokenType::GreaterThanEqual
+            | TokenType::LessThan
+            | TokenType::LessThanEqual
+            | TokenType::And
+            | TokenType::Or
+                if matches!(left_type, Type::Optional(_))
+                    || matches!(right_type, Type::Optional(_)) =>
+            {
+                self.expect_non_nil(left_type, left)?;
+                self.expect_non_nil(right_type, right)
+            }
             TokenType::Plus
             | TokenType::Minus
             | TokenType::Asterisk
@@ -31,7 +73,9 @@ impl TypeChecker<'_> {
                     return Err(());
                 }
 
-                if left_type != Type::Integer && left_type != Type::Float {
+                // `+` also joins strings
+                let is_concat = left_type == Type::String && matches!(operator, Token::Plus);
+                if left_type != Type::Integer && left_type != Type::Float && !is_concat {
                     self.throw_error(VynError::TypeMismatch {
                         expected: vec![Type::Integer, Type::Float],
                         found: left_type,
@@ -42,9 +86,18 @@ impl TypeChecker<'_> {
 
                 Ok(left_type)
             }
-            TokenType::Equal
-            | TokenType::NotEqual
-            | TokenType::GreaterThan
+            TokenType::Equal | TokenType::NotEqual => {
+                if left_type != right_type {
+                    self.throw_error(VynError::TypeMismatch {
+                        expected: vec![left_type.clone()],
+                        found: right_type,
+                        span,
+                    });
+                    return Err(());
+                }
+                Ok(Type::Bool)
+            }
+            TokenType::GreaterThan
             | TokenType::GreaterThanEqual
             | TokenType::LessThan
             | TokenType::LessThanEqual => {
@@ -56,6 +109,16 @@ impl TypeChecker<'_> {
                     });
                     return Err(());
                 }
+
+                // Strings are ordered by their characters, like a dictionary
+                if !matches!(left_type, Type::Integer | Type::Float | Type::String) {
+                    self.throw_error(VynError::TypeMismatch {
+                        expected: vec![Type::Integer, Type::Float, Type::String],
+                        found: left_type,
+                        span,
+                    });
+                    return Err(());
+                }
                 Ok(Type::Bool)
             }
             TokenType::And | TokenType::Or => {
@@ -83,3 +146,15 @@ impl TypeChecker<'_> {
         }
     }
 }
+
+/*
+ * Checks for `==` / `!=` between an optional and nil, or between an
+ * optional and a value of its inner type
+ * */
+fn is_nil_comparison(left: &Type, right: &Type) -> bool {
+    match (left, right) {
+        (Type::Optional(_), Type::Nil) | (Type::Nil, Type::Optional(_)) => true,
+        (Type::Optional(inner), other) | (other, Type::Optional(inner)) => **inner == *other,
+        _ => false,
+    }
+}
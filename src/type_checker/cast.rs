@@ -0,0 +1,37 @@
+use crate::{
+    ast::{ast::Expression, type_annotation::TypeAnnotation},
+    error_handler::errors::VynError,
+    type_checker::type_checker::{Type, TypeChecker},
+    utils::Span,
+};
+
+impl TypeChecker<'_> {
+    /*
+     * Checks an explicit 'value as Type' conversion
+     *
+     * Only numeric conversions are allowed, casting a value to its own
+     * type is accepted and compiles to nothing
+     *
+     * -- Arguments: [&mut self, value - converted expression,
+     *                target - annotated target type, span - whole cast]
+     * -- Return value: Result<Type, ()> - the target type
+     * */
+    pub(crate) fn check_cast(
+        &mut self,
+        value: &Expression,
+        target: &TypeAnnotation,
+        span: Span,
+    ) -> Result<Type, ()> {
+        let from = self.check_expression(value, None)?;
+        let from = self.expect_non_nil(from, value)?;
+        let to = Type::from_anotated_type(target, self.static_eval, &mut self.errors);
+
+        let is_numeric = |t: &Type| matches!(t, Type::Integer | Type::Float);
+        if !is_numeric(&from) || !is_numeric(&to) {
+            self.throw_error(VynError::InvalidCast { from, to, span });
+            return Err(());
+        }
+
+        Ok(to)
+    }
+}
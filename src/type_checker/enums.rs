@@ -0,0 +1,383 @@
+use std::{collections::HashSet, mem};
+
+use crate::{
+    ast::{
+        ast::{EnumVariant, Expression, MatchArm, MatchExprArm, Pattern},
+        type_annotation::TypeAnnotation,
+    },
+    error_handler::errors::VynError,
+    type_checker::{
+        symbol_type_table::SymbolTypeTable,
+        type_checker::{Type, TypeChecker},
+    },
+    utils::{Span, Spanned},
+};
+
+// Tags are encoded in a single byte
+const MAX_ENUM_VARIANTS: usize = u8::MAX as usize + 1;
+// Payload slots are encoded in a single byte
+const MAX_PAYLOAD_SIZE: usize = u8::MAX as usize;
+
+impl TypeChecker<'_> {
+    /*
+     * Checks an enum declaration
+     *
+     * The parser already registered the enum, this only validates its
+     * variant list
+     *
+     * -- Arguments: [&mut self, identifier - enum name,
+     *                variants - declared variants, span - whole declaration]
+     * -- Return value: Result<(), ()>
+     * */
+    pub(crate) fn check_enum_decl(
+        &mut self,
+        identifier: &Expression,
+        variants: &[EnumVariant],
+        span: Span,
+    ) -> Result<(), ()> {
+        if variants.len() > MAX_ENUM_VARIANTS {
+            self.throw_error(VynError::TooManyVariants {
+                name: identifier.node.to_string(),
+                span,
+            });
+            return Err(());
+        }
+
+        let mut seen = HashSet::new();
+        for variant in variants {
+            let name = variant.identifier.node.to_string();
+
+            if !seen.insert(name.clone()) {
+                self.throw_error(VynError::DuplicateVariant {
+                    name,
+                    span: variant.identifier.span,
+                });
+                return Err(());
+            }
+
+            if variant.payload.len() > MAX_PAYLOAD_SIZE {
+                self.throw_error(VynError::TooManyFields {
+                    name: format!("{}::{}", identifier.node, name),
+                    span: variant.identifier.span,
+                });
+                return Err(());
+            }
+
+            // Surfaces errors in the payload types, such as a bad array size
+            for payload_type in &variant.payload {
+                Type::from_anotated_type(payload_type, self.static_eval, &mut self.errors);
+            }
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Checks a `Name::Variant(values)` expression
+     *
+     * -- Arguments: [&mut self, enum_type - annotated enum type,
+     *                variant - variant name, arguments - payload values,
+     *                span - whole expression]
+     * -- Return value: Result<Type, ()> - the enum type
+     * */
+    pub(crate) fn check_enum_variant(
+        &mut self,
+        enum_type: &TypeAnnotation,
+        variant: &Expression,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Result<Type, ()> {
+        let enum_type = Type::from_anotated_type(enum_type, self.static_eval, &mut self.errors);
+        let variant_name = variant.node.to_string();
+
+        let Some((_, payload)) = enum_type.variant(&variant_name) else {
+            self.throw_error(VynError::UnknownVariant {
+                target: enum_type.clone(),
+                variant: variant_name,
+                span: variant.span,
+            });
+            return Err(());
+        };
+        let payload = payload.to_vec();
+
+        if arguments.len() != payload.len() {
+            self.throw_error(VynError::PayloadCountMismatch {
+                variant: format!("{enum_type}::{variant_name}"),
+                expected: payload.len(),
+                got: arguments.len(),
+                span,
+            });
+            return Err(());
+        }
+
+        for (argument, payload_type) in arguments.iter().zip(payload) {
+            let argument_type = self.check_expression(argument, Some(payload_type.clone()))?;
+
+            if argument_type != payload_type {
+                self.throw_error(VynError::TypeMismatch {
+                    expected: vec![payload_type],
+                    found: argument_type,
+                    span: argument.span,
+                });
+                return Err(());
+            }
+        }
+
+        Ok(enum_type)
+    }
+
+    /*
+     * Checks a `match` statement
+     *
+     * Every variant of the subject's enum must be covered by an arm or a
+     * trailing `_` arm
+     *
+     * -- Arguments: [&mut self, subject - matched value, arms - match arms,
+     *                span - `match` keyword up to the subject]
+     * -- Return value: Result<(), ()>
+     * */
+    pub(crate) fn check_match_stmt(
+        &mut self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        span: Span,
+    ) -> Result<(), ()> {
+        let subject_type = self.check_match_subject(subject)?;
+        let mut covered = Vec::new();
+        let before = self.assignment_state();
+        // States at the end of the arms that fall through
+        let mut paths = Vec::new();
+
+        for arm in arms {
+            let bindings = self.check_pattern(&subject_type, &arm.pattern, &mut covered)?;
+
+            // Errors inside one arm don't stop the others from being checked
+            self.restore_assignment_state(&before);
+            let _ = self.check_in_arm_scope(bindings, |checker| checker.check_statement(&arm.body));
+
+            if !Self::always_jumps(&arm.body) {
+                paths.push(self.assignment_state());
+            }
+        }
+        self.join_assignment_states(&paths);
+
+        self.check_exhaustive(&subject_type, &covered, span)
+    }
+
+    /*
+     * Checks a `match` expression
+     *
+     * Like a match statement, and every arm must produce a value of the
+     * same type
+     *
+     * -- Arguments: [&mut self, subject - matched value, arms - match arms,
+     *                expected_type - type required by the context, if any,
+     *                span - whole expression]
+     * -- Return value: Result<Type, ()> - the type of the arms
+     * */
+    pub(crate) fn check_match_expr(
+        &mut self,
+        subject: &Expression,
+        arms: &[MatchExprArm],
+        expected_type: Option<Type>,
+        span: Span,
+    ) -> Result<Type, ()> {
+        let subject_type = self.check_match_subject(subject)?;
+        let mut covered = Vec::new();
+        let mut result_type = expected_type;
+        let before = self.assignment_state();
+        let mut paths = Vec::new();
+
+        for arm in arms {
+            let bindings = self.check_pattern(&subject_type, &arm.pattern, &mut covered)?;
+
+            self.restore_assignment_state(&before);
+            let value_type = self.check_in_arm_scope(bindings, |checker| {
+                checker.check_expression(&arm.value, result_type.clone())
+            })?;
+            paths.push(self.assignment_state());
+
+            match &result_type {
+                Some(expected) if *expected != value_type => {
+                    self.throw_error(VynError::TypeMismatch {
+                        expected: vec![expected.clone()],
+                        found: value_type,
+                        span: arm.value.span,
+                    });
+                    return Err(());
+                }
+                Some(_) => {}
+                None => result_type = Some(value_type),
+            }
+        }
+
+        self.join_assignment_states(&paths);
+        self.check_exhaustive(&subject_type, &covered, subject.span)?;
+
+        let Some(result_type) = result_type else {
+            self.throw_error(VynError::TypeInfer {
+                expr: subject.node.clone(),
+                span,
+            });
+            return Err(());
+        };
+
+        Ok(result_type)
+    }
+
+    fn check_match_subject(&mut self, subject: &Expression) -> Result<Type, ()> {
+        let subject_type = self.check_expression(subject, None)?;
+        let subject_type = self.expect_non_nil(subject_type, subject)?;
+
+        if !matches!(subject_type, Type::Enum { .. }) {
+            self.throw_error(VynError::InvalidMatchTarget {
+                target: subject_type,
+                span: subject.span,
+            });
+            return Err(());
+        }
+
+        Ok(subject_type)
+    }
+
+    /*
+     * Checks an arm pattern against the matched type
+     *
+     * -- Arguments: [&mut self, subject_type - enum being matched,
+     *                pattern - arm pattern,
+     *                covered - tags handled by earlier arms, updated here]
+     * -- Return value: Result<Vec<(name, type, span)>, ()> - variables the
+     *                  pattern binds, `_` bindings excluded
+     * */
+    fn check_pattern(
+        &mut self,
+        subject_type: &Type,
+        pattern: &Spanned<Pattern>,
+        covered: &mut Vec<bool>,
+    ) -> Result<Vec<(String, Type, Span)>, ()> {
+        let Type::Enum { variants, .. } = subject_type else {
+            unreachable!("Match subjects are checked to be enums");
+        };
+        covered.resize(variants.len(), false);
+
+        match &pattern.node {
+            Pattern::Wildcard => {
+                if covered.iter().all(|c| *c) {
+                    self.throw_error(VynError::UnreachableMatchArm { span: pattern.span });
+                    return Err(());
+                }
+
+                covered.fill(true);
+                Ok(Vec::new())
+            }
+
+            Pattern::Variant {
+                enum_type,
+                variant,
+                bindings,
+            } => {
+                let pattern_type =
+                    Type::from_anotated_type(enum_type, self.static_eval, &mut self.errors);
+
+                if pattern_type != *subject_type {
+                    self.throw_error(VynError::TypeMismatch {
+                        expected: vec![subject_type.clone()],
+                        found: pattern_type,
+                        span: pattern.span,
+                    });
+                    return Err(());
+                }
+
+                let variant_name = variant.node.to_string();
+                let Some((tag, payload)) = subject_type.variant(&variant_name) else {
+                    self.throw_error(VynError::UnknownVariant {
+                        target: subject_type.clone(),
+                        variant: variant_name,
+                        span: variant.span,
+                    });
+                    return Err(());
+                };
+
+                if bindings.len() != payload.len() {
+                    self.throw_error(VynError::PayloadCountMismatch {
+                        variant: format!("{subject_type}::{variant_name}"),
+                        expected: payload.len(),
+                        got: bindings.len(),
+                        span: pattern.span,
+                    });
+                    return Err(());
+                }
+
+                if covered[tag] {
+                    self.throw_error(VynError::UnreachableMatchArm { span: pattern.span });
+                    return Err(());
+                }
+                covered[tag] = true;
+
+                Ok(bindings
+                    .iter()
+                    .zip(payload)
+                    .filter(|(binding, _)| binding.node.to_string() != "_")
+                    .map(|(binding, payload_type)| {
+                        (binding.node.to_string(), payload_type.clone(), binding.span)
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    fn check_exhaustive(
+        &mut self,
+        subject_type: &Type,
+        covered: &[bool],
+        span: Span,
+    ) -> Result<(), ()> {
+        let Type::Enum { variants, .. } = subject_type else {
+            unreachable!("Match subjects are checked to be enums");
+        };
+
+        let missing: Vec<String> = variants
+            .iter()
+            .enumerate()
+            .filter(|(tag, _)| !covered.get(*tag).copied().unwrap_or(false))
+            .map(|(_, (name, _))| name.clone())
+            .collect();
+
+        if !missing.is_empty() {
+            self.throw_error(VynError::NonExhaustiveMatch {
+                target: subject_type.clone(),
+                missing,
+                span,
+            });
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Runs a check inside a new scope holding an arm's bindings
+     *
+     * Bindings are immutable, like function parameters without '@'
+     * */
+    fn check_in_arm_scope<T>(
+        &mut self,
+        bindings: Vec<(String, Type, Span)>,
+        check: impl FnOnce(&mut Self) -> Result<T, ()>,
+    ) -> Result<T, ()> {
+        let parent_table = mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new());
+        self.symbol_type_table = parent_table.enter_scope();
+
+        let mut result = Ok(());
+        for (name, binding_type, span) in bindings {
+            result = result.and(self.declare_variable(name, span, binding_type, span, false));
+        }
+
+        let result = result.and_then(|_| check(self));
+
+        self.symbol_type_table =
+            mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new()).exit_scope();
+
+        result
+    }
+}
@@ -0,0 +1,325 @@
+use std::mem;
+
+use crate::{
+    ast::{
+        ast::{Expr, Expression, FunctionParameter, Statement, Stmt},
+        type_annotation::TypeAnnotation,
+    },
+    error_handler::errors::VynError,
+    type_checker::type_checker::{Type, TypeChecker},
+    utils::Span,
+    vyn_vm::natives,
+};
+
+impl TypeChecker<'_> {
+    /*
+     * Declares a top-level function's signature before any body is checked,
+     * so functions can call each other regardless of declaration order
+     *
+     * -- Arguments: [&mut self], stmt - any top-level statement
+     * -- Return value: Err(()) if the function name is already taken
+     * */
+    pub(crate) fn declare_function(&mut self, stmt: &Statement) -> Result<(), ()> {
+        let Stmt::FunctionDeclaration {
+            identifier,
+            parameters,
+            return_type,
+            ..
+        } = &stmt.node
+        else {
+            return Ok(());
+        };
+
+        let name = match &identifier.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => unreachable!("Function name must be an identifier"),
+        };
+
+        let function_type = self.function_type(parameters, return_type.as_ref());
+
+        self.symbol_type_table.declare_identifier(
+            name,
+            function_type,
+            stmt.span,
+            false,
+            &mut self.errors,
+        )
+    }
+
+    /*
+     * Checks a function body against its signature
+     *
+     * The body gets its own symbol table (see `enter_function_scope`), a fresh
+     * loop depth and the declared return type for `return` statements.
+     *
+     * -- Arguments: [&mut self], stmt - the Stmt::FunctionDeclaration
+     * -- Return value: Err(()) if the body has errors or misses a return
+     * */
+    pub(crate) fn check_function_decl(&mut self, stmt: &Statement) -> Result<(), ()> {
+        let Stmt::FunctionDeclaration {
+            identifier,
+            parameters,
+            return_type,
+            body,
+        } = &stmt.node
+        else {
+            unreachable!()
+        };
+
+        let name = match &identifier.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => unreachable!("Function name must be an identifier"),
+        };
+
+        let expected_return = match return_type {
+            Some(an_type) => Type::from_anotated_type(an_type, self.static_eval, &mut self.errors),
+            None => Type::Nil,
+        };
+
+        let function_table = self.symbol_type_table.enter_function_scope();
+        let outer_table = mem::replace(&mut self.symbol_type_table, function_table);
+        let outer_loop_depth = mem::replace(&mut self.loop_depth, 0);
+        let outer_return_type = self.return_type.replace(expected_return.clone());
+
+        let result =
+            self.with_globals_assigned(|checker| checker.check_function_body(parameters, body));
+
+        self.symbol_type_table = outer_table;
+        self.loop_depth = outer_loop_depth;
+        self.return_type = outer_return_type;
+
+        result?;
+
+        if expected_return != Type::Nil && !Self::always_returns(body) {
+            self.throw_error(VynError::MissingReturn {
+                name,
+                expected: expected_return,
+                span: stmt.span,
+            });
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    fn check_function_body(
+        &mut self,
+        parameters: &[FunctionParameter],
+        body: &Statement,
+    ) -> Result<(), ()> {
+        for param in parameters {
+            let param_name = match &param.identifier.node {
+                Expr::Identifier(n) => n.clone(),
+                _ => unreachable!("Parameter name must be an identifier"),
+            };
+
+            let param_type =
+                Type::from_anotated_type(&param.annotated_type, self.static_eval, &mut self.errors);
+
+            self.declare_variable(
+                param_name,
+                param.identifier.span,
+                param_type,
+                param.identifier.span,
+                param.mutable,
+            )?;
+        }
+
+        self.check_statement(body)
+    }
+
+    pub(crate) fn check_return(
+        &mut self,
+        value: Option<&Expression>,
+        span: Span,
+    ) -> Result<(), ()> {
+        let Some(expected) = self.return_type.clone() else {
+            self.throw_error(VynError::ReturnOutsideFunction { span });
+            return Err(());
+        };
+
+        let found = match value {
+            Some(expr) => self.check_expression(expr, Some(expected.clone()))?,
+            // A bare `return` gives nil, which any optional accepts
+            None if matches!(expected, Type::Optional(_)) => expected.clone(),
+            None => Type::Nil,
+        };
+
+        if found != expected {
+            self.throw_error(VynError::TypeMismatch {
+                expected: vec![expected],
+                found,
+                span: value.map(|v| v.span).unwrap_or(span),
+            });
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_call(
+        &mut self,
+        callee: &Expression,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Result<Type, ()> {
+        let name = match &callee.node {
+            Expr::Identifier(n) => n.clone(),
+            _ => {
+                let found = self.check_expression(callee, None)?;
+                self.throw_error(VynError::NotCallable {
+                    found,
+                    span: callee.span,
+                });
+                return Err(());
+            }
+        };
+
+        // Declarations shadow natives of the same name
+        if !self.symbol_type_table.is_declared(&name) && natives::is_native(&name) {
+            return self.check_native_call(&name, arguments, span);
+        }
+
+        let symbol = self
+            .symbol_type_table
+            .resolve_identifier(&name, callee.span, &mut self.errors)?;
+
+        let (parameters, return_type) = match symbol.symbol_type.clone() {
+            Type::Function {
+                parameters,
+                return_type,
+            } => (parameters, return_type),
+            found => {
+                self.throw_error(VynError::NotCallable {
+                    found,
+                    span: callee.span,
+                });
+                return Err(());
+            }
+        };
+
+        if arguments.len() != parameters.len() {
+            self.throw_error(VynError::ArgumentCountMismatch {
+                name,
+                expected: parameters.len(),
+                got: arguments.len(),
+                span,
+            });
+            return Err(());
+        }
+
+        for (arg, param_type) in arguments.iter().zip(parameters) {
+            let arg_type = self.check_expression(arg, Some(param_type.clone()))?;
+
+            if arg_type != param_type {
+                self.throw_error(VynError::TypeMismatch {
+                    expected: vec![param_type],
+                    found: arg_type,
+                    span: arg.span,
+                });
+                return Err(());
+            }
+        }
+
+        Ok(*return_type)
+    }
+
+    fn function_type(
+        &mut self,
+        parameters: &[FunctionParameter],
+        return_type: Option<&TypeAnnotation>,
+    ) -> Type {
+        let parameters = parameters
+            .iter()
+            .map(|p| Type::from_anotated_type(&p.annotated_type, self.static_eval, &mut self.errors))
+            .collect();
+
+        let return_type = match return_type {
+            Some(an_type) => Type::from_anotated_type(an_type, self.static_eval, &mut self.errors),
+            None => Type::Nil,
+        };
+
+        Type::Function {
+            parameters,
+            return_type: Box::new(return_type),
+        }
+    }
+
+    /*
+     * Checks whether every path through a statement leaves the enclosing
+     * block, through `return`, `break` or `continue`
+     * */
+    pub(crate) fn always_jumps(stmt: &Statement) -> bool {
+        match &stmt.node {
+            Stmt::Return { .. } | Stmt::Break | Stmt::Continue => true,
+            Stmt::Scope { statements } | Stmt::Block { statements } => {
+                statements.iter().any(Self::always_jumps)
+            }
+            Stmt::IfDeclaration {
+                consequence,
+                alternate,
+                ..
+            } => {
+                Self::always_jumps(consequence)
+                    && alternate.as_ref().as_ref().is_some_and(Self::always_jumps)
+            }
+            Stmt::Match { arms, .. } => {
+                !arms.is_empty() && arms.iter().all(|arm| Self::always_jumps(&arm.body))
+            }
+            Stmt::Loop { body } => !Self::contains_break(body),
+            _ => false,
+        }
+    }
+
+    /*
+     * Checks whether every path through a statement ends in `return`
+     *
+     * -- Notes:
+     * # An `if` only returns if both branches do
+     * # A `match` only returns if every arm does
+     * # A `loop` without `break` never falls through
+     * */
+    fn always_returns(stmt: &Statement) -> bool {
+        match &stmt.node {
+            Stmt::Return { .. } => true,
+            Stmt::Scope { statements } | Stmt::Block { statements } => {
+                statements.iter().any(Self::always_returns)
+            }
+            Stmt::IfDeclaration {
+                consequence,
+                alternate,
+                ..
+            } => {
+                Self::always_returns(consequence)
+                    && alternate.as_ref().as_ref().is_some_and(Self::always_returns)
+            }
+            Stmt::Match { arms, .. } => {
+                !arms.is_empty() && arms.iter().all(|arm| Self::always_returns(&arm.body))
+            }
+            Stmt::Loop { body } => !Self::contains_break(body),
+            _ => false,
+        }
+    }
+
+    /*
+     * Checks for a `break` that exits the current loop (nested loops are skipped)
+     * */
+    fn contains_break(stmt: &Statement) -> bool {
+        match &stmt.node {
+            Stmt::Break => true,
+            Stmt::Scope { statements } | Stmt::Block { statements } => {
+                statements.iter().any(Self::contains_break)
+            }
+            Stmt::IfDeclaration {
+                consequence,
+                alternate,
+                ..
+            } => {
+                Self::contains_break(consequence)
+                    || alternate.as_ref().as_ref().is_some_and(Self::contains_break)
+            }
+            Stmt::Match { arms, .. } => arms.iter().any(|arm| Self::contains_break(&arm.body)),
+            _ => false,
+        }
+    }
+}
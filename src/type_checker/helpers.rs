@@ -1,7 +1,10 @@
 use crate::{
     ast::ast::{Expr, Expression},
     error_handler::error_collector::ErrorCollector,
-    type_checker::type_checker::{Type, TypeChecker},
+    type_checker::{
+        static_evaluator::StaticValue,
+        type_checker::{Type, TypeChecker},
+    },
 };
 
 impl TypeChecker<'_> {
@@ -58,4 +61,25 @@ impl TypeChecker<'_> {
             _ => false,
         }
     }
+
+    /*
+     * Checks whether an index loop step is known to be 0 at compile time,
+     * steps only known at runtime are checked by the VM
+     * */
+    pub(crate) fn is_static_zero(&self, expr: &Expression) -> bool {
+        if !self.is_value_static(expr, None) {
+            return false;
+        }
+
+        let mut temp_errors = ErrorCollector::new();
+        let value = self
+            .static_eval
+            .evaluate_static_expr(expr, &mut temp_errors);
+
+        match value {
+            Ok(StaticValue::Int(n)) => n == 0,
+            Ok(StaticValue::Float(f)) => f == 0.0,
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use crate::{
+    ast::ast::{Statement, Stmt},
+    error_handler::warnings::VynWarning,
+    type_checker::{
+        symbol_type_table::SymbolType,
+        type_checker::{Type, TypeChecker},
+    },
+    utils::Span,
+};
+
+/*
+ * A variable the unused lints look at once the whole program is checked
+ * */
+pub(crate) struct TrackedVariable {
+    name: String,
+    // The identifier, warnings point here rather than at the whole statement
+    span: Span,
+    symbol: SymbolType,
+}
+
+impl TypeChecker<'_> {
+    /*
+     * Declares a variable, parameter or match binding in the current scope
+     *
+     * Warns when it shadows a variable from an enclosing scope and keeps
+     * track of it for the unused lints
+     *
+     * -- Arguments: [&mut self, name - variable name, ident_span - its
+     *                identifier, var_type - declared type, span - the whole
+     *                declaration, mutable - declared with '@']
+     * -- Return value: Err(()) if the name is already taken in this scope
+     * */
+    pub(crate) fn declare_variable(
+        &mut self,
+        name: String,
+        ident_span: Span,
+        var_type: Type,
+        span: Span,
+        mutable: bool,
+    ) -> Result<(), ()> {
+        let shadowed = self.symbol_type_table.shadowed_span(&name);
+
+        self.symbol_type_table.declare_identifier(
+            name.clone(),
+            var_type,
+            span,
+            mutable,
+            &mut self.errors,
+        )?;
+
+        if let Some(original_span) = shadowed {
+            self.warn(VynWarning::Shadowing {
+                name: name.clone(),
+                original_span,
+                span: ident_span,
+            });
+        }
+
+        let Ok(symbol) = self
+            .symbol_type_table
+            .resolve_identifier(&name, span, &mut self.errors)
+        else {
+            return Err(());
+        };
+
+        self.tracked_variables.push(TrackedVariable {
+            symbol: symbol.clone(),
+            name,
+            span: ident_span,
+        });
+        Ok(())
+    }
+
+    /*
+     * Warns about variables never read and '@' variables never assigned
+     *
+     * Names starting with '_' are meant to be unused and are skipped by the
+     * first check, heap values by the second
+     * */
+    pub(crate) fn check_unused_variables(&mut self) {
+        for variable in std::mem::take(&mut self.tracked_variables) {
+            let usage = &variable.symbol.usage;
+
+            if !usage.read.get() && !variable.name.starts_with('_') {
+                self.warn(VynWarning::UnusedVariable {
+                    name: variable.name.clone(),
+                    span: variable.span,
+                });
+            }
+
+            // A shared value can be changed through another '@' name, which
+            // needs the value to be mutable here as well
+            if variable.symbol.mutable
+                && !usage.assigned.get()
+                && !variable.symbol.symbol_type.is_heap_value()
+            {
+                self.warn(VynWarning::UnusedMut {
+                    name: variable.name,
+                    span: variable.span,
+                });
+            }
+        }
+    }
+
+    /*
+     * Checks the statements of a block in order
+     *
+     * The first statement after one that always jumps away is reported as
+     * unreachable, later ones would only repeat the warning
+     * */
+    pub(crate) fn check_block(&mut self, statements: &[Statement]) {
+        let mut jumped_by: Option<&str> = None;
+        let mut reported = false;
+
+        for stmt in statements {
+            if let Some(after) = jumped_by
+                && !reported
+            {
+                self.warn(VynWarning::UnreachableCode {
+                    after: after.to_string(),
+                    span: stmt.span,
+                });
+                reported = true;
+            }
+
+            let _ = self.check_statement(stmt);
+
+            if jumped_by.is_none() {
+                jumped_by = Self::jump_keyword(stmt);
+            }
+        }
+    }
+
+    /*
+     * -- Return value: Some(keyword) of the statement if control never
+     *                  reaches the one after it (see `always_jumps`)
+     * */
+    fn jump_keyword(stmt: &Statement) -> Option<&'static str> {
+        if !Self::always_jumps(stmt) {
+            return None;
+        }
+
+        let keyword = match &stmt.node {
+            Stmt::Break => "break",
+            Stmt::Continue => "continue",
+            Stmt::Return { .. } => "return",
+            Stmt::IfDeclaration { .. } => "if",
+            Stmt::Match { .. } => "match",
+            Stmt::Loop { .. } => "loop",
+            _ => "block",
+        };
+        Some(keyword)
+    }
+
+    pub(crate) fn warn(&mut self, warning: VynWarning) {
+        self.errors.add_warning(warning);
+    }
+}
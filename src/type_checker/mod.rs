@@ -1,6 +1,15 @@
+pub mod assignment;
 pub mod binary_expr;
+pub mod cast;
+pub mod enums;
+pub mod function;
 pub mod helpers;
+pub mod lints;
+pub mod natives;
+pub mod optional;
 pub mod static_evaluator;
+pub mod strings;
+pub mod structs;
 pub mod symbol_type_table;
 pub mod type_checker;
 pub mod unary;
@@ -0,0 +1,82 @@
+use crate::{
+    ast::ast::Expression,
+    error_handler::errors::VynError,
+    type_checker::type_checker::{Type, TypeChecker},
+    utils::Span,
+    vyn_vm::natives,
+};
+
+impl TypeChecker<'_> {
+    /*
+     * Checks a call to a native function
+     *
+     * A name with a single entry is checked like a declared function. With
+     * several entries, the argument types pick the one that is called
+     *
+     * -- Arguments: [&mut self, name - native's name,
+     *                arguments - call arguments, span - whole call]
+     * -- Return value: Result<Type, ()> - the chosen entry's return type
+     * */
+    pub(crate) fn check_native_call(
+        &mut self,
+        name: &str,
+        arguments: &[Box<Expression>],
+        span: Span,
+    ) -> Result<Type, ()> {
+        let candidates = natives::overloads(name);
+
+        if candidates
+            .iter()
+            .all(|c| c.parameters.len() != arguments.len())
+        {
+            let expected = candidates[0].parameters.len();
+
+            if candidates.iter().all(|c| c.parameters.len() == expected) {
+                self.throw_error(VynError::ArgumentCountMismatch {
+                    name: name.to_string(),
+                    expected,
+                    got: arguments.len(),
+                    span,
+                });
+                return Err(());
+            }
+        }
+
+        let single = match candidates.as_slice() {
+            [native] => Some(native),
+            _ => None,
+        };
+
+        let mut found = Vec::with_capacity(arguments.len());
+        for (i, arg) in arguments.iter().enumerate() {
+            let expected = single.map(|native| native.parameters[i].clone());
+            let arg_type = self.check_expression(arg, expected.clone())?;
+            let arg_type = self.expect_non_nil(arg_type, arg)?;
+
+            if let Some(expected) = expected
+                && arg_type != expected
+            {
+                self.throw_error(VynError::TypeMismatch {
+                    expected: vec![expected],
+                    found: arg_type,
+                    span: arg.span,
+                });
+                return Err(());
+            }
+            found.push(arg_type);
+        }
+
+        match natives::resolve(name, &found) {
+            Some((_, native)) => Ok(native.return_type.clone()),
+            None => {
+                self.throw_error(VynError::NoMatchingOverload {
+                    name: name.to_string(),
+                    found,
+                    candidates: candidates.iter().map(|c| c.signature()).collect(),
+                    span,
+                });
+                Err(())
+            }
+        }
+    }
+}
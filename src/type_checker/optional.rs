@@ -0,0 +1,159 @@
+use std::mem;
+
+use crate::{
+    ast::ast::{Expr, Expression},
+    error_handler::errors::VynError,
+    tokens::Token,
+    type_checker::{
+        symbol_type_table::SymbolTypeTable,
+        type_checker::{Type, TypeChecker},
+    },
+};
+
+/*
+ * Variables a condition proves to be non-nil
+ *
+ * `x != nil` proves `x` when it holds, `x == nil` when it fails. `and`,
+ * `or` and `not` combine the checks of their operands
+ * */
+#[derive(Debug, Default)]
+pub struct NilChecks {
+    pub when_true: Vec<String>,
+    pub when_false: Vec<String>,
+}
+
+impl NilChecks {
+    pub fn from_condition(condition: &Expression) -> Self {
+        match &condition.node {
+            Expr::BinaryOperation {
+                left,
+                operator: operator @ (Token::Equal | Token::NotEqual),
+                right,
+            } => {
+                let checked = match (&left.node, &right.node) {
+                    (Expr::Identifier(name), Expr::NilLiteral)
+                    | (Expr::NilLiteral, Expr::Identifier(name)) => vec![name.clone()],
+                    _ => Vec::new(),
+                };
+
+                match operator {
+                    Token::NotEqual => Self {
+                        when_true: checked,
+                        when_false: Vec::new(),
+                    },
+                    _ => Self {
+                        when_true: Vec::new(),
+                        when_false: checked,
+                    },
+                }
+            }
+
+            Expr::BinaryOperation {
+                left,
+                operator: Token::And,
+                right,
+            } => {
+                let mut when_true = Self::from_condition(left).when_true;
+                when_true.extend(Self::from_condition(right).when_true);
+
+                Self {
+                    when_true,
+                    when_false: Vec::new(),
+                }
+            }
+
+            Expr::BinaryOperation {
+                left,
+                operator: Token::Or,
+                right,
+            } => {
+                let mut when_false = Self::from_condition(left).when_false;
+                when_false.extend(Self::from_condition(right).when_false);
+
+                Self {
+                    when_true: Vec::new(),
+                    when_false,
+                }
+            }
+
+            Expr::Unary {
+                operator: Token::Not | Token::Bang,
+                right,
+            } => {
+                let inner = Self::from_condition(right);
+
+                Self {
+                    when_true: inner.when_false,
+                    when_false: inner.when_true,
+                }
+            }
+
+            _ => Self::default(),
+        }
+    }
+}
+
+impl TypeChecker<'_> {
+    /*
+     * Rejects a possibly-nil value where a plain value is needed
+     *
+     * -- Arguments: [&mut self, value_type - type of `expr`,
+     *                expr - the value being used]
+     * -- Return value: Result<Type, ()> - `value_type` when it can't be nil
+     * */
+    pub(crate) fn expect_non_nil(&mut self, value_type: Type, expr: &Expression) -> Result<Type, ()> {
+        if let Type::Optional(inner) = &value_type {
+            let narrowable = match &expr.node {
+                Expr::Identifier(name) => self.symbol_type_table.can_narrow(name),
+                _ => false,
+            };
+
+            self.throw_error(VynError::PossiblyNil {
+                expr: expr.node.to_string(),
+                inner_type: (**inner).clone(),
+                narrowable,
+                span: expr.span,
+            });
+            return Err(());
+        }
+
+        Ok(value_type)
+    }
+
+    /*
+     * Narrows optional variables to their inner type for the rest of the
+     * current scope
+     * */
+    pub(crate) fn narrow(&mut self, names: &[String]) {
+        for name in names {
+            self.symbol_type_table.narrow_identifier(name);
+        }
+    }
+
+    /*
+     * Runs a check in a new scope where `names` are narrowed
+     *
+     * Assignments inside the scope must keep the narrowed type, so a
+     * narrowed variable stays non-nil until the scope ends
+     * */
+    pub(crate) fn check_narrowed<T>(
+        &mut self,
+        names: &[String],
+        check: impl FnOnce(&mut Self) -> Result<T, ()>,
+    ) -> Result<T, ()> {
+        if names.is_empty() {
+            return check(self);
+        }
+
+        let parent_table = mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new());
+        self.symbol_type_table = parent_table.enter_scope();
+
+        self.narrow(names);
+        let result = check(self);
+
+        self.symbol_type_table =
+            mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new()).exit_scope();
+
+        result
+    }
+}
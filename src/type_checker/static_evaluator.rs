@@ -1,14 +1,18 @@
 use crate::{
-    ast::ast::{Expr, Expression, Program, Statement, Stmt},
+    ast::{
+        ast::{Expr, Expression, Program, Statement, Stmt},
+        type_annotation::TypeAnnotation,
+    },
     error_handler::{error_collector::ErrorCollector, errors::VynError},
     tokens::TokenType,
     utils::Span,
+    vyn_vm::arithmeric::checked_pow,
 };
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum StaticValue {
-    Int(i32),
+    Int(i64),
     Float(f64),
     Bool(bool),
     String(String),
@@ -16,7 +20,7 @@ pub enum StaticValue {
 }
 
 impl StaticValue {
-    pub fn as_int(&self) -> Option<i32> {
+    pub fn as_int(&self) -> Option<i64> {
         match self {
             StaticValue::Int(n) => Some(*n),
             _ => None,
@@ -38,6 +42,7 @@ impl StaticValue {
     }
 }
 
+#[derive(Clone)]
 pub struct StaticEvaluator {
     // Store evaluated statics
     statics: HashMap<String, (StaticValue, Span)>,
@@ -159,7 +164,7 @@ impl StaticEvaluator {
 
     /// Evaluate a static expression
     pub fn evaluate_static_expr(
-        &mut self,
+        &self,
         expr: &Expression,
         errors: &mut ErrorCollector,
     ) -> Result<StaticValue, ()> {
@@ -194,7 +199,9 @@ impl StaticEvaluator {
 
                 match operator.get_token_type() {
                     TokenType::Minus => match right_val {
-                        StaticValue::Int(n) => Ok(StaticValue::Int(-n)),
+                        StaticValue::Int(n) => n.checked_neg().map(StaticValue::Int).ok_or_else(|| {
+                            errors.add(VynError::StaticOverflow { span: expr.span });
+                        }),
                         StaticValue::Float(f) => Ok(StaticValue::Float(-f)),
                         _ => {
                             errors.add(VynError::InvalidStaticOperation {
@@ -214,7 +221,7 @@ impl StaticEvaluator {
                             Err(())
                         }
                     },
-                    TokenType::Bang => match right_val {
+                    TokenType::Bang | TokenType::Not => match right_val {
                         StaticValue::Bool(b) => Ok(StaticValue::Bool(!b)),
                         StaticValue::Int(n) => Ok(StaticValue::Bool(n == 0)),
                         _ => {
@@ -241,6 +248,19 @@ impl StaticEvaluator {
                 right,
             } => {
                 let left_val = self.evaluate_static_expr(left, errors)?;
+
+                // Short-circuit: the right side is not evaluated when the
+                // left side already decides the result
+                match (&left_val, operator.get_token_type()) {
+                    (StaticValue::Bool(false), TokenType::And) => {
+                        return Ok(StaticValue::Bool(false));
+                    }
+                    (StaticValue::Bool(true), TokenType::Or) => {
+                        return Ok(StaticValue::Bool(true));
+                    }
+                    _ => {}
+                }
+
                 let right_val = self.evaluate_static_expr(right, errors)?;
 
                 self.evaluate_binary_op(
@@ -252,6 +272,34 @@ impl StaticEvaluator {
                 )
             }
 
+            Expr::Cast { value, target } => {
+                let value = self.evaluate_static_expr(value, errors)?;
+
+                match (value, target.as_ref()) {
+                    (StaticValue::Int(n), TypeAnnotation::FloatType) => {
+                        Ok(StaticValue::Float(n as f64))
+                    }
+                    (StaticValue::Float(f), TypeAnnotation::IntegerType) => {
+                        // Same rules as the FLOAT_TO_INT instruction: truncate toward zero
+                        if f.is_finite() && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+                            Ok(StaticValue::Int(f as i64))
+                        } else {
+                            errors.add(VynError::StaticOverflow { span: expr.span });
+                            Err(())
+                        }
+                    }
+                    (v @ StaticValue::Int(_), TypeAnnotation::IntegerType)
+                    | (v @ StaticValue::Float(_), TypeAnnotation::FloatType) => Ok(v),
+                    _ => {
+                        errors.add(VynError::InvalidStaticOperation {
+                            operation: format!("as {}", target),
+                            span: expr.span,
+                        });
+                        Err(())
+                    }
+                }
+            }
+
             _ => {
                 errors.add(VynError::NotStaticExpression { span: expr.span });
                 Err(())
@@ -292,7 +340,10 @@ impl StaticEvaluator {
                     errors.add(VynError::DivisionByZero { span });
                     Err(())
                 } else {
-                    Ok(StaticValue::Int(l / r))
+                    // i64::MIN / -1 overflows
+                    l.checked_div(r).map(StaticValue::Int).ok_or_else(|| {
+                        errors.add(VynError::StaticOverflow { span });
+                    })
                 }
             }
             (StaticValue::Int(l), TokenType::Caret, StaticValue::Int(r)) => {
@@ -300,7 +351,7 @@ impl StaticEvaluator {
                     errors.add(VynError::NegativeExponent { span });
                     Err(())
                 } else {
-                    l.checked_pow(r as u32)
+                    checked_pow(l, r as u64)
                         .map(StaticValue::Int)
                         .ok_or_else(|| {
                             errors.add(VynError::StaticOverflow { span });
@@ -328,6 +379,11 @@ impl StaticEvaluator {
                 }
             }
 
+            // String operations
+            (StaticValue::String(l), TokenType::Plus, StaticValue::String(r)) => {
+                Ok(StaticValue::String(l + &r))
+            }
+
             // Boolean operations
             (StaticValue::Bool(l), TokenType::And, StaticValue::Bool(r)) => {
                 Ok(StaticValue::Bool(l && r))
@@ -352,7 +408,7 @@ impl StaticEvaluator {
     }
 
     /// Get a static as an integer (helper for array sizes)
-    pub fn get_static_int(&self, name: &str) -> Option<i32> {
+    pub fn get_static_int(&self, name: &str) -> Option<i64> {
         self.get_static(name)?.as_int()
     }
 }
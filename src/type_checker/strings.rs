@@ -0,0 +1,96 @@
+use crate::{
+    ast::ast::Expression,
+    error_handler::errors::VynError,
+    type_checker::type_checker::{Type, TypeChecker},
+    utils::Span,
+};
+
+// The only field a String has, its length in characters
+pub const STRING_LENGTH_FIELD: &str = "length";
+
+impl TypeChecker<'_> {
+    /*
+     * Checks a `target::start..end` slice
+     *
+     * -- Arguments: [&mut self, target - sliced string,
+     *                start, end - character indices, span - whole expression]
+     * -- Return value: Result<Type, ()> - String
+     * */
+    pub(crate) fn check_slice(
+        &mut self,
+        target: &Expression,
+        start: &Expression,
+        end: &Expression,
+        span: Span,
+    ) -> Result<Type, ()> {
+        let target_type = self.check_expression(target, None)?;
+        let target_type = self.expect_non_nil(target_type, target)?;
+
+        if target_type != Type::String {
+            self.throw_error(VynError::InvalidSlice {
+                target: target_type,
+                span,
+            });
+            return Err(());
+        }
+
+        for bound in [start, end] {
+            let bound_type = self.check_expression(bound, None)?;
+            let bound_type = self.expect_non_nil(bound_type, bound)?;
+
+            if bound_type != Type::Integer {
+                self.throw_error(VynError::TypeMismatch {
+                    expected: vec![Type::Integer],
+                    found: bound_type,
+                    span: bound.span,
+                });
+                return Err(());
+            }
+        }
+
+        Ok(Type::String)
+    }
+
+    /*
+     * Checks a `"text {value} text"` literal
+     *
+     * Any value can be interpolated, arrays, structs and enums are written
+     * the same way `stdout#` writes them
+     *
+     * -- Return value: Result<Type, ()> - String
+     * */
+    pub(crate) fn check_string_interpolation(&mut self, parts: &[Expression]) -> Result<Type, ()> {
+        let mut result = Ok(Type::String);
+
+        // Errors in one part don't stop the others from being checked
+        for part in parts {
+            let part_type = self
+                .check_expression(part, None)
+                .and_then(|part_type| self.expect_non_nil(part_type, part));
+
+            if part_type.is_err() {
+                result = Err(());
+            }
+        }
+
+        result
+    }
+
+    /*
+     * Checks a field read on a String, `s.length` is the only one
+     * */
+    pub(crate) fn check_string_field(&mut self, field: &Expression) -> Result<Type, ()> {
+        let name = field.node.to_string();
+
+        if name != STRING_LENGTH_FIELD {
+            self.throw_error(VynError::UnknownField {
+                target: Type::String,
+                field: name,
+                span: field.span,
+            });
+            return Err(());
+        }
+
+        Ok(Type::Integer)
+    }
+}
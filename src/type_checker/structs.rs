@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+
+use crate::{
+    ast::{
+        ast::{Expr, Expression, StructField, StructLiteralField},
+        type_annotation::TypeAnnotation,
+    },
+    error_handler::errors::VynError,
+    type_checker::type_checker::{Type, TypeChecker},
+    utils::Span,
+};
+
+// Field slots are encoded in a single byte
+const MAX_STRUCT_FIELDS: usize = u8::MAX as usize;
+
+impl TypeChecker<'_> {
+    /*
+     * Checks a struct declaration
+     *
+     * The parser already registered the struct, this only validates its
+     * field list
+     *
+     * -- Arguments: [&mut self, identifier - struct name,
+     *                fields - declared fields, span - whole declaration]
+     * -- Return value: Result<(), ()>
+     * */
+    pub(crate) fn check_struct_decl(
+        &mut self,
+        identifier: &Expression,
+        fields: &[StructField],
+        span: Span,
+    ) -> Result<(), ()> {
+        if fields.len() > MAX_STRUCT_FIELDS {
+            self.throw_error(VynError::TooManyFields {
+                name: identifier.node.to_string(),
+                span,
+            });
+            return Err(());
+        }
+
+        let mut seen = HashSet::new();
+        for field in fields {
+            let name = field.identifier.node.to_string();
+
+            if !seen.insert(name.clone()) {
+                self.throw_error(VynError::DuplicateField {
+                    name,
+                    span: field.identifier.span,
+                });
+                return Err(());
+            }
+
+            // Surfaces errors in the field type, such as a bad array size
+            Type::from_anotated_type(&field.annotated_type, self.static_eval, &mut self.errors);
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Checks a `Name { field: value, ... }` literal
+     *
+     * Every field must be given exactly once, in any order
+     *
+     * -- Arguments: [&mut self, struct_type - annotated struct type,
+     *                fields - given fields, span - whole literal]
+     * -- Return value: Result<Type, ()> - the struct type
+     * */
+    pub(crate) fn check_struct_literal(
+        &mut self,
+        struct_type: &TypeAnnotation,
+        fields: &[StructLiteralField],
+        span: Span,
+    ) -> Result<Type, ()> {
+        let struct_type = Type::from_anotated_type(struct_type, self.static_eval, &mut self.errors);
+        let mut seen = HashSet::new();
+
+        for field in fields {
+            let name = field.identifier.node.to_string();
+
+            let Some((_, field_type)) = struct_type.field(&name) else {
+                self.throw_error(VynError::UnknownField {
+                    target: struct_type.clone(),
+                    field: name,
+                    span: field.identifier.span,
+                });
+                return Err(());
+            };
+            let field_type = field_type.clone();
+
+            if !seen.insert(name.clone()) {
+                self.throw_error(VynError::DuplicateField {
+                    name,
+                    span: field.identifier.span,
+                });
+                return Err(());
+            }
+
+            let value_type = self.check_expression(&field.value, Some(field_type.clone()))?;
+            if value_type != field_type {
+                self.throw_error(VynError::TypeMismatch {
+                    expected: vec![field_type],
+                    found: value_type,
+                    span: field.value.span,
+                });
+                return Err(());
+            }
+        }
+
+        if let Type::Struct {
+            fields: declared, ..
+        } = &struct_type
+            && let Some((missing, _)) = declared.iter().find(|(name, _)| !seen.contains(name))
+        {
+            self.throw_error(VynError::MissingField {
+                target: struct_type.clone(),
+                field: missing.clone(),
+                span,
+            });
+            return Err(());
+        }
+
+        Ok(struct_type)
+    }
+
+    /*
+     * Checks a `target.field` read
+     *
+     * -- Return value: Result<Type, ()> - the field's type
+     * */
+    pub(crate) fn check_field_access(
+        &mut self,
+        target: &Expression,
+        field: &Expression,
+        span: Span,
+    ) -> Result<Type, ()> {
+        let target_type = self.check_expression(target, None)?;
+        let target_type = self.expect_non_nil(target_type, target)?;
+
+        if target_type == Type::String {
+            return self.check_string_field(field);
+        }
+
+        self.resolve_field(target_type, field, span)
+    }
+
+    /*
+     * Checks a `target.field = value` write
+     *
+     * The variable the access starts from must be mutable, so `p.x = 1`
+     * needs `let @p`
+     *
+     * -- Return value: Result<Type, ()> - the field's type
+     * */
+    pub(crate) fn check_field_assignment(
+        &mut self,
+        target: &Expression,
+        field: &Expression,
+        new_value: &Expression,
+        span: Span,
+    ) -> Result<Type, ()> {
+        if let Some(root) = root_identifier(target) {
+            let symbol =
+                self.symbol_type_table
+                    .resolve_identifier(root, target.span, &mut self.errors)?;
+            symbol.usage.assigned.set(true);
+
+            if symbol.is_static() {
+                let symbol_span = symbol.span;
+                self.throw_error(VynError::StaticMutation {
+                    identifier: root.to_string(),
+                    mutator_span: span,
+                    span: symbol_span,
+                });
+                return Err(());
+            }
+
+            if !symbol.mutable {
+                let symbol_span = symbol.span;
+                self.throw_error(VynError::ImmutableMutation {
+                    identifier: root.to_string(),
+                    span: symbol_span,
+                    mutation_span: span,
+                });
+                return Err(());
+            }
+        }
+
+        let target_type = self.check_expression(target, None)?;
+        let target_type = self.expect_non_nil(target_type, target)?;
+
+        // Strings are immutable, their length follows from their content
+        if target_type == Type::String {
+            self.check_string_field(field)?;
+            self.throw_error(VynError::ReadOnlyField {
+                target: target_type,
+                field: field.node.to_string(),
+                span,
+            });
+            return Err(());
+        }
+
+        let field_type = self.resolve_field(target_type, field, span)?;
+
+        let new_value_type = self.check_expression(new_value, Some(field_type.clone()))?;
+        if new_value_type != field_type {
+            self.throw_error(VynError::TypeMismatch {
+                expected: vec![field_type],
+                found: new_value_type,
+                span: new_value.span,
+            });
+            return Err(());
+        }
+
+        Ok(field_type)
+    }
+
+    fn resolve_field(
+        &mut self,
+        target_type: Type,
+        field: &Expression,
+        span: Span,
+    ) -> Result<Type, ()> {
+        if !matches!(target_type, Type::Struct { .. }) {
+            self.throw_error(VynError::InvalidFieldAccess {
+                target: target_type,
+                span,
+            });
+            return Err(());
+        }
+
+        let name = field.node.to_string();
+        match target_type.field(&name) {
+            Some((_, field_type)) => Ok(field_type.clone()),
+            None => {
+                self.throw_error(VynError::UnknownField {
+                    target: target_type,
+                    field: name,
+                    span: field.span,
+                });
+                Err(())
+            }
+        }
+    }
+}
+
+/*
+ * Finds the variable a chain of field and index accesses starts from
+ *
+ * -- Return value: Some(name) for `a.b::0.c`, None when the chain starts
+ *                  from something else, like a call
+ * */
+pub(crate) fn root_identifier(expr: &Expression) -> Option<&str> {
+    match &expr.node {
+        Expr::Identifier(name) => Some(name),
+        Expr::FieldAccess { target, .. } | Expr::Index { target, .. } => root_identifier(target),
+        _ => None,
+    }
+}
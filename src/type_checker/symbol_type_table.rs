@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -13,6 +14,19 @@ pub struct SymbolType {
     pub span: Span,
     pub mutable: bool,
     pub is_static: bool,
+    pub usage: Rc<SymbolUsage>,
+}
+
+/*
+ * What the checked code did with a variable, shared by every copy of its
+ * symbol so reads through a narrowed copy or from a function body count
+ * */
+#[derive(Default)]
+pub struct SymbolUsage {
+    pub read: Cell<bool>,
+    pub assigned: Cell<bool>,
+    // Declared without a value and not assigned on the path being checked
+    pub unassigned: Cell<bool>,
 }
 
 impl SymbolType {
@@ -21,7 +35,7 @@ impl SymbolType {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct SymbolTypeTable {
     pub parent: Option<Rc<SymbolTypeTable>>,
     store: HashMap<String, SymbolType>,
@@ -59,6 +73,7 @@ impl SymbolTypeTable {
             span,
             mutable,
             is_static: false,
+            usage: Rc::default(),
         };
 
         self.store.insert(ident, symbol_type);
@@ -86,6 +101,7 @@ impl SymbolTypeTable {
             span,
             mutable: false,
             is_static: true,
+            usage: Rc::default(),
         };
 
         self.store.insert(ident, symbol_type);
@@ -98,25 +114,102 @@ impl SymbolTypeTable {
         span: Span,
         errors: &mut ErrorCollector,
     ) -> Result<&SymbolType, ()> {
+        match self.lookup(ident) {
+            Some((s, _)) => Ok(s),
+            None => {
+                errors.add(VynError::UndefinedVariable {
+                    name: ident.to_string(),
+                    span,
+                });
+                Err(())
+            }
+        }
+    }
+
+    pub fn is_declared(&self, ident: &str) -> bool {
+        self.lookup(ident).is_some()
+    }
+
+    /*
+     * Finds the declaration a new one in this scope would shadow
+     *
+     * -- Return value: Some(span) of a variable with the same name in an
+     *                  enclosing scope, None if there is none or it lives in
+     *                  this scope (a redeclaration, not shadowing)
+     * */
+    pub fn shadowed_span(&self, ident: &str) -> Option<Span> {
+        if self.store.contains_key(ident) {
+            return None;
+        }
+
+        self.parent
+            .as_ref()?
+            .lookup(ident)
+            .filter(|(symbol, _)| !matches!(symbol.symbol_type, Type::Function { .. }))
+            .map(|(symbol, _)| symbol.span)
+    }
+
+    /*
+     * Checks whether a nil check can narrow a variable
+     *
+     * Any function can assign to a mutable global, including one called
+     * right after the check, so those are never narrowed
+     * */
+    pub fn can_narrow(&self, ident: &str) -> bool {
+        match self.lookup(ident) {
+            Some((symbol, is_global)) => !(symbol.mutable && is_global),
+            None => false,
+        }
+    }
+
+    /*
+     * Turns an optional variable into its inner type for the rest of the
+     * current scope
+     *
+     * Used after a nil check proved the variable holds a value. It keeps
+     * its span and mutability, names `can_narrow` rejects are ignored
+     * */
+    pub fn narrow_identifier(&mut self, ident: &str) {
+        if !self.can_narrow(ident) {
+            return;
+        }
+        let Some((symbol, _)) = self.lookup(ident) else {
+            return;
+        };
+        let Type::Optional(inner) = &symbol.symbol_type else {
+            return;
+        };
+
+        let narrowed = SymbolType {
+            symbol_type: (**inner).clone(),
+            ..symbol.clone()
+        };
+        self.store.insert(ident.to_string(), narrowed);
+    }
+
+    /*
+     * Finds a symbol in this scope or an enclosing one
+     *
+     * -- Return value: Some((symbol, is_global)), `is_global` is set when it
+     *                  lives in the root scope, which holds the globals both
+     *                  at the top level and inside function bodies
+     * */
+    fn lookup(&self, ident: &str) -> Option<(&SymbolType, bool)> {
         // Check current scope
         if let Some(s) = self.store.get(ident) {
-            return Ok(s);
+            return Some((s, self.parent.is_none()));
         }
 
         // Walk up parent scopes
         let mut current = self.parent.as_ref();
         while let Some(parent) = current {
             if let Some(s) = parent.store.get(ident) {
-                return Ok(s);
+                return Some((s, parent.parent.is_none()));
             }
             current = parent.parent.as_ref();
         }
 
-        errors.add(VynError::UndefinedVariable {
-            name: ident.to_string(),
-            span,
-        });
-        Err(())
+        None
     }
 
     pub fn enroll_type_alias(
@@ -141,6 +234,36 @@ impl SymbolTypeTable {
         }
     }
 
+    /*
+     * Creates the root scope of a function body
+     *
+     * Function bodies run in their own register window, so only statics,
+     * other functions and top-level (global) variables are visible from the
+     * enclosing scopes
+     * */
+    pub fn enter_function_scope(&self) -> SymbolTypeTable {
+        let mut function_scope = SymbolTypeTable::new();
+        let mut current = Some(self);
+
+        while let Some(table) = current {
+            let is_global_scope = table.parent.is_none();
+
+            for (name, symbol) in &table.store {
+                let visible = is_global_scope
+                    || symbol.is_static()
+                    || matches!(symbol.symbol_type, Type::Function { .. });
+
+                if visible && !function_scope.store.contains_key(name) {
+                    function_scope.store.insert(name.clone(), symbol.clone());
+                }
+            }
+
+            current = table.parent.as_deref();
+        }
+
+        function_scope.enter_scope()
+    }
+
     pub fn exit_scope(self) -> SymbolTypeTable {
         match self.parent {
             Some(parent_rc) => Rc::try_unwrap(parent_rc).unwrap_or_else(|rc| (*rc).clone()),
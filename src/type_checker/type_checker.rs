@@ -3,13 +3,21 @@ use crate::{
         ast::{Expr, Expression, Program, Statement, Stmt},
         type_annotation::TypeAnnotation,
     },
-    error_handler::{error_collector::ErrorCollector, errors::VynError},
+    error_handler::{error_collector::ErrorCollector, errors::VynError, internal},
     tokens::{Token, TokenType},
-    type_checker::{static_evaluator::StaticEvaluator, symbol_type_table::SymbolTypeTable},
-    utils::{Span, throw_error},
+    type_checker::{
+        assignment::AssignmentState,
+        lints::TrackedVariable,
+        optional::NilChecks,
+        static_evaluator::StaticEvaluator,
+        structs::root_identifier,
+        symbol_type_table::{SymbolTypeTable, SymbolUsage},
+    },
+    utils::Span,
+    vyn_vm::vm::MAX_ARRAY_LENGTH,
 };
 use core::fmt;
-use std::mem;
+use std::{mem, rc::Rc};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
@@ -21,6 +29,22 @@ pub enum Type {
     Identifier,
     Array(Box<Type>, usize),
     Sequence(Box<Type>),
+    // Either a value of the inner type or nil
+    Optional(Box<Type>),
+    Function {
+        parameters: Vec<Type>,
+        return_type: Box<Type>,
+    },
+    // Fields in declaration order, a field's position is its slot at runtime
+    Struct {
+        name: String,
+        fields: Vec<(String, Type)>,
+    },
+    // Variants in declaration order, a variant's position is its tag at runtime
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<Type>)>,
+    },
 }
 
 impl fmt::Display for Type {
@@ -38,6 +62,20 @@ impl fmt::Display for Type {
             Type::Sequence(t) => {
                 write!(f, "[]{}", t)
             }
+            Type::Optional(t) => write!(f, "{}?", t),
+            Type::Function {
+                parameters,
+                return_type,
+            } => {
+                let params = parameters
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "fn({}) -> {}", params, return_type)
+            }
+            Type::Struct { name, .. } | Type::Enum { name, .. } => write!(f, "{}", name),
         }
     }
 }
@@ -65,6 +103,114 @@ impl Type {
                 let t = Type::from_anotated_type(ta, static_eval, errors);
                 Type::Sequence(Box::new(t))
             }
+            TypeAnnotation::OptionalType(ta) => {
+                let t = Type::from_anotated_type(ta, static_eval, errors);
+                Type::Optional(Box::new(t))
+            }
+            TypeAnnotation::StructType { name, fields } => Type::Struct {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(field, ta)| {
+                        (
+                            field.clone(),
+                            Type::from_anotated_type(ta, static_eval, errors),
+                        )
+                    })
+                    .collect(),
+            },
+            TypeAnnotation::EnumType { name, variants } => Type::Enum {
+                name: name.clone(),
+                variants: variants
+                    .iter()
+                    .map(|(variant, payload)| {
+                        (
+                            variant.clone(),
+                            payload
+                                .iter()
+                                .map(|ta| Type::from_anotated_type(ta, static_eval, errors))
+                                .collect(),
+                        )
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /*
+     * Finds a struct field by name
+     *
+     * -- Arguments: [&self], name - field name
+     * -- Return value: Some((slot, field type)), None for unknown fields or
+     *                  non-struct types
+     * */
+    pub fn field(&self, name: &str) -> Option<(usize, &Type)> {
+        match self {
+            Type::Struct { fields, .. } => fields
+                .iter()
+                .enumerate()
+                .find(|(_, (field, _))| field == name)
+                .map(|(slot, (_, t))| (slot, t)),
+            _ => None,
+        }
+    }
+
+    /*
+     * Finds an enum variant by name
+     *
+     * -- Arguments: [&self], name - variant name
+     * -- Return value: Some((tag, payload types)), None for unknown variants
+     *                  or non-enum types
+     * */
+    pub fn variant(&self, name: &str) -> Option<(usize, &[Type])> {
+        match self {
+            Type::Enum { variants, .. } => variants
+                .iter()
+                .enumerate()
+                .find(|(_, (variant, _))| variant == name)
+                .map(|(tag, (_, payload))| (tag, payload.as_slice())),
+            _ => None,
+        }
+    }
+
+    /*
+     * Strips the optional marker, `Int?` becomes `Int`
+     *
+     * Once checked, an optional value is stored exactly like its inner type,
+     * so the IR uses this to pick instructions
+     * */
+    pub fn without_nil(self) -> Type {
+        match self {
+            Type::Optional(inner) => *inner,
+            other => other,
+        }
+    }
+
+    /*
+     * Checks whether values of this type live on the heap, where they are
+     * shared rather than copied and can be changed in place
+     * */
+    pub fn is_heap_value(&self) -> bool {
+        match self {
+            Type::Array(..) | Type::Sequence(_) | Type::Struct { .. } | Type::Enum { .. } => true,
+            Type::Optional(inner) => inner.is_heap_value(),
+            _ => false,
+        }
+    }
+
+    /*
+     * Checks whether `get_type_default_value` can build a value of this type
+     * */
+    pub fn has_default_value(&self) -> bool {
+        match self {
+            Type::String
+            | Type::Integer
+            | Type::Float
+            | Type::Bool
+            | Type::Sequence(_)
+            | Type::Optional(_) => true,
+            Type::Array(t, _) => t.has_default_value(),
+            _ => false,
         }
     }
 
@@ -74,45 +220,48 @@ impl Type {
         static_eval: &StaticEvaluator,
         symbol_table: &SymbolTypeTable,
         errors: &mut ErrorCollector,
-    ) -> Self {
-        match &expr.node {
+    ) -> Result<Self, ()> {
+        let inferred = match &expr.node {
             Expr::IntegerLiteral(_) => Self::Integer,
             Expr::FloatLiteral(_) => Self::Float,
             Expr::BooleanLiteral(_) => Self::Bool,
             Expr::StringLiteral(_) => Self::String,
             Expr::NilLiteral => Self::Nil,
 
-            Expr::ArrayLiteral { elements } => {
-                if elements.is_empty() {
-                    // Empty array - we can't infer the type
-                    // this shoulf be unreachable
+            Expr::ArrayLiteral { elements } => match elements.first() {
+                // Infer from first element
+                Some(first) => {
+                    let elem_type = Self::from_ast(first, static_eval, symbol_table, errors)?;
+                    Self::Array(Box::new(elem_type), elements.len())
+                }
+                // Empty array - we can't infer the type
+                None => {
                     errors.add(VynError::TypeInfer {
                         expr: expr.node.clone(),
                         span: expr.span,
                     });
-                    unreachable!()
-                } else {
-                    // Infer from first element
-                    let elem_type = Self::from_ast(&elements[0], static_eval, symbol_table, errors);
-                    let size = elements.len();
-                    Self::Array(Box::new(elem_type), size)
+                    return Err(());
                 }
-            }
+            },
 
-            Expr::Identifier(name) => {
-                match symbol_table.resolve_identifier(name, expr.span, errors) {
-                    Ok(symbol) => symbol.symbol_type.clone(),
-                    Err(_) => unreachable!(),
-                }
-            }
+            Expr::Identifier(name) => symbol_table
+                .resolve_identifier(name, expr.span, errors)?
+                .symbol_type
+                .clone(),
 
             Expr::Unary { operator, right } => {
-                let operand_type = Self::from_ast(right, static_eval, symbol_table, errors);
+                let operand_type = Self::from_ast(right, static_eval, symbol_table, errors)?;
 
                 match operator {
-                    Token::Minus => operand_type, // Negation preserves type
-                    Token::Bang => Self::Bool,    // Logical NOT returns bool
-                    _ => unreachable!(),
+                    Token::Minus => operand_type,           // Negation preserves type
+                    Token::Bang | Token::Not => Self::Bool, // Logical NOT returns bool
+                    _ => {
+                        errors.add(VynError::InvalidUnaryOperator {
+                            operator: operator.clone(),
+                            span: expr.span,
+                        });
+                        return Err(());
+                    }
                 }
             }
 
@@ -121,8 +270,8 @@ impl Type {
                 operator,
                 right,
             } => {
-                let left_type = Self::from_ast(left, static_eval, symbol_table, errors);
-                let _right_type = Self::from_ast(right, static_eval, symbol_table, errors);
+                let left_type = Self::from_ast(left, static_eval, symbol_table, errors)?;
+                Self::from_ast(right, static_eval, symbol_table, errors)?;
 
                 match operator {
                     // Arithmetic operators preserve type (int + int = int, float + float = float)
@@ -141,28 +290,103 @@ impl Type {
                     // Logical operators return bool
                     Token::And | Token::Or => Self::Bool,
 
-                    _ => unreachable!(),
+                    _ => {
+                        errors.add(VynError::InvalidBinaryOperator {
+                            operator: operator.clone(),
+                            span: expr.span,
+                        });
+                        return Err(());
+                    }
                 }
             }
 
             Expr::Index { target, .. } => {
-                let target_type = Self::from_ast(target, static_eval, symbol_table, errors);
-
-                match target_type {
+                match Self::from_ast(target, static_eval, symbol_table, errors)? {
                     Self::Array(elem_type, _) => *elem_type,
                     Self::Sequence(elem_type) => *elem_type,
-                    _ => unreachable!(),
+                    // Indexing a string gives a one character string
+                    Self::String => Self::String,
+                    target_type => {
+                        errors.add(VynError::InvalidIndexing {
+                            target: target_type,
+                            span: expr.span,
+                        });
+                        return Err(());
+                    }
                 }
             }
 
+            Expr::Slice { .. } | Expr::StringInterpolation { .. } => Self::String,
+
             Expr::VariableAssignment { new_value, .. } => {
-                Self::from_ast(new_value, static_eval, symbol_table, errors)
+                Self::from_ast(new_value, static_eval, symbol_table, errors)?
             }
 
             Expr::IndexAssignment { new_value, .. } => {
-                Self::from_ast(new_value, static_eval, symbol_table, errors)
+                Self::from_ast(new_value, static_eval, symbol_table, errors)?
             }
-        }
+
+            Expr::Call { callee, .. } => {
+                match Self::from_ast(callee, static_eval, symbol_table, errors)? {
+                    Self::Function { return_type, .. } => *return_type,
+                    found => {
+                        errors.add(VynError::NotCallable {
+                            found,
+                            span: callee.span,
+                        });
+                        return Err(());
+                    }
+                }
+            }
+
+            Expr::Cast { target, .. } => Self::from_anotated_type(target, static_eval, errors),
+
+            Expr::StructLiteral { struct_type, .. } => {
+                Self::from_anotated_type(struct_type, static_eval, errors)
+            }
+
+            Expr::FieldAccess { target, field } => {
+                let target_type = Self::from_ast(target, static_eval, symbol_table, errors)?;
+                if target_type == Self::String {
+                    return Ok(Self::Integer);
+                }
+
+                let field_name = field.node.to_string();
+                match target_type.field(&field_name) {
+                    Some((_, field_type)) => field_type.clone(),
+                    None => {
+                        errors.add(VynError::UnknownField {
+                            target: target_type,
+                            field: field_name,
+                            span: field.span,
+                        });
+                        return Err(());
+                    }
+                }
+            }
+
+            Expr::FieldAssignment { new_value, .. } => {
+                Self::from_ast(new_value, static_eval, symbol_table, errors)?
+            }
+
+            Expr::EnumVariant { enum_type, .. } => {
+                Self::from_anotated_type(enum_type, static_eval, errors)
+            }
+
+            // Every arm has the same type, infer from the first one
+            Expr::Match { arms, .. } => match arms.first() {
+                Some(arm) => Self::from_ast(&arm.value, static_eval, symbol_table, errors)?,
+                None => {
+                    errors.add(VynError::TypeInfer {
+                        expr: expr.node.clone(),
+                        span: expr.span,
+                    });
+                    return Err(());
+                }
+            },
+        };
+
+        Ok(inferred)
     }
 
     pub fn get_type_default_value(t: &Type) -> Expression {
@@ -179,6 +403,7 @@ impl Type {
                 }
             }
             Self::Sequence(_) => Expr::ArrayLiteral { elements: vec![] },
+            Self::Optional(_) => Expr::NilLiteral,
 
             _ => unreachable!(),
         };
@@ -195,53 +420,61 @@ impl Type {
         static_eval: &StaticEvaluator,
         errors: &mut ErrorCollector,
     ) -> Option<usize> {
-        match &expr.node {
+        let size = match &expr.node {
             // Direct integer literal
-            Expr::IntegerLiteral(n) => {
-                if *n >= 0 {
-                    Some(*n as usize)
-                } else {
-                    errors.add(VynError::NegativeArraySize {
-                        size: *n,
-                        span: expr.span,
-                    });
-                    None
-                }
-            }
+            Expr::IntegerLiteral(n) => *n,
 
             // Identifier reference to a static
-            Expr::Identifier(name) => {
-                if let Some(n) = static_eval.get_static_int(name) {
-                    if n >= 0 {
-                        Some(n as usize)
-                    } else {
-                        errors.add(VynError::NegativeArraySize {
-                            size: n,
-                            span: expr.span,
-                        });
-                        None
-                    }
-                } else {
+            Expr::Identifier(name) => match static_eval.get_static_int(name) {
+                Some(n) => n,
+                None => {
                     errors.add(VynError::ArraySizeNotStatic { span: expr.span });
-                    None
+                    return None;
                 }
-            }
+            },
 
             // For complex expressions, we could try to evaluate them
             // but for now, just report an error
             _ => {
                 errors.add(VynError::ArraySizeNotStatic { span: expr.span });
-                None
+                return None;
             }
+        };
+
+        if size < 0 {
+            errors.add(VynError::NegativeArraySize {
+                size,
+                span: expr.span,
+            });
+            return None;
         }
+
+        // The verifier refuses to allocate anything larger
+        if size as usize > MAX_ARRAY_LENGTH {
+            errors.add(VynError::ArraySizeTooLarge {
+                size: size as usize,
+                span: expr.span,
+            });
+            return None;
+        }
+
+        Some(size as usize)
     }
 }
 
 pub struct TypeChecker<'a> {
     pub(crate) symbol_type_table: SymbolTypeTable,
     pub(crate) errors: ErrorCollector,
-    static_eval: &'a StaticEvaluator,
-    loop_depth: usize,
+    pub(crate) static_eval: &'a StaticEvaluator,
+    pub(crate) loop_depth: usize,
+    // Declared return type of the function being checked
+    pub(crate) return_type: Option<Type>,
+    // Every variable declared so far, for the unused lints
+    pub(crate) tracked_variables: Vec<TrackedVariable>,
+    // Variables declared without a value, see `assignment.rs`
+    pub(crate) uninitialized: Vec<Rc<SymbolUsage>>,
+    // Assignment state at each `break` of the enclosing loops
+    pub(crate) break_states: Vec<Vec<AssignmentState>>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -251,15 +484,29 @@ impl<'a> TypeChecker<'a> {
             errors: ErrorCollector::new(),
             static_eval,
             loop_depth: 0,
+            return_type: None,
+            tracked_variables: Vec::new(),
+            uninitialized: Vec::new(),
+            break_states: Vec::new(),
         }
     }
 
     /// Main entry point
     pub fn check_program(&mut self, program: &Program) -> Result<(), ErrorCollector> {
+        // Functions are hoisted so they can be called before their declaration
         for stmt in &program.statements {
-            let _ = self.check_statement(stmt);
+            let _ = self.declare_function(stmt);
         }
 
+        for stmt in &program.statements {
+            let _ = match &stmt.node {
+                Stmt::FunctionDeclaration { .. } => self.check_function_decl(stmt),
+                _ => self.check_statement(stmt),
+            };
+        }
+
+        self.check_unused_variables();
+
         if self.errors.has_errors() {
             Err(mem::take(&mut self.errors))
         } else {
@@ -269,6 +516,7 @@ impl<'a> TypeChecker<'a> {
 
     pub(crate) fn check_statement(&mut self, stmt: &Statement) -> Result<(), ()> {
         let span = stmt.span;
+        internal::track_span(span);
 
         match &stmt.node {
             Stmt::Expression { expression } => {
@@ -290,14 +538,24 @@ impl<'a> TypeChecker<'a> {
                     _ => unreachable!("Variable name must be an identifier"),
                 };
 
-                self.symbol_type_table.declare_identifier(
-                    var_name,
+                self.declare_variable(
+                    var_name.clone(),
+                    identifier.span,
                     expected_type.clone(),
                     span,
                     *mutable,
-                    &mut self.errors,
                 )?;
 
+                if value.is_none()
+                    && !matches!(expected_type, Type::Optional(_))
+                    && let Ok(symbol) =
+                        self.symbol_type_table
+                            .resolve_identifier(&var_name, span, &mut self.errors)
+                {
+                    let usage = symbol.usage.clone();
+                    self.track_uninitialized(usage);
+                }
+
                 if let Some(val) = value {
                     let value_type = self.check_expression(val, Some(expected_type.clone()))?;
 
@@ -309,6 +567,13 @@ impl<'a> TypeChecker<'a> {
                         });
                         return Err(());
                     }
+                } else if !expected_type.has_default_value() {
+                    self.throw_error(VynError::MissingInitializer {
+                        name: identifier.node.to_string(),
+                        var_type: expected_type,
+                        span,
+                    });
+                    return Err(());
                 }
                 Ok(())
             }
@@ -325,8 +590,12 @@ impl<'a> TypeChecker<'a> {
                     return Err(());
                 }
 
+                let runs_until_break = self.static_condition(condition) == Some(true);
+
                 self.loop_depth += 1;
-                let stmt = self.check_statement(body.as_ref());
+                let stmt = self.check_loop_body(runs_until_break, |checker| {
+                    checker.check_statement(body.as_ref())
+                });
                 self.loop_depth -= 1;
 
                 stmt
@@ -404,6 +673,10 @@ impl<'a> TypeChecker<'a> {
                     return Err(());
                 }
 
+                // The loop itself sets and steps the variable
+                init_ident_info.usage.assigned.set(true);
+                init_ident_info.usage.unassigned.set(false);
+
                 if init_ident_info.symbol_type != start_range_type {
                     self.throw_error(VynError::TypeMismatch {
                         expected: vec![init_ident_info.symbol_type.clone()],
@@ -454,9 +727,18 @@ impl<'a> TypeChecker<'a> {
                         self.loop_depth -= 1;
                         return Err(());
                     }
+
+                    // A step of 0 never reaches the end of the range
+                    if self.is_static_zero(step_expr) {
+                        self.throw_error(VynError::ZeroLoopStep {
+                            is_static: true,
+                            span: step_expr.span,
+                        });
+                    }
                 }
 
-                let stmt = self.check_statement(body.as_ref());
+                let stmt =
+                    self.check_loop_body(false, |checker| checker.check_statement(body.as_ref()));
 
                 // Exit scope after checking body
                 self.symbol_type_table =
@@ -468,7 +750,8 @@ impl<'a> TypeChecker<'a> {
 
             Stmt::Loop { body } => {
                 self.loop_depth += 1;
-                let stmt = self.check_statement(body.as_ref());
+                let stmt =
+                    self.check_loop_body(true, |checker| checker.check_statement(body.as_ref()));
                 self.loop_depth -= 1;
 
                 stmt
@@ -484,6 +767,7 @@ impl<'a> TypeChecker<'a> {
                     return Err(());
                 }
 
+                self.record_break_state();
                 Ok(())
             }
 
@@ -556,15 +840,24 @@ impl<'a> TypeChecker<'a> {
                 Ok(())
             }
 
+            Stmt::StructDeclaration { identifier, fields } => {
+                self.check_struct_decl(identifier, fields, span)
+            }
+
+            Stmt::EnumDeclaration {
+                identifier,
+                variants,
+            } => self.check_enum_decl(identifier, variants, span),
+
+            Stmt::Match { subject, arms } => self.check_match_stmt(subject, arms, span),
+
             Stmt::Scope { statements } => {
                 let parent_table =
                     mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new());
 
                 self.symbol_type_table = parent_table.enter_scope();
 
-                for stmt in statements {
-                    let _ = self.check_statement(stmt);
-                }
+                self.check_block(statements);
 
                 self.symbol_type_table =
                     mem::replace(&mut self.symbol_type_table, SymbolTypeTable::new()).exit_scope();
@@ -578,6 +871,7 @@ impl<'a> TypeChecker<'a> {
                 alternate,
             } => {
                 let condition_type = self.check_expression(condition, None)?;
+                let condition_type = self.expect_non_nil(condition_type, condition)?;
 
                 if condition_type != Type::Bool {
                     self.throw_error(VynError::TypeMismatch {
@@ -588,11 +882,44 @@ impl<'a> TypeChecker<'a> {
                     return Err(());
                 }
 
-                self.check_statement(&consequence)?;
+                let nil_checks = NilChecks::from_condition(condition);
+                let static_condition = self.static_condition(condition);
+                let before = self.assignment_state();
+                // States at the end of the branches that fall through
+                let mut paths = Vec::new();
+
+                self.check_narrowed(&nil_checks.when_true, |checker| {
+                    checker.check_statement(consequence)
+                })?;
+
+                if static_condition != Some(false) && !Self::always_jumps(consequence) {
+                    paths.push(self.assignment_state());
+                }
+                self.restore_assignment_state(&before);
 
                 if let Some(alt) = alternate.as_ref() {
-                    self.check_statement(alt)?;
+                    self.check_narrowed(&nil_checks.when_false, |checker| {
+                        checker.check_statement(alt)
+                    })?;
                 }
+
+                if static_condition != Some(true)
+                    && !alternate.as_ref().as_ref().is_some_and(Self::always_jumps)
+                {
+                    paths.push(self.assignment_state());
+                }
+                self.join_assignment_states(&paths);
+
+                // Code after the `if` only runs when a branch that always
+                // leaves the block was not taken
+                if Self::always_jumps(consequence) {
+                    self.narrow(&nil_checks.when_false);
+                } else if let Some(alt) = alternate.as_ref()
+                    && Self::always_jumps(alt)
+                {
+                    self.narrow(&nil_checks.when_true);
+                }
+
                 Ok(())
             }
 
@@ -601,14 +928,54 @@ impl<'a> TypeChecker<'a> {
                 Ok(())
             }
 
-            _ => throw_error(&format!("unknown ast:\n\n{:#?}", stmt.node), 1),
+            Stmt::FunctionDeclaration { identifier, .. } => {
+                self.throw_error(VynError::NestedFunctionDeclaration {
+                    name: identifier.node.to_string(),
+                    span,
+                });
+                Err(())
+            }
+
+            Stmt::Return { value } => self.check_return(value.as_ref(), span),
+
+            Stmt::Block { .. } => unreachable!("The parser never produces block statements"),
         }
     }
 
+    /*
+     * Checks an expression against the type its context expects, if any
+     *
+     * A value or nil is accepted where an optional of its type is expected
+     * and takes the optional type, an optional given where a plain value is
+     * expected is reported as possibly nil
+     *
+     * -- Arguments: [&mut self, expr - expression to check,
+     *                expected_type - type required by the context]
+     * -- Return value: Result<Type, ()> - the type of the expression
+     * */
     pub(crate) fn check_expression(
         &mut self,
         expr: &Expression,
         expected_type: Option<Type>,
+    ) -> Result<Type, ()> {
+        internal::track_span(expr.span);
+        let found = self.check_expression_node(expr, expected_type.clone())?;
+
+        match expected_type {
+            Some(Type::Optional(inner)) if found == Type::Nil || found == *inner => {
+                Ok(Type::Optional(inner))
+            }
+            Some(expected) if matches!(&found, Type::Optional(inner) if **inner == expected) => {
+                self.expect_non_nil(found, expr)
+            }
+            _ => Ok(found),
+        }
+    }
+
+    fn check_expression_node(
+        &mut self,
+        expr: &Expression,
+        expected_type: Option<Type>,
     ) -> Result<Type, ()> {
         let span = expr.span;
 
@@ -694,18 +1061,62 @@ impl<'a> TypeChecker<'a> {
             }
 
             Expr::Identifier(name) => {
-                let ident =
-                    self.symbol_type_table
-                        .resolve_identifier(name, span, &mut self.errors)?;
+                let ident = self
+                    .symbol_type_table
+                    .resolve_identifier(name, span, &mut self.errors)?
+                    .clone();
+                ident.usage.read.set(true);
+                self.check_assigned(name, &ident, span)?;
+
+                if matches!(ident.symbol_type, Type::Function { .. }) {
+                    self.throw_error(VynError::FunctionAsValue {
+                        name: name.clone(),
+                        span,
+                    });
+                    return Err(());
+                }
 
                 Ok(ident.symbol_type.clone())
             }
 
+            Expr::Call { callee, arguments } => self.check_call(callee, arguments, span),
+
             Expr::Unary { operator, right } => self.check_unary(operator, right, span),
 
+            Expr::Cast { value, target } => self.check_cast(value, target, span),
+
+            Expr::StructLiteral {
+                struct_type,
+                fields,
+            } => self.check_struct_literal(struct_type, fields, span),
+
+            Expr::FieldAccess { target, field } => self.check_field_access(target, field, span),
+
+            Expr::FieldAssignment {
+                target,
+                field,
+                new_value,
+            } => self.check_field_assignment(target, field, new_value, span),
+
+            Expr::EnumVariant {
+                enum_type,
+                variant,
+                arguments,
+            } => self.check_enum_variant(enum_type, variant, arguments, span),
+
+            Expr::Match { subject, arms } => {
+                self.check_match_expr(subject, arms, expected_type, span)
+            }
+
+            Expr::Slice { target, start, end } => self.check_slice(target, start, end, span),
+
+            Expr::StringInterpolation { parts } => self.check_string_interpolation(parts),
+
             Expr::Index { target, property } => {
                 let target_type = self.check_expression(target.as_ref(), None)?;
+                let target_type = self.expect_non_nil(target_type, target)?;
                 let property_type = self.check_expression(property.as_ref(), None)?;
+                let property_type = self.expect_non_nil(property_type, property)?;
 
                 if property_type != Type::Integer {
                     self.throw_error(VynError::TypeMismatch {
@@ -719,6 +1130,7 @@ impl<'a> TypeChecker<'a> {
                 match target_type.clone() {
                     Type::Array(element_type, _size) => Ok(*element_type),
                     Type::Sequence(element_type) => Ok(*element_type),
+                    Type::String => Ok(Type::String),
 
                     _ => {
                         self.throw_error(VynError::InvalidIndexing {
@@ -735,16 +1147,19 @@ impl<'a> TypeChecker<'a> {
                 property,
                 new_value,
             } => {
-                if let Expr::Identifier(name) = &target.node {
+                // `a::1::0 = 7` writes into `a` as much as `a::1 = [7, 8]`
+                // does, both need `let @a`
+                if let Some(root) = root_identifier(target) {
                     let ident_symbol = self.symbol_type_table.resolve_identifier(
-                        name,
+                        root,
                         target.span,
                         &mut self.errors,
                     )?;
+                    ident_symbol.usage.assigned.set(true);
 
                     if !ident_symbol.mutable {
                         self.throw_error(VynError::ImmutableMutation {
-                            identifier: name.clone(),
+                            identifier: root.to_string(),
                             span: ident_symbol.span,
                             mutation_span: span,
                         });
@@ -753,7 +1168,7 @@ impl<'a> TypeChecker<'a> {
 
                     if ident_symbol.is_static() {
                         self.throw_error(VynError::StaticMutation {
-                            identifier: name.clone(),
+                            identifier: root.to_string(),
                             mutator_span: span,
                             span: ident_symbol.span,
                         });
@@ -762,7 +1177,9 @@ impl<'a> TypeChecker<'a> {
                 }
 
                 let target_type = self.check_expression(target, None)?;
+                let target_type = self.expect_non_nil(target_type, target)?;
                 let property_type = self.check_expression(property, None)?;
+                let property_type = self.expect_non_nil(property_type, property)?;
 
                 if property_type != Type::Integer {
                     self.throw_error(VynError::TypeMismatch {
@@ -840,6 +1257,8 @@ impl<'a> TypeChecker<'a> {
                     &mut self.errors,
                 )?;
 
+                ident_symbol.usage.assigned.set(true);
+                let usage = ident_symbol.usage.clone();
                 let is_mutable = ident_symbol.mutable;
                 let is_static = ident_symbol.is_static();
                 let ident_span = ident_symbol.span;
@@ -864,8 +1283,11 @@ impl<'a> TypeChecker<'a> {
                     return Err(());
                 }
 
-                let new_value_type =
-                    self.check_expression(new_value, Some(expected_type.clone()))?;
+                let new_value_type = self.check_expression(new_value, Some(expected_type.clone()));
+                // Even a faulty assignment counts, reads after it would only
+                // repeat the error
+                usage.unassigned.set(false);
+                let new_value_type = new_value_type?;
 
                 if expected_type != new_value_type {
                     self.throw_error(VynError::TypeMismatch {
@@ -878,8 +1300,6 @@ impl<'a> TypeChecker<'a> {
 
                 Ok(expected_type)
             }
-
-            _ => throw_error(&format!("unknown expr:\n\n{:#?}", expr.node), 1),
         }
     }
 
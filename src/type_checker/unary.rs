@@ -14,6 +14,7 @@ impl TypeChecker<'_> {
         span: crate::utils::Span,
     ) -> Result<Type, ()> {
         let right_type = self.check_expression(right, None)?;
+        let right_type = self.expect_non_nil(right_type, right)?;
 
         match operator.get_token_type() {
             TokenType::Minus | TokenType::Plus => {
@@ -1,5 +1,5 @@
 use crate::{
-    bytecode::bytecode::{OpCode, read_uint8},
+    bytecode::bytecode::{OpCode, ToOpcode, read_uint8},
     error_handler::errors::VynError,
     runtime_value::values::RuntimeValue,
     vyn_vm::vm::VynVM,
@@ -8,24 +8,54 @@ use crate::{
 impl VynVM {
     #[inline]
     pub(crate) fn arith_int(&mut self, operator: u8) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
         let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
         self.ip += 3;
 
-        let left_reg = self.get_register(left_reg_idx);
-        let right_reg = self.get_register(right_reg_idx);
-
-        let l = left_reg.as_int().unwrap();
-        let r = right_reg.as_int().unwrap();
-        let result: i32 = match operator {
-            OpCode::ADD_INT => l + r,
-            OpCode::SUBTRACT_INT => l - r,
-            OpCode::MULTIPLY_INT => l * r,
-            OpCode::DIVIDE_INT => l / r,
-            OpCode::EXPONENT_INT => l.pow(r as u32),
+        let l = self.int_register(left_reg_idx, inst_pos)?;
+        let r = self.int_register(right_reg_idx, inst_pos)?;
+
+        if operator == OpCode::DIVIDE_INT && r == 0 {
+            return Err(VynError::DivisionByZero {
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+        if operator == OpCode::EXPONENT_INT && r < 0 {
+            return Err(VynError::NegativeExponent {
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        let result = if self.wrapping_arithmetic {
+            Some(match operator {
+                OpCode::ADD_INT => l.wrapping_add(r),
+                OpCode::SUBTRACT_INT => l.wrapping_sub(r),
+                OpCode::MULTIPLY_INT => l.wrapping_mul(r),
+                OpCode::DIVIDE_INT => l.wrapping_div(r),
+                OpCode::EXPONENT_INT => wrapping_pow(l, r as u64),
+
+                _ => unreachable!("Invalid arith int opcode"),
+            })
+        } else {
+            match operator {
+                OpCode::ADD_INT => l.checked_add(r),
+                OpCode::SUBTRACT_INT => l.checked_sub(r),
+                OpCode::MULTIPLY_INT => l.checked_mul(r),
+                // Only i64::MIN / -1 overflows
+                OpCode::DIVIDE_INT => l.checked_div(r),
+                OpCode::EXPONENT_INT => checked_pow(l, r as u64),
+
+                _ => unreachable!("Invalid arith int opcode"),
+            }
+        };
 
-            _ => unreachable!("Invalid arith int opcode"),
+        let Some(result) = result else {
+            return Err(VynError::IntegerOverflow {
+                expression: format!("{} {} {}", l, operator.to_opcode(), r),
+                span: self.debug_info.get_span(inst_pos),
+            });
         };
 
         self.set_register(dest, RuntimeValue::IntegerLiteral(result));
@@ -35,16 +65,14 @@ impl VynVM {
 
     #[inline]
     pub(crate) fn arith_float(&mut self, operator: u8) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
         let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
         self.ip += 3;
 
-        let left_reg = self.get_register(left_reg_idx);
-        let right_reg = self.get_register(right_reg_idx);
-
-        let l = left_reg.as_float().unwrap();
-        let r = right_reg.as_float().unwrap();
+        let l = self.float_register(left_reg_idx, inst_pos)?;
+        let r = self.float_register(right_reg_idx, inst_pos)?;
         let result = match operator {
             OpCode::ADD_FLOAT => l + r,
             OpCode::SUBTRACT_FLOAT => l - r,
@@ -62,13 +90,14 @@ impl VynVM {
 
     #[inline]
     pub(crate) fn concat_string(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
         let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
         self.ip += 3;
 
-        let left_str_idx = self.get_register(left_reg_idx).as_string_index().unwrap();
-        let right_str_idx = self.get_register(right_reg_idx).as_string_index().unwrap();
+        let left_str_idx = self.string_register(left_reg_idx, inst_pos)?;
+        let right_str_idx = self.string_register(right_reg_idx, inst_pos)?;
 
         let l = self.get_string(left_str_idx);
         let r = self.get_string(right_str_idx);
@@ -82,4 +111,59 @@ impl VynVM {
         self.set_register(dest, RuntimeValue::StringLiteral(idx));
         Ok(())
     }
+
+    #[inline]
+    pub(crate) fn check_step(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let step_reg_idx = read_uint8(&self.instructions, self.ip + 1) as usize;
+        self.ip += 1;
+
+        let step = self.get_register(step_reg_idx);
+        let is_zero = match step.as_int() {
+            Some(step) => step == 0,
+            None => step.as_float() == Some(0.0),
+        };
+
+        if is_zero {
+            return Err(VynError::ZeroLoopStep {
+                is_static: false,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Integer power that returns None on overflow, taking the full range of
+ * non-negative Int exponents (i64::checked_pow only accepts a u32)
+ * */
+pub(crate) fn checked_pow(base: i64, exponent: u64) -> Option<i64> {
+    match u32::try_from(exponent) {
+        Ok(exponent) => base.checked_pow(exponent),
+        // Past u32::MAX only 0, 1 and -1 stay in range
+        Err(_) => match base {
+            0 | 1 => Some(base),
+            -1 if exponent.is_multiple_of(2) => Some(1),
+            -1 => Some(-1),
+            _ => None,
+        },
+    }
+}
+
+/*
+ * Exponentiation by squaring that wraps on overflow, taking the full range
+ * of non-negative Int exponents (i64::wrapping_pow only accepts a u32)
+ * */
+fn wrapping_pow(mut base: i64, mut exponent: u64) -> i64 {
+    let mut result: i64 = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+    result
 }
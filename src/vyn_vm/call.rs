@@ -0,0 +1,186 @@
+use crate::{
+    bytecode::bytecode::{read_uint8, read_uint16, read_uint32},
+    error_handler::errors::VynError,
+    type_checker::type_checker::Type,
+    vyn_vm::{
+        natives::{MAX_NATIVE_ARITY, NATIVES, NativeFailure},
+        vm::{MAX_REGISTERS, NIL, VynVM},
+    },
+};
+
+// Deepest call chain allowed before raising a stack overflow
+pub const MAX_CALL_DEPTH: usize = 1024;
+
+/*
+ * Caller state saved by CALL and restored by RETURN
+ * */
+pub struct CallFrame {
+    // Offset of the instruction following the CALL
+    pub return_ip: usize,
+    // Register window base of the caller
+    pub base: usize,
+    // Slot base of the caller
+    pub slot_base: usize,
+    // Caller register receiving the return value
+    pub dest: u8,
+}
+
+impl VynVM {
+    /*
+     * Enters a function, shifting the register window so the callee's
+     * r0 is the caller's r[window]
+     *
+     * -- Operands: [dest, target, window, argc]
+     * */
+    #[inline]
+    pub(crate) fn call(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1);
+        let target = read_uint16(&self.instructions, self.ip + 2) as usize;
+        let window = read_uint8(&self.instructions, self.ip + 4) as usize;
+        self.ip += 5;
+
+        self.enter_function(inst_pos, dest, target, window)
+    }
+
+    /*
+     * Same as `call` with a 4-byte target
+     *
+     * -- Operands: [dest, target, window, argc]
+     * */
+    #[inline]
+    pub(crate) fn call_wide(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1);
+        let target = read_uint32(&self.instructions, self.ip + 2) as usize;
+        let window = read_uint8(&self.instructions, self.ip + 6) as usize;
+        self.ip += 7;
+
+        self.enter_function(inst_pos, dest, target, window)
+    }
+
+    #[inline]
+    fn enter_function(
+        &mut self,
+        inst_pos: usize,
+        dest: u8,
+        target: usize,
+        window: usize,
+    ) -> Result<(), VynError> {
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(VynError::StackOverflow {
+                depth: MAX_CALL_DEPTH,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        self.frames.push(CallFrame {
+            return_ip: self.ip + 1,
+            base: self.base,
+            slot_base: self.slot_base,
+            dest,
+        });
+
+        self.base += window;
+        // Every slot past the caller's belongs to the callee
+        self.slot_base = self.slots.len();
+        let needed = self.base + MAX_REGISTERS as usize;
+        if self.registers.len() < needed {
+            self.registers.resize(needed, NIL);
+        }
+
+        self.ip = target;
+        Ok(())
+    }
+
+    /*
+     * Leaves the current function and writes its result into the
+     * caller's destination register
+     *
+     * -- Operands: [value_reg]
+     * */
+    #[inline]
+    pub(crate) fn return_from_call(&mut self) -> Result<(), VynError> {
+        let value_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let value = self.get_register(value_reg);
+
+        // The compiler only emits RETURN inside functions, a `.vync` file
+        // can still reach one from the top level
+        let Some(frame) = self.frames.pop() else {
+            return Err(VynError::InvalidBytecode {
+                offset: self.ip,
+                reason: "RETURN executed outside of a function".to_string(),
+                span: self.debug_info.get_span(self.ip),
+            });
+        };
+
+        self.base = frame.base;
+        self.slots.truncate(self.slot_base);
+        self.slot_base = frame.slot_base;
+        self.set_register(frame.dest as usize, value);
+
+        // The main loop advances past the opcode byte
+        self.ip = frame.return_ip - 1;
+        Ok(())
+    }
+
+    /*
+     * Runs a native function on the arguments in window..window+argc
+     *
+     * No frame is pushed, the result goes straight into dest
+     *
+     * -- Operands: [dest, native, window, argc]
+     * */
+    #[inline]
+    pub(crate) fn call_native(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let native = &NATIVES[read_uint16(&self.instructions, self.ip + 2) as usize];
+        let window = read_uint8(&self.instructions, self.ip + 4) as usize;
+        let argc = read_uint8(&self.instructions, self.ip + 5) as usize;
+        self.ip += 5;
+
+        // The arguments stay in their registers while the native runs, so
+        // strings it allocates can't collect them
+        let start = self.base + window;
+        let mut args = [NIL; MAX_NATIVE_ARITY];
+        args[..argc].copy_from_slice(&self.registers[start..start + argc]);
+
+        // Natives unwrap their arguments, the verifier only checked the count
+        for (offset, (arg, parameter)) in args.iter().zip(&native.parameters).enumerate() {
+            let (fits, expected) = match parameter {
+                Type::Integer => (arg.as_int().is_some(), "an integer"),
+                Type::Float => (arg.is_float(), "a float"),
+                Type::String => (arg.is_string(), "a string"),
+                Type::Bool => (arg.as_bool().is_some(), "a boolean"),
+                _ => (true, ""),
+            };
+
+            if !fits {
+                return Err(self.wrong_register_type(window + offset, expected, inst_pos));
+            }
+        }
+
+        let span = self.debug_info.get_span(inst_pos);
+        let value = (native.function)(self, &args[..argc]).map_err(|failure| match failure {
+            NativeFailure::Invalid(message) => VynError::NativeError {
+                name: native.name.to_string(),
+                message,
+                span,
+            },
+            NativeFailure::Io {
+                action,
+                target,
+                error,
+            } => VynError::IoError {
+                action: action.to_string(),
+                target,
+                reason: error.to_string(),
+                span,
+            },
+        })?;
+
+        self.set_register(dest, value);
+        Ok(())
+    }
+}
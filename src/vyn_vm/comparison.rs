@@ -1,22 +1,21 @@
 use crate::{
     bytecode::bytecode::{OpCode, read_uint8},
     error_handler::errors::VynError,
+    runtime_value::values::{HeapObject, RuntimeValue},
     vyn_vm::vm::VynVM,
 };
 
 impl VynVM {
     #[inline]
     pub(crate) fn compare_int(&mut self, opcode: u8) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
         let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
         self.ip += 3;
 
-        let left_reg = self.get_register(left_reg_idx);
-        let right_reg = self.get_register(right_reg_idx);
-
-        let l = left_reg.as_int().unwrap();
-        let r = right_reg.as_int().unwrap();
+        let l = self.int_register(left_reg_idx, inst_pos)?;
+        let r = self.int_register(right_reg_idx, inst_pos)?;
         let res = match opcode {
             OpCode::LESS_INT => l < r,
             OpCode::LESS_EQUAL_INT => l <= r,
@@ -32,16 +31,14 @@ impl VynVM {
 
     #[inline]
     pub(crate) fn compare_float(&mut self, opcode: u8) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
         let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
         self.ip += 3;
 
-        let left_reg = self.get_register(left_reg_idx);
-        let right_reg = self.get_register(right_reg_idx);
-
-        let l = left_reg.as_float().unwrap();
-        let r = right_reg.as_float().unwrap();
+        let l = self.float_register(left_reg_idx, inst_pos)?;
+        let r = self.float_register(right_reg_idx, inst_pos)?;
         let res = match opcode {
             OpCode::LESS_FLOAT => l < r,
             OpCode::LESS_EQUAL_FLOAT => l <= r,
@@ -66,8 +63,8 @@ impl VynVM {
         let right_reg = self.get_register(right_reg_idx);
 
         let res = match opcode {
-            OpCode::EQUAL => left_reg == right_reg,
-            OpCode::NOT_EQUAL => left_reg != right_reg,
+            OpCode::EQUAL => self.values_equal(left_reg, right_reg),
+            OpCode::NOT_EQUAL => !self.values_equal(left_reg, right_reg),
 
             _ => unreachable!("Invalid compare_equality opcode"),
         };
@@ -75,4 +72,43 @@ impl VynVM {
         self.set_register(dest, self.runtime_bool(res));
         Ok(())
     }
+
+    /*
+     * Equality behind `==` and `!=`
+     *
+     * Strings are equal when their contents are, enum values when they hold
+     * the same variant and equal payloads, any other heap value is only
+     * equal to itself
+     * */
+    fn values_equal(&self, left: RuntimeValue, right: RuntimeValue) -> bool {
+        match (left, right) {
+            (RuntimeValue::StringLiteral(l), RuntimeValue::StringLiteral(r)) => {
+                self.get_string(l) == self.get_string(r)
+            }
+            (RuntimeValue::EnumLiteral(l), RuntimeValue::EnumLiteral(r)) => {
+                match (&self.heap_table[l], &self.heap_table[r]) {
+                    (
+                        HeapObject::Enum {
+                            tag: left_tag,
+                            payload: left_payload,
+                            ..
+                        },
+                        HeapObject::Enum {
+                            tag: right_tag,
+                            payload: right_payload,
+                            ..
+                        },
+                    ) => {
+                        left_tag == right_tag
+                            && left_payload
+                                .iter()
+                                .zip(right_payload)
+                                .all(|(l, r)| self.values_equal(*l, *r))
+                    }
+                    _ => false,
+                }
+            }
+            _ => left == right,
+        }
+    }
 }
@@ -0,0 +1,315 @@
+use crate::{
+    bytecode::bytecode::{read_uint8, read_uint32},
+    error_handler::errors::VynError,
+    runtime_value::values::{HeapObject, RuntimeValue},
+    vyn_vm::vm::{NIL, VynVM},
+};
+
+impl VynVM {
+    #[inline]
+    pub(crate) fn array_new_fixed(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let size = read_uint32(&self.instructions, self.ip + 2) as usize;
+        self.ip += 5;
+
+        let idx = self.alloc_heap(HeapObject::Array {
+            elements: vec![NIL; size],
+        });
+
+        self.set_register(dest, RuntimeValue::ArrayLiteral(idx));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn array_new_dynamic(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let capacity = read_uint32(&self.instructions, self.ip + 2) as usize;
+        self.ip += 5;
+
+        let idx = self.alloc_heap(HeapObject::Sequence {
+            elements: Vec::with_capacity(capacity),
+        });
+
+        self.set_register(dest, RuntimeValue::SequenceLiteral(idx));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn array_set(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let array_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let index = read_uint32(&self.instructions, self.ip + 2) as i64;
+        let value_reg = read_uint8(&self.instructions, self.ip + 6) as usize;
+        self.ip += 6;
+
+        let value = self.get_register(value_reg);
+        self.store_element(array_reg, index, value, inst_pos)
+    }
+
+    #[inline]
+    pub(crate) fn array_set_reg(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let array_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let index_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let value_reg = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let index = self.int_register(index_reg, inst_pos)?;
+        let value = self.get_register(value_reg);
+        self.store_element(array_reg, index, value, inst_pos)
+    }
+
+    #[inline]
+    pub(crate) fn array_get(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let array_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let index_reg = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let heap_idx = self.get_heap_index(array_reg, inst_pos)?;
+        let index = self.int_register(index_reg, inst_pos)?;
+        let elements = self.heap_table[heap_idx].elements();
+
+        if index < 0 || index as usize >= elements.len() {
+            return Err(VynError::IndexOutOfBounds {
+                size: elements.len(),
+                idx: index,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        let value = elements[index as usize];
+        self.set_register(dest, value);
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn array_push(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let array_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let value_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        let heap_idx = self.get_heap_index(array_reg, inst_pos)?;
+        self.check_not_frozen(heap_idx, inst_pos)?;
+        let value = self.get_register(value_reg);
+
+        let HeapObject::Sequence { elements } = &mut self.heap_table[heap_idx] else {
+            return Err(self.wrong_register_type(array_reg, "a sequence", inst_pos));
+        };
+
+        elements.push(value);
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn struct_new(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let layout = read_uint32(&self.instructions, self.ip + 2) as usize;
+        let size = read_uint8(&self.instructions, self.ip + 6) as usize;
+        self.ip += 6;
+
+        let idx = self.alloc_heap(HeapObject::Struct {
+            layout,
+            fields: vec![NIL; size],
+        });
+
+        self.set_register(dest, RuntimeValue::StructLiteral(idx));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn struct_get(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let struct_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let field = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let heap_idx = self.get_record_index(struct_reg, inst_pos)?;
+        let Some(&value) = self.heap_table[heap_idx].elements().get(field) else {
+            return Err(self.missing_field(field, inst_pos));
+        };
+
+        self.set_register(dest, value);
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn struct_set(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let struct_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let field = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let value_reg = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let heap_idx = self.get_record_index(struct_reg, inst_pos)?;
+        self.check_not_frozen(heap_idx, inst_pos)?;
+        let value = self.get_register(value_reg);
+
+        let Some(slot) = self.heap_table[heap_idx].elements_mut().get_mut(field) else {
+            return Err(self.missing_field(field, inst_pos));
+        };
+
+        *slot = value;
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn enum_new(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let name = read_uint32(&self.instructions, self.ip + 2) as usize;
+        let tag = read_uint8(&self.instructions, self.ip + 6) as usize;
+        let size = read_uint8(&self.instructions, self.ip + 7) as usize;
+        self.ip += 7;
+
+        let idx = self.alloc_heap(HeapObject::Enum {
+            name,
+            tag,
+            payload: vec![NIL; size],
+        });
+
+        self.set_register(dest, RuntimeValue::EnumLiteral(idx));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn enum_tag(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let enum_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        let tag = match self.get_register(enum_reg) {
+            RuntimeValue::EnumLiteral(idx) => match &self.heap_table[idx] {
+                HeapObject::Enum { tag, .. } => *tag,
+                object => unreachable!("Expected an enum at heap index {idx}, got {:?}", object),
+            },
+            _ => return Err(self.wrong_register_type(enum_reg, "an enum", inst_pos)),
+        };
+
+        self.set_register(dest, RuntimeValue::IntegerLiteral(tag as i64));
+        Ok(())
+    }
+
+    /*
+     * Writes a value into an array/sequence element
+     *
+     * -- Arguments: [&mut self],
+     *               array_reg - register holding the array
+     *               index - element index (may be negative, which is out of bounds)
+     *               value - value to store
+     *               inst_pos - bytecode offset of the instruction (for error spans)
+     * -- Return value: Err(IndexOutOfBounds) if the index is outside the array
+     * */
+    fn store_element(
+        &mut self,
+        array_reg: usize,
+        index: i64,
+        value: RuntimeValue,
+        inst_pos: usize,
+    ) -> Result<(), VynError> {
+        let heap_idx = self.get_heap_index(array_reg, inst_pos)?;
+        self.check_not_frozen(heap_idx, inst_pos)?;
+        let elements = self.heap_table[heap_idx].elements_mut();
+
+        if index < 0 || index as usize >= elements.len() {
+            return Err(VynError::IndexOutOfBounds {
+                size: elements.len(),
+                idx: index,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        elements[index as usize] = value;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn get_heap_index(&self, reg: usize, inst_pos: usize) -> Result<usize, VynError> {
+        match self.get_register(reg) {
+            RuntimeValue::ArrayLiteral(idx) | RuntimeValue::SequenceLiteral(idx) => Ok(idx),
+            _ => Err(self.wrong_register_type(reg, "an array", inst_pos)),
+        }
+    }
+
+    /*
+     * Heap index of a struct, or of an enum value whose payload slots are
+     * read and written like fields
+     * */
+    #[inline(always)]
+    fn get_record_index(&self, reg: usize, inst_pos: usize) -> Result<usize, VynError> {
+        match self.get_register(reg) {
+            RuntimeValue::StructLiteral(idx) | RuntimeValue::EnumLiteral(idx) => Ok(idx),
+            _ => Err(self.wrong_register_type(reg, "a struct or enum", inst_pos)),
+        }
+    }
+
+    /*
+     * Field operands are literals the verifier can't match to a layout, the
+     * struct or enum payload they index is only known here
+     * */
+    #[cold]
+    fn missing_field(&self, field: usize, inst_pos: usize) -> VynError {
+        VynError::InvalidBytecode {
+            offset: inst_pos,
+            reason: format!("field {field} does not exist"),
+            span: self.debug_info.get_span(inst_pos),
+        }
+    }
+
+    pub(crate) fn alloc_heap(&mut self, object: HeapObject) -> usize {
+        self.heap_table.push(object);
+        self.frozen.push(false);
+        self.heap_table.len() - 1
+    }
+
+    /*
+     * Marks the value in a register and every heap object reachable from it
+     * as read-only, run when the value is bound with `let`
+     *
+     * Values are shared, so without this a mutable name bound to the same
+     * value could change what the `let` binding holds. Objects that are
+     * already frozen are skipped, which also stops at cycles
+     * */
+    #[inline]
+    pub(crate) fn freeze(&mut self) -> Result<(), VynError> {
+        let value_reg = read_uint8(&self.instructions, self.ip + 1) as usize;
+        self.ip += 1;
+
+        let mut pending = vec![self.get_register(value_reg)];
+        while let Some(value) = pending.pop() {
+            let (RuntimeValue::ArrayLiteral(idx)
+            | RuntimeValue::SequenceLiteral(idx)
+            | RuntimeValue::StructLiteral(idx)
+            | RuntimeValue::EnumLiteral(idx)) = value
+            else {
+                continue;
+            };
+
+            if self.frozen[idx] {
+                continue;
+            }
+
+            self.frozen[idx] = true;
+            pending.extend_from_slice(self.heap_table[idx].elements());
+        }
+
+        Ok(())
+    }
+
+    /*
+     * Refuses writes to objects a `let` binding can reach
+     * */
+    #[inline(always)]
+    fn check_not_frozen(&self, heap_idx: usize, inst_pos: usize) -> Result<(), VynError> {
+        if self.frozen[heap_idx] {
+            return Err(VynError::FrozenMutation {
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    bytecode::bytecode::{read_uint8, read_uint16},
+    bytecode::bytecode::{read_uint8, read_uint16, read_uint32},
     error_handler::errors::VynError,
     runtime_value::values::RuntimeValue,
     vyn_vm::vm::VynVM,
@@ -17,6 +17,17 @@ impl VynVM {
         Ok(())
     }
 
+    #[inline(always)]
+    pub(crate) fn load_constant_wide(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let const_idx = read_uint32(&self.instructions, self.ip + 2) as usize;
+        self.ip += 5;
+
+        let constant = self.constants[const_idx];
+        self.set_register(dest, constant);
+        Ok(())
+    }
+
     #[inline(always)]
     pub(crate) fn load_string(&mut self) -> Result<(), VynError> {
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
@@ -28,6 +39,17 @@ impl VynVM {
         Ok(())
     }
 
+    #[inline(always)]
+    pub(crate) fn load_string_wide(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let str_idx = read_uint32(&self.instructions, self.ip + 2) as usize;
+        self.ip += 5;
+
+        let str = RuntimeValue::StringLiteral(str_idx);
+        self.set_register(dest, str);
+        Ok(())
+    }
+
     #[inline(always)]
     pub(crate) fn load_static(&mut self, static_val: RuntimeValue) -> Result<(), VynError> {
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
@@ -8,7 +8,7 @@ impl VynVM {
         self.ip += 2;
 
         let src_reg = self.get_register(src);
-        self.set_register(dest, self.runtime_bool(self.is_truthy(src_reg)));
+        self.set_register(dest, self.runtime_bool(!self.is_truthy(src_reg)));
         Ok(())
     }
 }
@@ -1,7 +1,12 @@
 pub mod arithmeric;
+pub mod call;
 pub mod comparison;
+pub mod heap;
 pub mod helpers;
 pub mod loaders;
 pub mod logical;
+pub mod natives;
+pub mod string_heap;
+pub mod strings;
 pub mod unary;
 pub mod vm;
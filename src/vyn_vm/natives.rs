@@ -0,0 +1,421 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, BufRead, Write},
+    path::Path,
+    sync::LazyLock,
+};
+
+use crate::{
+    runtime_value::values::RuntimeValue,
+    type_checker::type_checker::Type,
+    vyn_vm::vm::{NIL, VynVM},
+};
+
+// Most arguments any native takes, CALL_NATIVE copies them out of the
+// register window into a buffer of this size
+pub const MAX_NATIVE_ARITY: usize = 3;
+
+// Longest string `repeat` builds, in bytes, so a huge count is reported
+// instead of exhausting memory
+const MAX_REPEAT_LENGTH: usize = 1 << 28;
+
+pub type NativeResult = Result<RuntimeValue, NativeFailure>;
+
+/*
+ * Why a native function stopped the program
+ * */
+pub enum NativeFailure {
+    // The arguments can't be handled, the message says why
+    Invalid(String),
+    // A read or write failed, `target` is a path or a stream name
+    Io {
+        action: &'static str,
+        target: String,
+        error: io::Error,
+    },
+}
+
+/*
+ * A function implemented by the VM, called with CALL_NATIVE
+ *
+ * Arguments arrive already type checked against `parameters`. An Err stops
+ * the program with a runtime error
+ * */
+pub struct NativeFn {
+    pub name: &'static str,
+    pub parameters: Vec<Type>,
+    pub return_type: Type,
+    pub function: fn(&mut VynVM, &[RuntimeValue]) -> NativeResult,
+}
+
+impl NativeFn {
+    /*
+     * Formats the signature for diagnostics, `min(Int, Int) -> Int`
+     * */
+    pub fn signature(&self) -> String {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}({}) -> {}", self.name, parameters, self.return_type)
+    }
+}
+
+/*
+ * Every native function, CALL_NATIVE refers to them by position
+ *
+ * A name may appear more than once with different parameter types, the
+ * type checker picks the entry matching the arguments. Compiled `.vync`
+ * files store these positions, so new entries only ever go at the end
+ * */
+pub static NATIVES: LazyLock<Vec<NativeFn>> = LazyLock::new(|| {
+    use Type::{Bool, Float, Integer as Int, Nil, String as Str};
+    let optional = |t: Type| Type::Optional(Box::new(t));
+
+    vec![
+        // Math
+        native("sqrt", vec![Float], Float, sqrt),
+        native("floor", vec![Float], Float, floor),
+        native("ceil", vec![Float], Float, ceil),
+        native("round", vec![Float], Float, round),
+        native("pow", vec![Float, Float], Float, pow),
+        native("abs", vec![Int], Int, abs_int),
+        native("abs", vec![Float], Float, abs_float),
+        native("min", vec![Int, Int], Int, min_int),
+        native("min", vec![Float, Float], Float, min_float),
+        native("max", vec![Int, Int], Int, max_int),
+        native("max", vec![Float, Float], Float, max_float),
+        // Strings
+        native("upper", vec![Str], Str, upper),
+        native("lower", vec![Str], Str, lower),
+        native("trim", vec![Str], Str, trim),
+        native("contains", vec![Str, Str], Bool, contains),
+        native("starts_with", vec![Str, Str], Bool, starts_with),
+        native("ends_with", vec![Str, Str], Bool, ends_with),
+        native("index_of", vec![Str, Str], optional(Int), index_of),
+        native("replace", vec![Str, Str, Str], Str, replace),
+        native("repeat", vec![Str, Int], Str, repeat),
+        native("parse_int", vec![Str], optional(Int), parse_int),
+        native("parse_float", vec![Str], optional(Float), parse_float),
+        // Time
+        native("clock", vec![], Float, clock),
+        // I/O
+        native("read_line", vec![], optional(Str), read_line),
+        native("print", vec![Str], Nil, print),
+        native("eprint", vec![Str], Nil, eprint),
+        native("eprintln", vec![Str], Nil, eprintln),
+        native("read_file", vec![Str], Str, read_file),
+        native("write_file", vec![Str, Str], Nil, write_file),
+        native("append_file", vec![Str, Str], Nil, append_file),
+        native("file_exists", vec![Str], Bool, file_exists),
+    ]
+});
+
+fn native(
+    name: &'static str,
+    parameters: Vec<Type>,
+    return_type: Type,
+    function: fn(&mut VynVM, &[RuntimeValue]) -> NativeResult,
+) -> NativeFn {
+    debug_assert!(parameters.len() <= MAX_NATIVE_ARITY);
+
+    NativeFn {
+        name,
+        parameters,
+        return_type,
+        function,
+    }
+}
+
+pub fn is_native(name: &str) -> bool {
+    NATIVES.iter().any(|native| native.name == name)
+}
+
+/*
+ * Every entry sharing a name, in table order
+ * */
+pub fn overloads(name: &str) -> Vec<&'static NativeFn> {
+    NATIVES
+        .iter()
+        .filter(|native| native.name == name)
+        .collect()
+}
+
+/*
+ * Finds the entry a call with these argument types refers to
+ *
+ * -- Return value: Some((index into NATIVES, entry))
+ * */
+pub fn resolve(name: &str, arguments: &[Type]) -> Option<(usize, &'static NativeFn)> {
+    NATIVES
+        .iter()
+        .enumerate()
+        .find(|(_, native)| native.name == name && native.parameters == arguments)
+}
+
+fn float(value: f64) -> NativeResult {
+    Ok(RuntimeValue::FloatLiteral(value))
+}
+
+fn int(value: i64) -> NativeResult {
+    Ok(RuntimeValue::IntegerLiteral(value))
+}
+
+fn text(vm: &mut VynVM, value: String) -> NativeResult {
+    Ok(RuntimeValue::StringLiteral(vm.intern_string(value)))
+}
+
+fn string_arg(vm: &VynVM, value: RuntimeValue) -> &str {
+    vm.get_string(value.as_string_index().unwrap())
+}
+
+fn sqrt(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().sqrt())
+}
+
+fn floor(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().floor())
+}
+
+fn ceil(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().ceil())
+}
+
+fn round(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().round())
+}
+
+fn pow(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(
+        args[0]
+            .as_float()
+            .unwrap()
+            .powf(args[1].as_float().unwrap()),
+    )
+}
+
+fn abs_int(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let value = args[0].as_int().unwrap();
+
+    if vm.wrapping_arithmetic {
+        return int(value.wrapping_abs());
+    }
+    match value.checked_abs() {
+        Some(value) => int(value),
+        None => Err(NativeFailure::Invalid(format!(
+            "the absolute value of {value} does not fit in an Int"
+        ))),
+    }
+}
+
+fn abs_float(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().abs())
+}
+
+fn min_int(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    int(args[0].as_int().unwrap().min(args[1].as_int().unwrap()))
+}
+
+fn min_float(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().min(args[1].as_float().unwrap()))
+}
+
+fn max_int(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    int(args[0].as_int().unwrap().max(args[1].as_int().unwrap()))
+}
+
+fn max_float(_: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    float(args[0].as_float().unwrap().max(args[1].as_float().unwrap()))
+}
+
+fn upper(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let result = string_arg(vm, args[0]).to_uppercase();
+    text(vm, result)
+}
+
+fn lower(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let result = string_arg(vm, args[0]).to_lowercase();
+    text(vm, result)
+}
+
+fn trim(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let result = string_arg(vm, args[0]).trim().to_string();
+    text(vm, result)
+}
+
+fn contains(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let found = string_arg(vm, args[0]).contains(string_arg(vm, args[1]));
+    Ok(vm.runtime_bool(found))
+}
+
+fn starts_with(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let found = string_arg(vm, args[0]).starts_with(string_arg(vm, args[1]));
+    Ok(vm.runtime_bool(found))
+}
+
+fn ends_with(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let found = string_arg(vm, args[0]).ends_with(string_arg(vm, args[1]));
+    Ok(vm.runtime_bool(found))
+}
+
+/*
+ * Position of the first occurrence, counted in characters like `::`
+ * */
+fn index_of(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let string = string_arg(vm, args[0]);
+
+    match string.find(string_arg(vm, args[1])) {
+        Some(byte_idx) => int(string[..byte_idx].chars().count() as i64),
+        None => Ok(NIL),
+    }
+}
+
+fn replace(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let result = string_arg(vm, args[0]).replace(string_arg(vm, args[1]), string_arg(vm, args[2]));
+    text(vm, result)
+}
+
+fn repeat(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let count = args[1].as_int().unwrap();
+    let Ok(count) = usize::try_from(count) else {
+        return Err(NativeFailure::Invalid(format!(
+            "cannot repeat a string {count} times"
+        )));
+    };
+
+    let string = string_arg(vm, args[0]);
+    if string
+        .len()
+        .checked_mul(count)
+        .is_none_or(|length| length > MAX_REPEAT_LENGTH)
+    {
+        return Err(NativeFailure::Invalid(format!(
+            "repeating the string {count} times is longer than {MAX_REPEAT_LENGTH} bytes"
+        )));
+    }
+
+    let result = string.repeat(count);
+    text(vm, result)
+}
+
+fn parse_int(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    match string_arg(vm, args[0]).trim().parse::<i64>() {
+        Ok(value) => int(value),
+        Err(_) => Ok(NIL),
+    }
+}
+
+fn parse_float(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    match string_arg(vm, args[0]).trim().parse::<f64>() {
+        Ok(value) => float(value),
+        Err(_) => Ok(NIL),
+    }
+}
+
+/*
+ * Seconds since the VM started, for timing parts of a program
+ * */
+fn clock(vm: &mut VynVM, _: &[RuntimeValue]) -> NativeResult {
+    float(vm.started.elapsed().as_secs_f64())
+}
+
+/*
+ * Next line of standard input without its line ending, nil once input ends
+ * */
+fn read_line(vm: &mut VynVM, _: &[RuntimeValue]) -> NativeResult {
+    let mut line = String::new();
+
+    let read = io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|error| io_failure("read", "<stdin>", error))?;
+    if read == 0 {
+        return Ok(NIL);
+    }
+
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    text(vm, line)
+}
+
+/*
+ * Writes to stdout without a newline, flushing so prompts show up before
+ * the next `read_line`
+ * */
+fn print(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let mut out = io::stdout().lock();
+
+    out.write_all(string_arg(vm, args[0]).as_bytes())
+        .and_then(|_| out.flush())
+        .map_err(|error| io_failure("write to", "<stdout>", error))?;
+    Ok(NIL)
+}
+
+fn eprint(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    io::stderr()
+        .lock()
+        .write_all(string_arg(vm, args[0]).as_bytes())
+        .map_err(|error| io_failure("write to", "<stderr>", error))?;
+    Ok(NIL)
+}
+
+fn eprintln(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let mut err = io::stderr().lock();
+
+    err.write_all(string_arg(vm, args[0]).as_bytes())
+        .and_then(|_| err.write_all(b"\n"))
+        .map_err(|error| io_failure("write to", "<stderr>", error))?;
+    Ok(NIL)
+}
+
+fn read_file(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let path = string_arg(vm, args[0]);
+
+    let contents = fs::read_to_string(path).map_err(|error| io_failure("read", path, error))?;
+    text(vm, contents)
+}
+
+/*
+ * Creates the file, or replaces everything in it
+ * */
+fn write_file(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let path = string_arg(vm, args[0]);
+
+    fs::write(path, string_arg(vm, args[1])).map_err(|error| io_failure("write", path, error))?;
+    Ok(NIL)
+}
+
+/*
+ * Adds to the end of the file, creating it if needed
+ * */
+fn append_file(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let path = string_arg(vm, args[0]);
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(string_arg(vm, args[1]).as_bytes()))
+        .map_err(|error| io_failure("write", path, error))?;
+    Ok(NIL)
+}
+
+fn file_exists(vm: &mut VynVM, args: &[RuntimeValue]) -> NativeResult {
+    let exists = Path::new(string_arg(vm, args[0])).is_file();
+    Ok(vm.runtime_bool(exists))
+}
+
+fn io_failure(action: &'static str, target: &str, error: io::Error) -> NativeFailure {
+    NativeFailure::Io {
+        action,
+        target: target.to_string(),
+        error,
+    }
+}
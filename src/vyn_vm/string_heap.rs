@@ -0,0 +1,230 @@
+use std::mem;
+
+use crate::{
+    runtime_value::values::{HeapObject, RuntimeValue},
+    utils::print_info,
+    vyn_vm::vm::VynVM,
+};
+
+// Runtime strings allocated before the first collection
+const INITIAL_COLLECTION_THRESHOLD: usize = 1024;
+
+/*
+ * Storage behind RuntimeValue::StringLiteral
+ *
+ * The first `pinned` entries are the program's string table, constants the
+ * compiler already deduplicated. They live as long as the program does.
+ * Strings built at runtime go after them and are reclaimed by
+ * `VynVM::collect_strings` once nothing references them, their slots are
+ * reused by later allocations
+ * */
+pub struct StringHeap {
+    strings: Vec<String>,
+    pinned: usize,
+    // Whether each slot past `pinned` holds a live string
+    in_use: Vec<bool>,
+    free: Vec<usize>,
+    // Runtime strings allocated since the last collection
+    allocated_since_collection: usize,
+    threshold: usize,
+    stats: HeapStats,
+}
+
+/*
+ * Counters reported by `--heap-stats`
+ * */
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeapStats {
+    pub allocations: usize,
+    pub collections: usize,
+    pub freed: usize,
+    pub peak_live: usize,
+}
+
+impl StringHeap {
+    pub fn new(constants: Vec<String>) -> Self {
+        Self {
+            pinned: constants.len(),
+            strings: constants,
+            in_use: Vec::new(),
+            free: Vec::new(),
+            allocated_since_collection: 0,
+            threshold: INITIAL_COLLECTION_THRESHOLD,
+            stats: HeapStats::default(),
+        }
+    }
+
+    /*
+     * Replaces the constants with a new program's string table
+     *
+     * The REPL compiles each input against the previous table, so the new
+     * one already holds every string still referenced. All of them become
+     * constants, which keeps strings stored in globals valid
+     * */
+    pub fn load_constants(&mut self, constants: Vec<String>) {
+        self.pinned = constants.len();
+        self.strings = constants;
+        self.in_use.clear();
+        self.free.clear();
+        self.allocated_since_collection = 0;
+    }
+
+    /*
+     * Stores a string built at runtime
+     *
+     * -- Return value: usize - index to wrap in RuntimeValue::StringLiteral
+     * */
+    pub fn alloc(&mut self, string: String) -> usize {
+        self.stats.allocations += 1;
+        self.allocated_since_collection += 1;
+
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.strings[idx] = string;
+                idx
+            }
+            None => {
+                self.strings.push(string);
+                self.in_use.push(false);
+                self.strings.len() - 1
+            }
+        };
+        self.in_use[idx - self.pinned] = true;
+
+        self.stats.peak_live = self.stats.peak_live.max(self.live_runtime_strings());
+        idx
+    }
+
+    pub fn get(&self, idx: usize) -> &str {
+        &self.strings[idx]
+    }
+
+    pub fn as_slice(&self) -> &[String] {
+        &self.strings
+    }
+
+    pub fn needs_collection(&self) -> bool {
+        self.allocated_since_collection >= self.threshold
+    }
+
+    /*
+     * Frees every runtime string not marked as reachable
+     *
+     * The next collection runs once as many strings were allocated as
+     * survived this one, so collections get rarer as the live set grows
+     *
+     * -- Arguments: [&mut self], marks - reachability of every index
+     * -- Return value: void
+     * */
+    pub fn sweep(&mut self, marks: &[bool]) {
+        self.free.clear();
+
+        for (idx, marked) in marks.iter().enumerate().skip(self.pinned) {
+            if *marked {
+                continue;
+            }
+
+            let slot = &mut self.in_use[idx - self.pinned];
+            if *slot {
+                *slot = false;
+                self.stats.freed += 1;
+                // Drops the text, the empty slot waits for the next allocation
+                mem::take(&mut self.strings[idx]);
+            }
+            self.free.push(idx);
+        }
+
+        self.stats.collections += 1;
+        self.allocated_since_collection = 0;
+        self.threshold = INITIAL_COLLECTION_THRESHOLD.max(self.live_runtime_strings());
+    }
+
+    pub fn live_runtime_strings(&self) -> usize {
+        self.strings.len() - self.pinned - self.free.len()
+    }
+
+    pub fn constants(&self) -> usize {
+        self.pinned
+    }
+
+    pub fn stats(&self) -> HeapStats {
+        self.stats
+    }
+}
+
+impl VynVM {
+    /*
+     * Mark and sweep over the string heap
+     *
+     * Roots are the whole register file, spill slots, globals and the
+     * constant pool, plus every heap object reachable from them. Stale
+     * registers above the current window are scanned too, which can only
+     * keep a dead string alive a little longer, never free a live one
+     *
+     * -- Notes:
+     * # Only called from `intern_string`, before the new string's index is
+     *   written anywhere, so every operand of the running instruction is
+     *   still in a register
+     * */
+    pub(crate) fn collect_strings(&mut self) {
+        let mut marks = vec![false; self.strings.as_slice().len()];
+        let mut visited = vec![false; self.heap_table.len()];
+        let mut pending: Vec<RuntimeValue> = self
+            .registers
+            .iter()
+            .chain(&self.slots)
+            .chain(self.globals.iter().flatten())
+            .chain(&self.constants)
+            .copied()
+            .collect();
+
+        while let Some(value) = pending.pop() {
+            let heap_idx = match value {
+                RuntimeValue::StringLiteral(idx) => {
+                    marks[idx] = true;
+                    continue;
+                }
+                RuntimeValue::ArrayLiteral(idx)
+                | RuntimeValue::SequenceLiteral(idx)
+                | RuntimeValue::StructLiteral(idx)
+                | RuntimeValue::EnumLiteral(idx) => idx,
+                _ => continue,
+            };
+
+            if mem::replace(&mut visited[heap_idx], true) {
+                continue;
+            }
+
+            let object = &self.heap_table[heap_idx];
+            match object {
+                HeapObject::Struct { layout: name, .. } | HeapObject::Enum { name, .. } => {
+                    marks[*name] = true;
+                }
+                HeapObject::Array { .. } | HeapObject::Sequence { .. } => {}
+            }
+            pending.extend_from_slice(object.elements());
+        }
+
+        self.strings.sweep(&marks);
+    }
+
+    /*
+     * Prints the `--heap-stats` report
+     * */
+    pub fn report_heap_stats(&self) {
+        let stats = self.strings.stats();
+
+        print_info(&format!(
+            "Strings: {} constants, {} allocated at runtime, {} live, {} at peak",
+            self.strings.constants(),
+            stats.allocations,
+            self.strings.live_runtime_strings(),
+            stats.peak_live
+        ));
+        print_info(&format!(
+            "Collections: {}, {} strings freed",
+            stats.collections, stats.freed
+        ));
+        print_info(&format!("Heap objects: {}", self.heap_table.len()));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    bytecode::bytecode::{OpCode, read_uint8},
+    error_handler::errors::VynError,
+    runtime_value::values::RuntimeValue,
+    vyn_vm::vm::VynVM,
+};
+
+impl VynVM {
+    #[inline]
+    pub(crate) fn string_length(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let src = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        let str_idx = self.string_register(src, inst_pos)?;
+        let length = self.get_string(str_idx).chars().count();
+
+        self.set_register(dest, RuntimeValue::IntegerLiteral(length as i64));
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn string_index(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let string_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let index_reg = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let str_idx = self.string_register(string_reg, inst_pos)?;
+        let index = self.int_register(index_reg, inst_pos)?;
+        let string = self.get_string(str_idx);
+
+        let character = usize::try_from(index)
+            .ok()
+            .and_then(|index| string.chars().nth(index));
+
+        let Some(character) = character else {
+            return Err(VynError::IndexOutOfBounds {
+                size: string.chars().count(),
+                idx: index,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        };
+
+        let idx = self.intern_string(character.to_string());
+        self.set_register(dest, RuntimeValue::StringLiteral(idx));
+        Ok(())
+    }
+
+    /*
+     * Copies the characters in [start, end) of a string into a new string
+     * */
+    #[inline]
+    pub(crate) fn string_slice(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let string_reg = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let start_reg = read_uint8(&self.instructions, self.ip + 3) as usize;
+        let end_reg = read_uint8(&self.instructions, self.ip + 4) as usize;
+        self.ip += 4;
+
+        let str_idx = self.string_register(string_reg, inst_pos)?;
+        let start = self.int_register(start_reg, inst_pos)?;
+        let end = self.int_register(end_reg, inst_pos)?;
+        let string = self.get_string(str_idx);
+        let size = string.chars().count();
+
+        if start < 0 || start > end || end as usize > size {
+            return Err(VynError::SliceOutOfBounds {
+                size,
+                start,
+                end,
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        let slice: String = string
+            .chars()
+            .skip(start as usize)
+            .take((end - start) as usize)
+            .collect();
+
+        let idx = self.intern_string(slice);
+        self.set_register(dest, RuntimeValue::StringLiteral(idx));
+        Ok(())
+    }
+
+    /*
+     * Writes any value as text, the way `stdout#` prints it
+     * */
+    #[inline]
+    pub(crate) fn value_to_string(&mut self) -> Result<(), VynError> {
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let src = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        let value = self.get_register(src);
+        let mut text = Vec::new();
+        value
+            .write_to(&mut text, self.strings.as_slice(), &self.heap_table)
+            .unwrap();
+
+        // Every piece written is either valid UTF-8 or ASCII
+        let idx = self.intern_string(String::from_utf8(text).unwrap());
+        self.set_register(dest, RuntimeValue::StringLiteral(idx));
+        Ok(())
+    }
+
+    /*
+     * Orders strings by comparing them character by character
+     * */
+    #[inline]
+    pub(crate) fn compare_string(&mut self, opcode: u8) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let left_reg_idx = read_uint8(&self.instructions, self.ip + 2) as usize;
+        let right_reg_idx = read_uint8(&self.instructions, self.ip + 3) as usize;
+        self.ip += 3;
+
+        let left_str_idx = self.string_register(left_reg_idx, inst_pos)?;
+        let right_str_idx = self.string_register(right_reg_idx, inst_pos)?;
+
+        let l = self.get_string(left_str_idx);
+        let r = self.get_string(right_str_idx);
+        let res = match opcode {
+            OpCode::LESS_STRING => l < r,
+            OpCode::LESS_EQUAL_STRING => l <= r,
+            OpCode::GREATER_STRING => l > r,
+            OpCode::GREATER_EQUAL_STRING => l >= r,
+
+            _ => unreachable!("Invalid compare_string opcode"),
+        };
+
+        self.set_register(dest, self.runtime_bool(res));
+        Ok(())
+    }
+}
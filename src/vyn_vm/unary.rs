@@ -6,25 +6,74 @@ use crate::{
 impl VynVM {
     #[inline]
     pub(crate) fn negate_int(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let src = read_uint8(&self.instructions, self.ip + 2) as usize;
         self.ip += 2;
 
-        let value = self.get_register(src).as_int().unwrap();
-        self.set_register(dest, RuntimeValue::IntegerLiteral(-value));
+        let value = self.int_register(src, inst_pos)?;
+
+        // -i64::MIN is the only negation that overflows
+        let negated = if self.wrapping_arithmetic {
+            value.wrapping_neg()
+        } else {
+            value.checked_neg().ok_or_else(|| VynError::IntegerOverflow {
+                expression: format!("-({})", value),
+                span: self.debug_info.get_span(inst_pos),
+            })?
+        };
+
+        self.set_register(dest, RuntimeValue::IntegerLiteral(negated));
 
         Ok(())
     }
 
     #[inline]
     pub(crate) fn negate_float(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
         let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
         let src = read_uint8(&self.instructions, self.ip + 2) as usize;
         self.ip += 2;
 
-        let value = self.get_register(src).as_float().unwrap();
+        let value = self.float_register(src, inst_pos)?;
         self.set_register(dest, RuntimeValue::FloatLiteral(-value));
 
         Ok(())
     }
+
+    #[inline]
+    pub(crate) fn int_to_float(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let src = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        // Magnitudes above 2^53 round to the nearest representable Float
+        let value = self.int_register(src, inst_pos)?;
+        self.set_register(dest, RuntimeValue::FloatLiteral(value as f64));
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn float_to_int(&mut self) -> Result<(), VynError> {
+        let inst_pos = self.ip;
+        let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+        let src = read_uint8(&self.instructions, self.ip + 2) as usize;
+        self.ip += 2;
+
+        let value = self.float_register(src, inst_pos)?;
+
+        // Truncates toward zero, `i64::MAX as f64` is 2^63 which is already out of range
+        if !value.is_finite() || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+            return Err(VynError::InvalidConversion {
+                value: value.to_string(),
+                span: self.debug_info.get_span(inst_pos),
+            });
+        }
+
+        self.set_register(dest, RuntimeValue::IntegerLiteral(value as i64));
+
+        Ok(())
+    }
 }
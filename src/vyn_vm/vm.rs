@@ -5,10 +5,11 @@ use std::{
 };
 
 use crate::{
-    bytecode::bytecode::{Instructions, OpCode, ToOpcode, read_uint8, read_uint16},
+    bytecode::bytecode::{Instructions, OpCode, ToOpcode, read_uint8, read_uint16, read_uint32},
     compiler::{compiler::Bytecode, debug_info::DebugInfo},
     error_handler::errors::VynError,
-    runtime_value::values::RuntimeValue,
+    runtime_value::values::{HeapObject, RuntimeValue},
+    vyn_vm::{call::CallFrame, string_heap::StringHeap},
 };
 
 // Singletons for common values
@@ -17,15 +18,34 @@ pub const TRUE: RuntimeValue = RuntimeValue::BooleanLiteral(true);
 pub const FALSE: RuntimeValue = RuntimeValue::BooleanLiteral(false);
 
 pub const MAX_REGISTERS: u8 = 255;
-type Registers = [RuntimeValue; MAX_REGISTERS as usize];
+
+// Limits the compiler keeps to and the verifier enforces, so a `.vync` file
+// can't make the VM allocate without bound
+pub const MAX_ARRAY_LENGTH: usize = 1 << 24;
+pub const MAX_GLOBALS: usize = 1 << 20;
+pub const MAX_SPILL_SLOTS: usize = 1 << 12;
 
 pub struct VynVM {
-    // Registers store actual RuntimeValues
-    pub(crate) registers: Registers,
+    // Registers store actual RuntimeValues, every call frame
+    // addresses its own window starting at `base`
+    pub(crate) registers: Vec<RuntimeValue>,
+    pub(crate) base: usize,
+    pub(crate) frames: Vec<CallFrame>,
+    // Values the register allocator spilled, every call frame
+    // addresses its own slots starting at `slot_base`
+    pub(crate) slots: Vec<RuntimeValue>,
+    pub(crate) slot_base: usize,
+    // Top-level variables, None until their declaration runs
+    pub(crate) globals: Vec<Option<RuntimeValue>>,
     // Constant pool
     pub(crate) constants: Vec<RuntimeValue>,
 
-    pub(crate) string_table: Vec<String>,
+    // Strings referenced by RuntimeValue::StringLiteral
+    pub(crate) strings: StringHeap,
+    // Arrays and sequences referenced by RuntimeValue::{Array,Sequence}Literal
+    pub(crate) heap_table: Vec<HeapObject>,
+    // Per heap object, set once it is reachable from a `let` binding
+    pub(crate) frozen: Vec<bool>,
     // Program bytecode
     pub(crate) instructions: Instructions,
     // Instruction pointer
@@ -33,6 +53,12 @@ pub struct VynVM {
     // Instruction spans
     pub(crate) debug_info: DebugInfo,
 
+    // Wrap integer arithmetic on overflow instead of raising an error
+    pub(crate) wrapping_arithmetic: bool,
+
+    // When the VM was created, `clock()` counts from here
+    pub(crate) started: Instant,
+
     // for debugging
     runtime_duration: Duration,
 }
@@ -40,16 +66,46 @@ pub struct VynVM {
 impl VynVM {
     pub fn new(bytecode: &mut Bytecode) -> Self {
         Self {
-            registers: [NIL; MAX_REGISTERS as usize],
+            registers: vec![NIL; MAX_REGISTERS as usize],
+            base: 0,
+            frames: Vec::new(),
+            slots: Vec::new(),
+            slot_base: 0,
+            globals: Vec::new(),
             constants: mem::take(&mut bytecode.constants),
-            string_table: mem::take(&mut bytecode.string_table),
+            strings: StringHeap::new(mem::take(&mut bytecode.string_table)),
+            heap_table: Vec::new(),
+            frozen: Vec::new(),
             instructions: mem::take(&mut bytecode.instructions),
             debug_info: mem::take(&mut bytecode.debug_info),
             ip: 0,
+            wrapping_arithmetic: false,
+            started: Instant::now(),
             runtime_duration: Duration::new(0, 0),
         }
     }
 
+    /*
+     * Replaces the program while keeping globals and the heap
+     *
+     * Used by the REPL, whose inputs read the variables the previous
+     * inputs declared
+     *
+     * -- Arguments: [&mut self], bytecode - next program to execute
+     * -- Return value: void
+     * */
+    pub fn load_program(&mut self, bytecode: &Bytecode) {
+        self.constants = bytecode.constants.clone();
+        self.strings.load_constants(bytecode.string_table.clone());
+        self.instructions = bytecode.instructions.clone();
+        self.debug_info = bytecode.debug_info.clone();
+        self.ip = 0;
+        self.base = 0;
+        self.frames.clear();
+        self.slots.clear();
+        self.slot_base = 0;
+    }
+
     pub fn execute(&mut self) -> Result<(), VynError> {
         let runtime_dur_start = Instant::now();
         loop {
@@ -70,6 +126,12 @@ impl VynVM {
                 OpCode::LOAD_STRING => {
                     self.load_string()?;
                 }
+                OpCode::LOAD_CONST_INT_WIDE | OpCode::LOAD_CONST_FLOAT_WIDE => {
+                    self.load_constant_wide()?;
+                }
+                OpCode::LOAD_STRING_WIDE => {
+                    self.load_string_wide()?;
+                }
                 OpCode::LOAD_NIL => {
                     self.load_static(NIL)?;
                 }
@@ -115,6 +177,18 @@ impl VynVM {
                 OpCode::CONCAT_STRING => {
                     self.concat_string()?;
                 }
+                OpCode::STRING_LENGTH => {
+                    self.string_length()?;
+                }
+                OpCode::STRING_INDEX => {
+                    self.string_index()?;
+                }
+                OpCode::STRING_SLICE => {
+                    self.string_slice()?;
+                }
+                OpCode::TO_STRING => {
+                    self.value_to_string()?;
+                }
 
                 OpCode::NEGATE_INT => {
                     self.negate_int()?;
@@ -125,6 +199,12 @@ impl VynVM {
                 OpCode::NOT => {
                     self.bool_not()?;
                 }
+                OpCode::INT_TO_FLOAT => {
+                    self.int_to_float()?;
+                }
+                OpCode::FLOAT_TO_INT => {
+                    self.float_to_int()?;
+                }
 
                 OpCode::LESS_INT => {
                     self.compare_int(opcode)?;
@@ -152,6 +232,19 @@ impl VynVM {
                     self.compare_float(opcode)?;
                 }
 
+                OpCode::LESS_STRING => {
+                    self.compare_string(opcode)?;
+                }
+                OpCode::LESS_EQUAL_STRING => {
+                    self.compare_string(opcode)?;
+                }
+                OpCode::GREATER_STRING => {
+                    self.compare_string(opcode)?;
+                }
+                OpCode::GREATER_EQUAL_STRING => {
+                    self.compare_string(opcode)?;
+                }
+
                 OpCode::EQUAL => {
                     self.compare_equality(opcode)?;
                 }
@@ -177,6 +270,72 @@ impl VynVM {
                     self.ip = jump_idx as usize;
                     continue;
                 }
+
+                OpCode::JUMP_IF_FALSE_WIDE => {
+                    let cond_reg_idx = read_uint8(&self.instructions, self.ip + 1);
+                    let jump_idx = read_uint32(&self.instructions, self.ip + 2);
+                    self.ip += 5;
+
+                    let cond_reg = self.get_register(cond_reg_idx as usize);
+
+                    if !self.is_truthy(cond_reg) {
+                        self.ip = jump_idx as usize;
+                        continue;
+                    }
+                }
+
+                OpCode::JUMP_UNCOND_WIDE => {
+                    let jump_idx = read_uint32(&self.instructions, self.ip + 1);
+                    self.ip = jump_idx as usize;
+                    continue;
+                }
+                OpCode::STORE_GLOBAL => {
+                    let index = read_uint16(&self.instructions, self.ip + 1) as usize;
+                    let src = read_uint8(&self.instructions, self.ip + 3) as usize;
+                    self.ip += 3;
+
+                    self.store_global(index, src);
+                }
+                OpCode::STORE_GLOBAL_WIDE => {
+                    let index = read_uint32(&self.instructions, self.ip + 1) as usize;
+                    let src = read_uint8(&self.instructions, self.ip + 5) as usize;
+                    self.ip += 5;
+
+                    self.store_global(index, src);
+                }
+                OpCode::LOAD_GLOBAL => {
+                    let inst_pos = self.ip;
+                    let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+                    let index = read_uint16(&self.instructions, self.ip + 2) as usize;
+                    self.ip += 3;
+
+                    self.load_global(dest, index, inst_pos)?;
+                }
+                OpCode::LOAD_GLOBAL_WIDE => {
+                    let inst_pos = self.ip;
+                    let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+                    let index = read_uint32(&self.instructions, self.ip + 2) as usize;
+                    self.ip += 5;
+
+                    self.load_global(dest, index, inst_pos)?;
+                }
+
+                OpCode::SPILL => {
+                    let slot = read_uint16(&self.instructions, self.ip + 1) as usize;
+                    let src = read_uint8(&self.instructions, self.ip + 3) as usize;
+                    self.ip += 3;
+
+                    let value = self.get_register(src);
+                    self.set_slot(slot, value);
+                }
+                OpCode::RELOAD => {
+                    let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
+                    let slot = read_uint16(&self.instructions, self.ip + 2) as usize;
+                    self.ip += 3;
+
+                    let value = self.get_slot(slot);
+                    self.set_register(dest, value);
+                }
                 OpCode::MOVE => {
                     let dest = read_uint8(&self.instructions, self.ip + 1) as usize;
                     let src = read_uint8(&self.instructions, self.ip + 2) as usize;
@@ -186,7 +345,66 @@ impl VynVM {
                     self.set_register(dest, value);
                 }
 
+                OpCode::ARRAY_NEW_FIXED => {
+                    self.array_new_fixed()?;
+                }
+                OpCode::ARRAY_NEW_DYNAMIC => {
+                    self.array_new_dynamic()?;
+                }
+                OpCode::ARRAY_SET => {
+                    self.array_set()?;
+                }
+                OpCode::ARRAY_SET_REG => {
+                    self.array_set_reg()?;
+                }
+                OpCode::ARRAY_GET => {
+                    self.array_get()?;
+                }
+                OpCode::ARRAY_PUSH => {
+                    self.array_push()?;
+                }
+
+                OpCode::STRUCT_NEW => {
+                    self.struct_new()?;
+                }
+                OpCode::STRUCT_GET => {
+                    self.struct_get()?;
+                }
+                OpCode::STRUCT_SET => {
+                    self.struct_set()?;
+                }
+
+                OpCode::ENUM_NEW => {
+                    self.enum_new()?;
+                }
+                OpCode::ENUM_TAG => {
+                    self.enum_tag()?;
+                }
+
+                OpCode::CHECK_STEP => {
+                    self.check_step()?;
+                }
+                OpCode::FREEZE => {
+                    self.freeze()?;
+                }
+
+                OpCode::CALL => {
+                    self.call()?;
+                    continue;
+                }
+                OpCode::CALL_WIDE => {
+                    self.call_wide()?;
+                    continue;
+                }
+                OpCode::CALL_NATIVE => {
+                    self.call_native()?;
+                }
+                OpCode::RETURN => {
+                    self.return_from_call()?;
+                }
+
                 OpCode::LOG_ADDR => {
+                    let inst_pos = self.ip;
                     let src = read_uint8(&self.instructions, self.ip + 1) as usize;
                     self.ip += 1;
 
@@ -194,8 +412,16 @@ impl VynVM {
                     let stdout = io::stdout();
                     let mut out = stdout.lock();
 
-                    value.write_to(&mut out, &self.string_table).unwrap();
-                    out.write_all(b"\n").unwrap();
+                    // Fails once whatever reads our output goes away
+                    value
+                        .write_to(&mut out, self.strings.as_slice(), &self.heap_table)
+                        .and_then(|_| out.write_all(b"\n"))
+                        .map_err(|error| VynError::IoError {
+                            action: "write to".to_string(),
+                            target: "<stdout>".to_string(),
+                            reason: error.to_string(),
+                            span: self.debug_info.get_span(inst_pos),
+                        })?;
                 }
 
                 _ => unreachable!("Unknown opcode byte {}", opcode.to_opcode()),
@@ -207,17 +433,103 @@ impl VynVM {
 
     #[inline(always)]
     pub(crate) fn set_register(&mut self, reg: usize, value: RuntimeValue) {
-        self.registers[reg] = value
+        self.registers[self.base + reg] = value
     }
 
     #[inline(always)]
     pub(crate) fn get_register(&self, reg: usize) -> RuntimeValue {
-        self.registers[reg]
+        self.registers[self.base + reg]
+    }
+
+    /*
+     * Typed register reads
+     *
+     * The compiler only hands an instruction registers of the type it
+     * expects, the verifier can't tell for a `.vync` file so a mismatch is
+     * reported here instead of panicking
+     * */
+    #[inline(always)]
+    pub(crate) fn int_register(&self, reg: usize, inst_pos: usize) -> Result<i64, VynError> {
+        self.get_register(reg)
+            .as_int()
+            .ok_or_else(|| self.wrong_register_type(reg, "an integer", inst_pos))
+    }
+
+    #[inline(always)]
+    pub(crate) fn float_register(&self, reg: usize, inst_pos: usize) -> Result<f64, VynError> {
+        self.get_register(reg)
+            .as_float()
+            .ok_or_else(|| self.wrong_register_type(reg, "a float", inst_pos))
+    }
+
+    #[inline(always)]
+    pub(crate) fn string_register(&self, reg: usize, inst_pos: usize) -> Result<usize, VynError> {
+        self.get_register(reg)
+            .as_string_index()
+            .ok_or_else(|| self.wrong_register_type(reg, "a string", inst_pos))
+    }
+
+    #[cold]
+    pub(crate) fn wrong_register_type(
+        &self,
+        reg: usize,
+        expected: &str,
+        inst_pos: usize,
+    ) -> VynError {
+        let found = self.get_register(reg).get_type();
+
+        VynError::InvalidBytecode {
+            offset: inst_pos,
+            reason: format!("r{reg} should hold {expected}, found {}", found.to_string()),
+            span: self.debug_info.get_span(inst_pos),
+        }
+    }
+
+    #[inline(always)]
+    fn store_global(&mut self, index: usize, src: usize) {
+        if index >= self.globals.len() {
+            self.globals.resize(index + 1, None);
+        }
+        self.globals[index] = Some(self.get_register(src));
+    }
+
+    #[inline(always)]
+    fn load_global(&mut self, dest: usize, index: usize, inst_pos: usize) -> Result<(), VynError> {
+        // A function can be called before the globals it reads are declared
+        let Some(value) = self.globals.get(index).copied().flatten() else {
+            return Err(VynError::UninitializedGlobal {
+                span: self.debug_info.get_span(inst_pos),
+            });
+        };
+
+        self.set_register(dest, value);
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_slot(&mut self, slot: usize, value: RuntimeValue) {
+        let idx = self.slot_base + slot;
+        if idx >= self.slots.len() {
+            self.slots.resize(idx + 1, NIL);
+        }
+        self.slots[idx] = value
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_slot(&self, slot: usize) -> RuntimeValue {
+        // A slot that was never spilled to reads as nil
+        self.slots
+            .get(self.slot_base + slot)
+            .copied()
+            .unwrap_or(NIL)
     }
 
     pub(crate) fn intern_string(&mut self, str: String) -> usize {
-        self.string_table.push(str);
-        self.string_table.len() - 1
+        if self.strings.needs_collection() {
+            self.collect_strings();
+        }
+
+        self.strings.alloc(str)
     }
 
     // For debugging
@@ -227,7 +539,7 @@ impl VynVM {
         // for now since its just for debugging
         let mut occupied = Vec::with_capacity(MAX_REGISTERS as usize);
 
-        for reg in &self.registers {
+        for reg in &self.registers[..MAX_REGISTERS as usize] {
             if reg.is_nil() {
                 continue;
             }
@@ -238,11 +550,19 @@ impl VynVM {
         occupied
     }
 
+    /*
+     * Switches integer arithmetic between checked (the default, overflow is a
+     * runtime error) and two's-complement wrapping
+     * */
+    pub fn set_wrapping_arithmetic(&mut self, enabled: bool) {
+        self.wrapping_arithmetic = enabled;
+    }
+
     pub fn get_runtime_exec_dur(&self) -> Duration {
         self.runtime_duration
     }
 
     pub fn get_string(&self, idx: usize) -> &str {
-        self.string_table.get(idx).unwrap()
+        self.strings.get(idx)
     }
 }